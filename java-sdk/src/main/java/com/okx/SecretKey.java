@@ -0,0 +1,127 @@
+package com.okx;
+
+import java.lang.ref.Cleaner;
+
+/**
+ * A private key parsed once by the native library and reused for many signatures.
+ * The key never comes back to java, it is wiped from native memory on {@link #close()}, or once
+ * the secret key is garbage collected when it was never closed.
+ * <p>
+ * The unifiedSign* functions of {@link ZKDEX} have no secret key variants, {@link #signAny} signs
+ * every unified transaction, and the orders a key owns in a trade.
+ */
+public class SecretKey implements AutoCloseable {
+    private static final Cleaner CLEANER = Cleaner.create();
+
+    private final long handle;
+    private final Cleaner.Cleanable cleanable;
+
+    // frees the native key, it must not reference the SecretKey or it would never be collected
+    private static class Free implements Runnable {
+        private final long handle;
+
+        Free(long handle) {
+            this.handle = handle;
+        }
+
+        @Override
+        public void run() {
+            ZKDEX.secretKeyFree(handle);
+        }
+    }
+
+    /**
+     * @param priKey private key
+     * @throws Exception
+     */
+    public SecretKey(String priKey) throws Exception {
+        this(ZKDEX.secretKeyNew(priKey));
+    }
+
+    private SecretKey(long handle) {
+        this.handle = handle;
+        this.cleanable = CLEANER.register(this, new Free(handle));
+    }
+
+    /**
+     * decrypt the key of a keystore
+     *
+     * @param keystore json string of the keystore
+     * @param password the password protecting the key
+     * @return the secret key
+     * @throws Exception
+     */
+    public static SecretKey fromKeystore(String keystore, String password) throws Exception {
+        return new SecretKey(ZKDEX.secretKeyFromKeystore(keystore, password));
+    }
+
+    /**
+     * @return packed public key string
+     * @throws Exception
+     */
+    public String publicKey() throws Exception {
+        return ZKDEX.secretKeyPublicKey(handle);
+    }
+
+    /**
+     * choose how the nonces of the following signatures are drawn
+     *
+     * @param mode "deterministic", the default, or "hedged" to mix fresh randomness into each nonce
+     * @throws Exception
+     */
+    public void setNonceMode(String mode) throws Exception {
+        ZKDEX.secretKeySetNonceMode(handle, mode);
+    }
+
+    /**
+     * check every signature against the public key before returning it, off by default as it about
+     * doubles the cost of signing
+     *
+     * @param verify true to check the signatures, a signature that fails throws INVALID_SIGNATURE
+     * @throws Exception
+     */
+    public void setVerifySignatures(boolean verify) throws Exception {
+        ZKDEX.secretKeySetVerifySignatures(handle, verify);
+    }
+
+    /**
+     * sign a msg, like {@link ZKDEX#sign}
+     *
+     * @param msg hash of msg
+     * @return json string of the signature
+     * @throws Exception
+     */
+    public String sign(String msg) throws Exception {
+        return ZKDEX.secretKeySign(handle, msg);
+    }
+
+    /**
+     * sign a unified transaction, like {@link ZKDEX#signAny}
+     *
+     * @param json unified transaction
+     * @return json string of the signature
+     * @throws Exception
+     */
+    public String signAny(String json) throws Exception {
+        return ZKDEX.secretKeySignAny(handle, json);
+    }
+
+    /**
+     * sign a json array of unified transactions, like {@link ZKDEX#batchSignAny}
+     *
+     * @param json json array of unified transactions
+     * @return json array with the signature or the error of each transaction
+     * @throws Exception
+     */
+    public String batchSignAny(String json) throws Exception {
+        return ZKDEX.secretKeyBatchSignAny(handle, json);
+    }
+
+    /**
+     * wipe the key, the secret key cannot sign afterwards, closing it again does nothing
+     */
+    @Override
+    public void close() {
+        cleanable.clean();
+    }
+}
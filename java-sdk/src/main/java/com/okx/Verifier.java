@@ -0,0 +1,57 @@
+package com.okx;
+
+/**
+ * Verifies signatures with a cache of the public keys it has seen, for services that check many
+ * signatures of the same keys. The least recently used key is dropped when the cache is full.
+ * The cache lives in native memory until {@link #close()}.
+ */
+public class Verifier implements AutoCloseable {
+    private long handle;
+
+    /**
+     * @param capacity the number of public keys to cache, 0 caches none
+     */
+    public Verifier(int capacity) {
+        this.handle = ZKDEX.verifierNew(capacity);
+    }
+
+    /**
+     * verify a signature, like {@link ZKDEX#verifySignature}
+     *
+     * @param sigR   r of signature
+     * @param sigS   s of signature
+     * @param pubKey packed public key
+     * @param msg    msg
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public boolean verifySignature(String sigR, String sigS, String pubKey, String msg) throws Exception {
+        return ZKDEX.verifierVerifySignature(handle, sigR, sigS, pubKey, msg);
+    }
+
+    /**
+     * @return json of the cache counters: hits, misses, len, tables and capacity
+     * @throws Exception
+     */
+    public String stats() throws Exception {
+        return ZKDEX.verifierStats(handle);
+    }
+
+    /**
+     * drop the cached keys and reset the counters
+     *
+     * @throws Exception
+     */
+    public void clear() throws Exception {
+        ZKDEX.verifierClear(handle);
+    }
+
+    /**
+     * free the cache, the verifier cannot be used afterwards
+     */
+    @Override
+    public void close() {
+        ZKDEX.verifierFree(handle);
+        handle = 0;
+    }
+}
@@ -147,6 +147,16 @@ public class ZKDEX {
      */
     public static native String signWithdraw(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signWithdraw} without the field range checks
+     *
+     * @param json   json of Withdraw
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signWithdrawUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a Transfer
      *
@@ -157,6 +167,16 @@ public class ZKDEX {
      */
     public static native String signTransfer(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signTransfer} without the field range checks
+     *
+     * @param json   json of Transfer
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signTransferUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a LimitOrder
      *
@@ -167,6 +187,16 @@ public class ZKDEX {
      */
     public static native String signLimitOrder(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signLimitOrder} without the field range checks
+     *
+     * @param json   json of LimitOrder
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signLimitOrderUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a Liquidate
      *
@@ -177,6 +207,16 @@ public class ZKDEX {
      */
     public static native String signLiquidate(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signLiquidate} without the field range checks
+     *
+     * @param json   json of Liquidate
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signLiquidateUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a SignedOraclePrice
      *
@@ -187,6 +227,16 @@ public class ZKDEX {
      */
     public static native String signSignedOraclePrice(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signSignedOraclePrice} without the field range checks
+     *
+     * @param json   json of SignedOraclePrice
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signSignedOraclePriceUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Withdraw
      *
@@ -205,6 +255,76 @@ public class ZKDEX {
      */
     public static native String hashTransfer(String json) throws Exception;
 
+    /**
+     * verify a signature over a Transfer, with or without fee
+     * @param json json of Transfer
+     * @param sigR r of signature
+     * @param sigS s of signature
+     * @param pubKey public key of the sender
+     * @return whether the sender signed the Transfer
+     * @throws Exception
+     */
+    public static native boolean verifyTransfer(String json, String sigR, String sigS, String pubKey) throws Exception;
+
+    /**
+     * sign a ConditionalTransfer, a Transfer with fact_registry_address and fact that only executes once
+     * the fact is registered on L1
+     *
+     * @param json   json of ConditionalTransfer
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signConditionalTransfer(String json, String priKey) throws Exception;
+
+    /**
+     * same as {@link #signConditionalTransfer} without the field range checks
+     *
+     * @param json   json of ConditionalTransfer
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signConditionalTransferUnchecked(String json, String priKey) throws Exception;
+
+    /**
+     * hash a ConditionalTransfer
+     *
+     * @param json json of ConditionalTransfer
+     * @return hash
+     * @throws Exception
+     */
+    public static native String hashConditionalTransfer(String json) throws Exception;
+
+    /**
+     * verify a signature over a ConditionalTransfer
+     * @param json json of ConditionalTransfer
+     * @param sigR r of signature
+     * @param sigS s of signature
+     * @param pubKey public key of the sender
+     * @return whether the sender signed the ConditionalTransfer
+     * @throws Exception
+     */
+    public static native boolean verifyConditionalTransfer(String json, String sigR, String sigS, String pubKey) throws Exception;
+
+    /**
+     * poseidon hash of field elements, the hash every transaction hash is built on
+     *
+     * @param json json array of field elements in hex with 0x prefix, each below the field modulus
+     * @return hash
+     * @throws Exception
+     */
+    public static native String poseidonHashFieldElements(String json) throws Exception;
+
+    /**
+     * poseidon hash of u64 limbs, each 4 limbs make one field element
+     *
+     * @param json json array of u64 limbs as decimal strings
+     * @return hash
+     * @throws Exception
+     */
+    public static native String poseidonHashU64Limbs(String json) throws Exception;
+
     /**
      * hash a LimitOrder
      *
@@ -274,6 +394,83 @@ public class ZKDEX {
      */
     public static native String privateKeyFromSeed(String seed) throws Exception;
 
+    /**
+     * generate a new private key from the secure random source of the os
+     *
+     * @return json string of private_key and the packed public_key
+     * @throws Exception
+     */
+    public static native String generatePrivateKey() throws Exception;
+
+    /**
+     * derive the private key of a sub-account from the master seed
+     *
+     * @param seed master seed
+     * @param path derivation path like m/7'/3', ' or h marks a hardened index, a key handed out
+     *             should sit at a hardened index, a normal one reveals its parent
+     * @return private key string
+     * @throws Exception
+     */
+    public static native String derivePrivateKey(String seed, String path) throws Exception;
+
+    /**
+     * derive the public key and chain code of a sub-account from the master seed
+     *
+     * @param seed master seed
+     * @param path derivation path like m/7'/3, ' or h marks a hardened index
+     * @return json string of public_key and chain_code
+     * @throws Exception
+     */
+    public static native String deriveExtendedPublicKey(String seed, String path) throws Exception;
+
+    /**
+     * derive a child public key below an extended public key, without the seed
+     *
+     * @param publicKey packed public key
+     * @param chainCode 32 bytes chain code in hex
+     * @param path      derivation path like m/3/0, hardened indexes are not allowed
+     * @return packed public key string
+     * @throws Exception
+     */
+    public static native String derivePublicKey(String publicKey, String chainCode, String path) throws Exception;
+
+    /**
+     * encrypt a private key into a password protected keystore
+     *
+     * @param privateKey private key
+     * @param password   the password protecting the key
+     * @return json string of the keystore
+     * @throws Exception
+     */
+    public static native String encryptKeystore(String privateKey, String password) throws Exception;
+
+    /**
+     * recover the private key of a keystore
+     *
+     * @param keystore json string of the keystore
+     * @param password the password protecting the key
+     * @return private key string
+     * @throws Exception
+     */
+    public static native String decryptKeystore(String keystore, String password) throws Exception;
+
+    /**
+     * the message the wallet signs with personal_sign to onboard
+     *
+     * @return onboarding message text
+     */
+    public static native String onboardingMessage();
+
+    /**
+     * derive the l2 key from the wallet signature of the onboarding message
+     *
+     * @param ethSignature 65 bytes signature in hex
+     * @param address      wallet address, the signature must recover to it
+     * @return json string of private_key and the packed public_key
+     * @throws Exception
+     */
+    public static native String l2KeyFromEthSignature(String ethSignature, String address) throws Exception;
+
     /**
      * check public key xw is on curve
      *
@@ -312,6 +509,16 @@ public class ZKDEX {
      */
     public static native String signSpotTransfer(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signSpotTransfer} without the field range checks
+     *
+     * @param json   json of spot Transfer
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signSpotTransferUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a spot withdrawal
      *
@@ -322,6 +529,16 @@ public class ZKDEX {
      */
     public static native String signSpotWithdrawal(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signSpotWithdrawal} without the field range checks
+     *
+     * @param json   json of spot withdrawal
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signSpotWithdrawalUnchecked(String json, String priKey) throws Exception;
+
     /**
      * sign a spot limit order
      *
@@ -332,6 +549,16 @@ public class ZKDEX {
      */
     public static native String signSpotLimitOrder(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #signSpotLimitOrder} without the field range checks
+     *
+     * @param json   json of spot limit order
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String signSpotLimitOrderUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a spot Transfer
      *
@@ -368,6 +595,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignWithdrawal(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignWithdrawal} without the field range checks
+     * @param json json of transfer transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignWithdrawalUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Withdrawal transaction
      * @param json json of transfer transaction
@@ -385,6 +621,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignTransfer(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignTransfer} without the field range checks
+     * @param json json of transfer transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignTransferUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Transfer transaction
      * @param json json of transfer transaction
@@ -403,6 +648,16 @@ public class ZKDEX {
      */
     public static native String unifiedSignSpotTrade(String json, String priKeyA, String priKeyB) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignSpotTrade} without the field range checks
+     * @param json json of spot trade transaction
+     * @param priKeyA private key of account A
+     * @param priKeyB private key of account B
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignSpotTradeUnchecked(String json, String priKeyA, String priKeyB) throws Exception;
+
     /**
      * hash a SpotTrade transaction
      * @param json json of spot trade transaction
@@ -421,6 +676,16 @@ public class ZKDEX {
      */
     public static native String unifiedSignPerpetualTrade(String json, String priKeyA, String priKeyB) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignPerpetualTrade} without the field range checks
+     * @param json json of perpetual trade transaction
+     * @param priKeyA private key of account A
+     * @param priKeyB private key of account B
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignPerpetualTradeUnchecked(String json, String priKeyA, String priKeyB) throws Exception;
+
     /**
      * hash a PerpetualTrade transaction
      * @param json json of perpetual trade transaction
@@ -438,6 +703,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignOraclePrice(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignOraclePrice} without the field range checks
+     * @param json json of oracle price transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignOraclePriceUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a OraclePrice transaction
      * @param json json of oracle price transaction
@@ -455,6 +729,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignLiquidate(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignLiquidate} without the field range checks
+     * @param json json of liquidate transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignLiquidateUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Liquidate transaction
      * @param json json of liquidate transaction
@@ -472,6 +755,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignSpotLimitOrder(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignSpotLimitOrder} without the field range checks
+     * @param json json of Spot Limit Order transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignSpotLimitOrderUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Spot Limit Order transaction
      * @param json json of Spot Limit Order transaction
@@ -489,6 +781,15 @@ public class ZKDEX {
      */
     public static native String unifiedSignPerpetualLimitOrder(String json, String priKey) throws Exception;
 
+    /**
+     * same as {@link #unifiedSignPerpetualLimitOrder} without the field range checks
+     * @param json json of Spot Limit Order transaction
+     * @param priKey private key
+     * @return signature
+     * @throws Exception
+     */
+    public static native String unifiedSignPerpetualLimitOrderUnchecked(String json, String priKey) throws Exception;
+
     /**
      * hash a Perpetual Limit Order transaction
      * @param json json of Perpetual Limit Order transaction
@@ -497,4 +798,239 @@ public class ZKDEX {
      */
     public static native String unifiedHashPerpetualLimitOrder(String json) throws Exception;
 
+    /**
+     * sign any unified transaction, the transaction kind is taken from its "type" field
+     * @param json json of unified transaction
+     * @param priKey private key
+     * @return signature, a trade only carries the signatures of the orders owned by priKey
+     * @throws Exception when priKey owns neither the transaction nor any order of a trade
+     */
+    public static native String signAny(String json, String priKey) throws Exception;
+
+    /**
+     * same as {@link #signAny} without the field range checks
+     * @param json json of unified transaction
+     * @param priKey private key
+     * @return signature, a trade only carries the signatures of the orders owned by priKey
+     * @throws Exception
+     */
+    public static native String signAnyUnchecked(String json, String priKey) throws Exception;
+
+    /**
+     * hash any unified transaction, the transaction kind is taken from its "type" field
+     * @param json json of unified transaction
+     * @return hash, or json of hash_a and hash_b for a trade
+     * @throws Exception
+     */
+    public static native String hashAny(String json) throws Exception;
+
+    /**
+     * verify a signature over any unified transaction, the transaction kind is taken from its "type" field
+     * @param json json of unified transaction
+     * @param sigR r of signature
+     * @param sigS s of signature
+     * @param pubKey public key of the signer
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean verifyAny(String json, String sigR, String sigS, String pubKey) throws Exception;
+
+    /**
+     * verify the signature embedded in a withdrawal transaction against its own public key
+     * @param json json of withdrawal transaction with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifyWithdrawal(String json) throws Exception;
+
+    /**
+     * verify the signature embedded in a transfer transaction against its own public key
+     * @param json json of transfer transaction with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifyTransfer(String json) throws Exception;
+
+    /**
+     * verify the signature embedded in a oracle price against its own public key
+     * @param json json of oracle price with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifyOraclePrice(String json) throws Exception;
+
+    /**
+     * verify the signature embedded in a liquidate transaction against its own public key
+     * @param json json of liquidate transaction with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifyLiquidate(String json) throws Exception;
+
+    /**
+     * verify the signature embedded in a spot limit order against its own public key
+     * @param json json of spot limit order with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifySpotLimitOrder(String json) throws Exception;
+
+    /**
+     * verify the signature embedded in a perpetual limit order against its own public key
+     * @param json json of perpetual limit order with its "signature"
+     * @return whether the signature is valid
+     * @throws Exception
+     */
+    public static native boolean unifiedVerifyPerpetualLimitOrder(String json) throws Exception;
+
+    /**
+     * verify the signatures embedded in both orders of a spot trade
+     * @param json json of spot trade with the "signature" of each order
+     * @return json of the result of each order, {"party_a":bool,"party_b":bool}
+     * @throws Exception
+     */
+    public static native String unifiedVerifySpotTrade(String json) throws Exception;
+
+    /**
+     * verify the signatures embedded in both orders of a perpetual trade
+     * @param json json of perpetual trade with the "signature" of each order
+     * @return json of the result of each order, {"party_a":bool,"party_b":bool}
+     * @throws Exception
+     */
+    public static native String unifiedVerifyPerpetualTrade(String json) throws Exception;
+
+    /**
+     * sign a withdrawal transaction and attach the signature and hash to it
+     * @param json json of withdrawal transaction
+     * @param priKey private key
+     * @return json of the signed withdrawal transaction
+     * @throws Exception
+     */
+    public static native String unifiedSignWithdrawalEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign a transfer transaction and attach the signature and hash to it
+     * @param json json of transfer transaction
+     * @param priKey private key
+     * @return json of the signed transfer transaction
+     * @throws Exception
+     */
+    public static native String unifiedSignTransferEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign a oracle price and attach the signature and hash to it
+     * @param json json of oracle price
+     * @param priKey private key
+     * @return json of the signed oracle price
+     * @throws Exception
+     */
+    public static native String unifiedSignOraclePriceEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign a liquidate transaction and attach the signature and hash to it
+     * @param json json of liquidate transaction
+     * @param priKey private key
+     * @return json of the signed liquidate transaction
+     * @throws Exception
+     */
+    public static native String unifiedSignLiquidateEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign a spot limit order and attach the signature and hash to it
+     * @param json json of spot limit order
+     * @param priKey private key
+     * @return json of the signed spot limit order
+     * @throws Exception
+     */
+    public static native String unifiedSignSpotLimitOrderEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign a perpetual limit order and attach the signature and hash to it
+     * @param json json of perpetual limit order
+     * @param priKey private key
+     * @return json of the signed perpetual limit order
+     * @throws Exception
+     */
+    public static native String unifiedSignPerpetualLimitOrderEnvelope(String json, String priKey) throws Exception;
+
+    /**
+     * sign both orders of a spot trade and attach the signature and hash to each of them
+     * @param json json of spot trade transaction
+     * @param priKeyA private key of account A
+     * @param priKeyB private key of account B
+     * @return json of the signed spot trade transaction
+     * @throws Exception
+     */
+    public static native String unifiedSignSpotTradeEnvelope(String json, String priKeyA, String priKeyB) throws Exception;
+
+    /**
+     * sign both orders of a perpetual trade and attach the signature and hash to each of them
+     * @param json json of perpetual trade transaction
+     * @param priKeyA private key of account A
+     * @param priKeyB private key of account B
+     * @return json of the signed perpetual trade transaction
+     * @throws Exception
+     */
+    public static native String unifiedSignPerpetualTradeEnvelope(String json, String priKeyA, String priKeyB) throws Exception;
+
+    /**
+     * sign a json array of unified transactions, each one like {@link #signAny}
+     * @param json json array of unified transactions
+     * @param priKey private key
+     * @return json array with the signature of each transaction in input order, or {"error":{"code","message"}}
+     * for a transaction that failed
+     * @throws Exception
+     */
+    public static native String batchSignAny(String json, String priKey) throws Exception;
+
+    /**
+     * hash a json array of unified transactions, each one like {@link #hashAny}
+     * @param json json array of unified transactions
+     * @return json array with the hash of each transaction in input order, or {"error":{"code","message"}}
+     * for a transaction that failed
+     * @throws Exception
+     */
+    public static native String batchHashAny(String json) throws Exception;
+
+    /**
+     * verify a json array of signed hashes in one batch
+     * @param json json array of {"signature":{"r","s"},"public_key","hash"}
+     * @return json array with whether each signature is valid in input order, or {"error":{"code","message"}}
+     * for a malformed item
+     * @throws Exception
+     */
+    public static native String batchVerify(String json) throws Exception;
+
+    // handles of native secret keys, used through SecretKey
+
+    static native long secretKeyNew(String priKey) throws Exception;
+
+    static native long secretKeyFromKeystore(String keystore, String password) throws Exception;
+
+    static native void secretKeyFree(long handle);
+
+    static native void secretKeySetNonceMode(long handle, String mode) throws Exception;
+
+    static native void secretKeySetVerifySignatures(long handle, boolean verify) throws Exception;
+
+    static native String secretKeyPublicKey(long handle) throws Exception;
+
+    static native String secretKeySign(long handle, String msg) throws Exception;
+
+    static native String secretKeySignAny(long handle, String json) throws Exception;
+
+    static native String secretKeyBatchSignAny(long handle, String json) throws Exception;
+
+    // handles of native verifiers, used through Verifier
+
+    static native long verifierNew(int capacity);
+
+    static native void verifierFree(long handle);
+
+    static native boolean verifierVerifySignature(long handle, String sigR, String sigS, String pubKey, String msg) throws Exception;
+
+    static native String verifierStats(long handle) throws Exception;
+
+    static native void verifierClear(long handle) throws Exception;
+
 }
@@ -0,0 +1,20 @@
+package com.okx;
+
+/**
+ * Thrown by the native methods of {@link ZKDEX} when the input is rejected.
+ * The code is stable and one of MALFORMED_JSON, INVALID_PUBLIC_KEY, INVALID_PRIVATE_KEY,
+ * AMOUNT_OUT_OF_RANGE, INVALID_SIGNATURE, INVALID_HASH, INVALID_ARGUMENT, INVALID_TRANSACTION,
+ * INVALID_KEYSTORE or INVALID_FIELDS.
+ */
+public class ZKDEXException extends Exception {
+    private final String code;
+
+    public ZKDEXException(String code, String message) {
+        super(message);
+        this.code = code;
+    }
+
+    public String getCode() {
+        return code;
+    }
+}
@@ -1,9 +1,12 @@
 package com.okx;
 
 import com.alibaba.fastjson2.JSON;
+import com.alibaba.fastjson2.JSONArray;
+import com.alibaba.fastjson2.JSONObject;
 import org.junit.Test;
 
 import static org.junit.Assert.assertEquals;
+import static org.junit.Assert.assertNotEquals;
 
 public class ZKDEXTest {
     static String priKey = "0x028dd913a169cf3732c306959e9c2a66a0075663e54e086977ed71c61fd7c273";
@@ -78,16 +81,76 @@ public class ZKDEXTest {
 
     }
 
+    @Test
+    public void signWithdrawReportsErrorCode() throws Exception {
+        String json = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"position_id\":\"2\",\"amount\":\"3\",\"eth_address\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"asset_id\":\"0x1\"}";
+        try {
+            ZKDEX.signWithdraw(json, "0xzz");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_PRIVATE_KEY", e.getCode());
+        }
+
+        try {
+            ZKDEX.hashWithdraw("{\"nonce\":");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("MALFORMED_JSON", e.getCode());
+        }
+    }
+
     @Test
     public void signTransfer() throws Exception {
         String json = "{\"nonce\":\"0\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"0\",\"sender_position_id\":\"0\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"0\",\"amount\":\"0\",\"asset_id\":\"0xa\"}";
-        String sigStr = ZKDEX.signTransfer(json, priKey);
+        String sigStr = ZKDEX.signTransferUnchecked(json, priKey);
         Signature signature = JSON.parseObject(sigStr, Signature.class);
         Signature expectSig = new Signature("0xa5920612d2b265813f31ee169b9e96e89548bdd53e9f4541e53fcdb1205c9c9a", "0x0028bdb4cc8f9f70c6ad081c03d662599fe732c118f268e537da019e3b473a09");
         assertEquals(expectSig, signature);
 
         String hash = ZKDEX.hashTransfer(json);
         assert ZKDEX.verifySignature(signature.getR(), signature.getS(), "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa", pubKeyY, hash);
+
+        try {
+            ZKDEX.signTransfer(json, priKey);
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_FIELDS", e.getCode());
+        }
+    }
+
+    @Test
+    public void signTransferWithFee() throws Exception {
+        String json = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fee\":\"5\",\"fee_asset_id\":\"0xb\",\"fee_position_id\":\"4\"}";
+        Signature signature = JSON.parseObject(ZKDEX.signTransfer(json, priKey), Signature.class);
+        String hash = ZKDEX.hashTransfer(json);
+        assertEquals("0x18f326b750d7c82f4cb4a3b0fec1f21e704bd14c192566f45fbbfaeaefb1ef44", hash);
+        assert ZKDEX.verifySignature(signature.getR(), signature.getS(), pubKeyX, pubKeyY, hash);
+        assert ZKDEX.verifyTransfer(json, signature.getR(), signature.getS(), pubKeyX);
+        assert !ZKDEX.verifyTransfer(json.replace("\"fee\":\"5\"", "\"fee\":\"6\""), signature.getR(), signature.getS(), pubKeyX);
+        try {
+            ZKDEX.hashTransfer(json.replace("\"position_id\":\"4\",", ""));
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("MALFORMED_JSON", e.getCode());
+        }
+    }
+
+    @Test
+    public void signConditionalTransfer() throws Exception {
+        String json = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fact_registry_address\":\"0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb\",\"fact\":\"0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b\"}";
+        Signature signature = JSON.parseObject(ZKDEX.signConditionalTransfer(json, priKey), Signature.class);
+        String hash = ZKDEX.hashConditionalTransfer(json);
+        assertEquals("0x0e82549aeb0699bb64a2f9273ebaeec466b4866db5200a491b9704856ee76bb3", hash);
+        assertNotEquals(ZKDEX.hashTransfer(json), hash);
+        assert ZKDEX.verifySignature(signature.getR(), signature.getS(), pubKeyX, pubKeyY, hash);
+        assert ZKDEX.verifyConditionalTransfer(json, signature.getR(), signature.getS(), pubKeyX);
+        assert !ZKDEX.verifyConditionalTransfer(json, signature.getR(), signature.getS(), "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a");
+        try {
+            ZKDEX.hashConditionalTransfer(json.replace("0xB6aD5EfB", "0xB6aD"));
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("MALFORMED_JSON", e.getCode());
+        }
     }
 
     @Test(expected = java.lang.Exception.class)
@@ -374,6 +437,17 @@ public class ZKDEXTest {
         assertEquals(ret, true);
     }
 
+    @Test
+    public void isOnCurveRejectsSmallOrderKey() throws Exception {
+        String orderTwo = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        try {
+            ZKDEX.isOnCurve(orderTwo, "0x0");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_PUBLIC_KEY", e.getCode());
+        }
+    }
+
     @Test(expected = java.lang.Exception.class)
     public void isOnCurveWithNullParam() throws Exception {
         String x = "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa";
@@ -403,6 +477,71 @@ public class ZKDEXTest {
         assertEquals(priKey, "0x02aca28609503a6474ec0a115b8662dbf760b6da6109e17c757dbbd3835c93f9");
     }
 
+    @Test
+    public void generatePrivateKey() throws Exception {
+        JSONObject key = JSON.parseObject(ZKDEX.generatePrivateKey());
+        assertEquals(66, key.getString("private_key").length());
+        Publickey publickey = JSON.parseObject(ZKDEX.privateKeyToPublicKeyXY(key.getString("private_key")), Publickey.class);
+        assertEquals(publickey.getX(), key.getString("public_key"));
+        assertNotEquals(key.getString("private_key"), JSON.parseObject(ZKDEX.generatePrivateKey()).getString("private_key"));
+    }
+
+    @Test
+    public void deriveKeys() throws Exception {
+        String seed = "hello world good life 996 very nice";
+        String priKey = ZKDEX.derivePrivateKey(seed, "m/7'/3");
+        assertEquals("0x006dacb284fb197c484075cb03f14ba8be86c07c96ab08a50ebef9813278b33f", priKey);
+        Publickey publickey = JSON.parseObject(ZKDEX.privateKeyToPublicKeyXY(priKey), Publickey.class);
+        assertEquals("0x9826359c2ffc1bc0b602d89bcd1fc6448a8823792ea57e4c1dd8a8afec46df8f", publickey.getX());
+
+        JSONObject master = JSON.parseObject(ZKDEX.deriveExtendedPublicKey(seed, "m"));
+        assertEquals("0x5aa33b47fa41321555e4fd679ededc4bc9886a414930ff242f7a7a60fb5eb499", master.getString("chain_code"));
+        String child = ZKDEX.derivePublicKey(master.getString("public_key"), master.getString("chain_code"), "m/1/2");
+        assertEquals("0x1e50f110dfe5d4da873afd8ecb2527e13f26c34aca560f11c56914e1011677f4", child);
+
+        try {
+            ZKDEX.derivePublicKey(master.getString("public_key"), master.getString("chain_code"), "m/7'");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_ARGUMENT", e.getCode());
+        }
+    }
+
+    @Test
+    public void keystore() throws Exception {
+        String keystore = "{\"version\":1,\"public_key\":\"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a\",\"crypto\":{\"cipher\":\"aes-256-gcm\",\"cipherparams\":{\"nonce\":\"6465666768696a6b6c6d6e6f\"},\"ciphertext\":\"c24f0dd29173eefcf850b5d8d33a833f3e6442247314ce990b015063ccf09cef7b44b49fc0d07046c18033d5caec9e4c\",\"kdf\":\"scrypt\",\"kdfparams\":{\"dklen\":32,\"n\":1024,\"r\":8,\"p\":1,\"salt\":\"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\"}}}";
+        assertEquals("0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe", ZKDEX.decryptKeystore(keystore, "correct horse battery staple"));
+        try {
+            ZKDEX.decryptKeystore(keystore, "wrong password");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_KEYSTORE", e.getCode());
+        }
+
+        String priKey = "0x028dd913a169cf3732c306959e9c2a66a0075663e54e086977ed71c61fd7c273";
+        JSONObject encrypted = JSON.parseObject(ZKDEX.encryptKeystore(priKey, "password"));
+        assertEquals("0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa", encrypted.getString("public_key"));
+        assertEquals(priKey, ZKDEX.decryptKeystore(encrypted.toJSONString(), "password"));
+    }
+
+    @Test
+    public void l2KeyFromEthSignature() throws Exception {
+        assertEquals("Sign this message to create your zkDEX key. Only sign this message on a trusted site.", ZKDEX.onboardingMessage());
+        String ethSignature = "0xe95a2346431278a7ba90b34ed34cb6a3b54cecdcdb583f8ce1a130ba2bb08bf1127e20e256d7d3c5b98c460b1656f9b695e16d1163bbc50f4f94d4961fe537821b";
+        String address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+        JSONObject key = JSON.parseObject(ZKDEX.l2KeyFromEthSignature(ethSignature, address));
+        assertEquals("0x017dcf6e6b75b27c1a0bd215f839e4572e59702202ce28576e65b520182719a7", key.getString("private_key"));
+        Publickey publickey = JSON.parseObject(ZKDEX.privateKeyToPublicKeyXY(key.getString("private_key")), Publickey.class);
+        assertEquals(publickey.getX(), key.getString("public_key"));
+
+        try {
+            ZKDEX.l2KeyFromEthSignature(ethSignature, "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_SIGNATURE", e.getCode());
+        }
+    }
+
     @Test
     public void privateKeyToPublicKeyXY() throws Exception {
         String pri_key = "0x028dd913a169cf3732c306959e9c2a66a0075663e54e086977ed71c61fd7c273";
@@ -500,13 +639,20 @@ public class ZKDEXTest {
     @Test
     public void test_sign_spot_limit_order() throws Exception {
         String json = "{\"nonce\":\"0\",\"expiration_timestamp\":\"0\",\"public_key\":\"0x0daed291535086c7569618ec99b090c220ac63add8ab019690c3ef3b40ca970a\",\"amount_buy\":\"0\",\"amount_sell\":\"0\",\"amount_fee\":\"0\",\"asset_buy\":\"0x01\",\"asset_sell\":\"0x02\",\"position_id\":\"1\"}";
-        String sigStr = ZKDEX.signSpotLimitOrder(json, priKey);
+        String sigStr = ZKDEX.signSpotLimitOrderUnchecked(json, priKey);
         Signature signature = JSON.parseObject(sigStr, Signature.class);
         Signature expectSig = new Signature("0x01aabe43b11787a211f9960a2abd2de3667965c52b5ff23ac853a91ebfc9b6c2", "0x01ffebd7ab388ae453baa839f123116bdfac8b57931bbbc463cf8dfcfab6fc02");
         assertEquals(expectSig, signature);
 
         String hash = ZKDEX.hashSpotLimitOrder(json);
         assert ZKDEX.verifySignature(signature.getR(), signature.getS(), pubKeyX, pubKeyY, hash);
+
+        try {
+            ZKDEX.signSpotLimitOrder(json, priKey);
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_FIELDS", e.getCode());
+        }
     }
 
     @Test
@@ -550,6 +696,11 @@ public class ZKDEXTest {
 
         String hash = ZKDEX.unifiedHashWithdrawal(json);
         assert ZKDEX.verifySignature(signature.getR(), signature.getS(), pubKeyX, pubKeyY, hash);
+
+        JSONObject envelope = JSON.parseObject(ZKDEX.unifiedSignWithdrawalEnvelope(json, priKey));
+        assertEquals("UNIFIED_WITHDRAWAL", envelope.getString("type"));
+        assertEquals(expectSig, envelope.getObject("signature", Signature.class));
+        assertEquals(hash, envelope.getString("hash"));
     }
 
     @Test
@@ -772,4 +923,208 @@ public class ZKDEXTest {
         String hash = ZKDEX.unifiedHashPerpetualLimitOrder(json);
         assert ZKDEX.verifySignature(signature.getR(), signature.getS(), pubKeyX, pubKeyY, hash);
     }
-}
\ No newline at end of file
+
+    @Test
+    public void unifiedVerifyPerpetualTrade() throws Exception {
+        String json = """
+                {
+                    "party_a_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":true,
+                        "nonce":"1210484339",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"4805234",
+                        "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                    },
+                    "party_b_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":false,
+                        "nonce":"12104",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"48052349",
+                        "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+                    },
+                    "actual_a_fee":"87916620",
+                    "actual_b_fee":"-9309",
+                    "actual_collateral":"775817",
+                    "actual_synthetic":"1530808"
+                }
+                """;
+        JSONObject sig = JSON.parseObject(ZKDEX.unifiedSignPerpetualTrade(json, priKey, priKey));
+        JSONObject tx = JSON.parseObject(json);
+        tx.getJSONObject("party_a_order").put("signature", sig.getJSONObject("signature_a"));
+        tx.getJSONObject("party_b_order").put("signature", sig.getJSONObject("signature_b"));
+
+        JSONObject ret = JSON.parseObject(ZKDEX.unifiedVerifyPerpetualTrade(tx.toJSONString()));
+        assertEquals(true, ret.getBoolean("party_a"));
+        assertEquals(false, ret.getBoolean("party_b"));
+
+        assert ZKDEX.unifiedVerifyPerpetualLimitOrder(tx.getJSONObject("party_a_order").toJSONString());
+    }
+
+    @Test
+    public void signAny() throws Exception{
+        String json = """
+                {
+                         "type":"PERP_CROSS",
+                         "amount_collateral":"15334874",
+                         "amount_fee":"1767749",
+                         "amount_synthetic":"15460142",
+                         "asset_id_collateral":"0x57d05d",
+                         "asset_id_synthetic":"0x2",
+                         "expiration_timestamp":"3608164305",
+                         "is_buying_synthetic":true,
+                         "nonce":"1210484339",
+                         "order_type":"LIMIT_ORDER_WITH_FEES",
+                         "position_id":"4805234",
+                         "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                }
+                """;
+        String sigStr = ZKDEX.signAny(json, priKey);
+        Signature signature = JSON.parseObject(sigStr, Signature.class);
+        Signature expectSig = new Signature("0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5","0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b");
+        assertEquals(expectSig, signature);
+
+        assertEquals(ZKDEX.unifiedHashPerpetualLimitOrder(json), ZKDEX.hashAny(json));
+        assert ZKDEX.verifyAny(json, signature.getR(), signature.getS(), pubKeyX);
+    }
+
+    @Test
+    public void batchSignAny() throws Exception{
+        String json = """
+                [{
+                         "type":"PERP_CROSS",
+                         "amount_collateral":"15334874",
+                         "amount_fee":"1767749",
+                         "amount_synthetic":"15460142",
+                         "asset_id_collateral":"0x57d05d",
+                         "asset_id_synthetic":"0x2",
+                         "expiration_timestamp":"3608164305",
+                         "is_buying_synthetic":true,
+                         "nonce":"1210484339",
+                         "order_type":"LIMIT_ORDER_WITH_FEES",
+                         "position_id":"4805234",
+                         "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                },
+                {"type":"DEPOSIT"}]
+                """;
+        JSONArray signatures = JSON.parseArray(ZKDEX.batchSignAny(json, priKey));
+        assertEquals(2, signatures.size());
+        Signature expectSig = new Signature("0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5","0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b");
+        assertEquals(expectSig, signatures.getObject(0, Signature.class));
+        assertEquals("MALFORMED_JSON", signatures.getJSONObject(1).getJSONObject("error").getString("code"));
+
+        JSONArray hashes = JSON.parseArray(ZKDEX.batchHashAny(json));
+        assertEquals(ZKDEX.hashAny(JSON.toJSONString(JSON.parseArray(json).get(0))), hashes.getString(0));
+        assertEquals("MALFORMED_JSON", hashes.getJSONObject(1).getJSONObject("error").getString("code"));
+    }
+
+    @Test
+    public void batchVerify() throws Exception {
+        String signed = "{\"signature\":{\"r\":\"0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749\",\"s\":\"0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f\"},\"public_key\":\"%s\",\"hash\":\"%s\"}";
+        String json = "[" + String.format(signed, pubKeyX, "0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9")
+                + "," + String.format(signed, pubKeyX, "0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520")
+                + "," + String.format(signed, "0x1", "0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9") + "]";
+        JSONArray valid = JSON.parseArray(ZKDEX.batchVerify(json));
+        assertEquals(3, valid.size());
+        assert valid.getBoolean(0);
+        assert !valid.getBoolean(1);
+        assertEquals("INVALID_PUBLIC_KEY", valid.getJSONObject(2).getJSONObject("error").getString("code"));
+        try {
+            ZKDEX.batchVerify("{}");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("MALFORMED_JSON", e.getCode());
+        }
+    }
+
+    @Test
+    public void secretKey() throws Exception {
+        String hash = "0x4068df25a7d520d7b11133a1c6ef27d009400e55bba6bf9b59c6cef63cb37d12";
+        try (SecretKey key = new SecretKey(priKey)) {
+            assertEquals("0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa", key.publicKey());
+            Signature signature = JSON.parseObject(key.sign(hash), Signature.class);
+            assertEquals(new Signature("0x1fb6b3bd2d5cf21862d38a189a16056926d00dc37cdc36586b60b2c9115c762c", "0x002b7e082743fd090f407430dc28e7031af4191f7e876b1abb01c901583170ca"), signature);
+            assertEquals(ZKDEX.sign(priKey, hash), key.sign(hash));
+            assertEquals("MALFORMED_JSON", JSON.parseArray(key.batchSignAny("[{\"type\":\"DEPOSIT\"}]")).getJSONObject(0).getJSONObject("error").getString("code"));
+
+            key.setNonceMode("hedged");
+            Signature hedged = JSON.parseObject(key.sign(hash), Signature.class);
+            assertNotEquals(signature, hedged);
+            assertEquals(true, ZKDEX.verifySignature(hedged.getR(), hedged.getS(), key.publicKey(), pubKeyY, hash));
+            key.setNonceMode("deterministic");
+            assertEquals(ZKDEX.sign(priKey, hash), key.sign(hash));
+            key.setVerifySignatures(true);
+            assertEquals(ZKDEX.sign(priKey, hash), key.sign(hash));
+            try {
+                key.setNonceMode("random");
+                assert false;
+            } catch (ZKDEXException e) {
+                assertEquals("INVALID_ARGUMENT", e.getCode());
+            }
+        }
+
+        SecretKey key = SecretKey.fromKeystore(ZKDEX.encryptKeystore(priKey, "password"), "password");
+        assertEquals("0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa", key.publicKey());
+        key.close();
+        try {
+            key.sign(hash);
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_ARGUMENT", e.getCode());
+        }
+        // the key was already freed, closing it again does not free it twice
+        key.close();
+    }
+
+    @Test
+    public void verifier() throws Exception {
+        String sigr = "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749";
+        String sigs = "0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f";
+        String msg = "0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9";
+        String errMsg = "0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520";
+        try (Verifier verifier = new Verifier(16)) {
+            assert verifier.verifySignature(sigr, sigs, pubKeyX, msg);
+            assert !verifier.verifySignature(sigr, sigs, pubKeyX, errMsg);
+            JSONObject stats = JSON.parseObject(verifier.stats());
+            assertEquals(1, stats.getIntValue("hits"));
+            assertEquals(1, stats.getIntValue("misses"));
+            assertEquals(1, stats.getIntValue("len"));
+            assertEquals(0, stats.getIntValue("tables"));
+            assertEquals(16, stats.getIntValue("capacity"));
+            try {
+                verifier.verifySignature(sigr, sigs, "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000", msg);
+                assert false;
+            } catch (ZKDEXException e) {
+                assertEquals("INVALID_PUBLIC_KEY", e.getCode());
+            }
+            verifier.clear();
+            assertEquals(0, JSON.parseObject(verifier.stats()).getIntValue("len"));
+        }
+    }
+
+    @Test
+    public void poseidonHash() throws Exception {
+        String hash = "0x1ae3633579d8817e55590943ae6e61019d09b0728a1736c0e7bb873bdbcd5619";
+        assertEquals(hash, ZKDEX.poseidonHashFieldElements("[\"0x1\", \"0x2\"]"));
+        assertEquals(hash, ZKDEX.poseidonHashU64Limbs("[\"1\", \"0\", \"0\", \"0\", \"2\"]"));
+        try {
+            ZKDEX.poseidonHashFieldElements("[\"0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001\"]");
+            assert false;
+        } catch (ZKDEXException e) {
+            assertEquals("INVALID_ARGUMENT", e.getCode());
+        }
+    }
+}
@@ -1,4 +1,5 @@
 const Benchmark = require('benchmark');
+const path = require('path');
 const wasm = require('@okbchain/zkdex-sdk')
 const assert = require("assert");
 var suite = new Benchmark.Suite();
@@ -10,13 +11,38 @@ let pub_key_x = "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7
 let pub_key_y = "0x0a3b966094be6c8981a22359df81f7fcdd50ac725401e3fc5872c780d158fb18";
 
 let pri_key = "0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe";
+let hash = wasm.hash_transfer(json);
+let secret_key = new wasm.SecretKey(pri_key);
+let verifier = new wasm.Verifier(16);
+
+// ZKDEX_BASELINE points at a javascript-sdk built before the generator tables, its sign and verify
+// run the franklin code paths next to the table ones of the current build
+let builds = [['table', wasm]];
+if (process.env.ZKDEX_BASELINE) {
+    builds.unshift(['franklin', require(path.resolve(process.env.ZKDEX_BASELINE))]);
+}
+for (let [name, build] of builds) {
+    suite.add(`test sign hash (${name})`, function() {
+        let sig_str = build.sign(pri_key, hash);
+    })
+    .add(`test verify hash (${name})`, function() {
+        assert.equal(build.verify_signature(sig_r, sig_s, pub_key_x, pub_key_y, hash), true);
+    });
+}
+
 suite.add('test sign transfer', function() {
-    let sig_str = wasm.sign_transfer(json, pri_key);
+    let sig_str = wasm.sign_transfer_unchecked(json, pri_key);
 })
 .add('test verify transfer', function() {
     let hash = wasm.hash_transfer(json);
     assert.equal(wasm.verify_signature(sig_r, sig_s, pub_key_x, pub_key_y, hash), true);
 })
+.add('test secret key sign hash', function() {
+    let sig_str = secret_key.sign(hash);
+})
+.add('test verifier verify hash', function() {
+    assert.equal(verifier.verify_signature(sig_r, sig_s, pub_key_x, hash), true);
+})
 // add listeners
 .on('cycle', function(event) {
     console.log(String(event.target));
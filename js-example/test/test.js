@@ -18,7 +18,8 @@ describe('test zkdex js function', function () {
     });
 
     it('test sgin trasnfer', function () {
-        let transfer_req = "{\"nonce\":\"0\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"0\",\"sender_position_id\":\"0\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"0\",\"amount\":\"0\",\"asset_id\":\"0xa\"}";
+        let transfer_req = "{\"nonce\":\"0\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"0\",\"sender_position_id\":\"0\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"0\",\"amount\":\"1\",\"asset_id\":\"0xa\"}";
+        assert.throws(() => zkdex.sign_transfer(transfer_req.replace("\"amount\":\"1\"", "\"amount\":\"0\""), pri_key), (e) => e.startsWith("INVALID_FIELDS: "));
         let sig_str = zkdex.sign_transfer(transfer_req, pri_key);
         let hash = zkdex.hash_transfer(transfer_req);
         let sig = JSON.parse(sig_str);
@@ -26,6 +27,29 @@ describe('test zkdex js function', function () {
         assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x,pub_key_y, err_hash), false);
     });
 
+    it('test sign transfer with fee', function () {
+        let transfer_req = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fee\":\"5\",\"fee_asset_id\":\"0xb\",\"fee_position_id\":\"4\"}";
+        let sig = JSON.parse(zkdex.sign_transfer(transfer_req, pri_key));
+        let hash = zkdex.hash_transfer(transfer_req);
+        assert.equal(hash, "0x18f326b750d7c82f4cb4a3b0fec1f21e704bd14c192566f45fbbfaeaefb1ef44");
+        assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x, pub_key_y, hash), true);
+        assert.equal(zkdex.verify_transfer(transfer_req, sig.r, sig.s, pub_key_x), true);
+        assert.equal(zkdex.verify_transfer(transfer_req.replace("\"fee\":\"5\"", "\"fee\":\"6\""), sig.r, sig.s, pub_key_x), false);
+        assert.throws(() => zkdex.hash_transfer(transfer_req.replace("\"position_id\":\"4\",", "")), (e) => e.startsWith("MALFORMED_JSON: "));
+    });
+
+    it('test sign conditional transfer', function () {
+        let transfer_req = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fact_registry_address\":\"0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb\",\"fact\":\"0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b\"}";
+        let sig = JSON.parse(zkdex.sign_conditional_transfer(transfer_req, pri_key));
+        let hash = zkdex.hash_conditional_transfer(transfer_req);
+        assert.equal(hash, "0x0e82549aeb0699bb64a2f9273ebaeec466b4866db5200a491b9704856ee76bb3");
+        assert.notEqual(hash, zkdex.hash_transfer(transfer_req));
+        assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x, pub_key_y, hash), true);
+        assert.equal(zkdex.verify_conditional_transfer(transfer_req, sig.r, sig.s, pub_key_x), true);
+        assert.equal(zkdex.verify_conditional_transfer(transfer_req, sig.r, sig.s, "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"), false);
+        assert.throws(() => zkdex.hash_conditional_transfer(transfer_req.replace("0xB6aD5EfB", "0xB6aD")), (e) => e.startsWith("MALFORMED_JSON: "));
+    });
+
     it('test sign limit order', function () {
         let limit_order_req = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"2\",\"amount_synthetic\":\"3\",\"amount_collateral\":\"4\",\"amount_fee\":\"5\",\"asset_id_synthetic\":\"0x6\",\"asset_id_collateral\":\"0x7\",\"position_id\":\"8\",\"is_buying_synthetic\":false}";
         let sig_str = zkdex.sign_limit_order(limit_order_req, pri_key);
@@ -81,6 +105,50 @@ describe('test zkdex js function', function () {
         assert.equal(priStr,"0x02aca28609503a6474ec0a115b8662dbf760b6da6109e17c757dbbd3835c93f9");
     });
 
+    it('test generate private key', () => {
+        let key = JSON.parse(zkdex.generate_private_key());
+        assert.equal(key.private_key.length, 66);
+        assert.equal(JSON.parse(zkdex.private_key_to_pubkey_xy(key.private_key)).x, key.public_key);
+        assert.notEqual(JSON.parse(zkdex.generate_private_key()).private_key, key.private_key);
+    });
+
+    it('test derive keys', () => {
+        let seed = "hello world good life 996 very nice";
+        let pri = zkdex.derive_private_key(seed, "m/7'/3");
+        assert.equal(pri, "0x006dacb284fb197c484075cb03f14ba8be86c07c96ab08a50ebef9813278b33f");
+        assert.equal(JSON.parse(zkdex.private_key_to_pubkey_xy(pri)).x, "0x9826359c2ffc1bc0b602d89bcd1fc6448a8823792ea57e4c1dd8a8afec46df8f");
+
+        let master = JSON.parse(zkdex.derive_extended_public_key(seed, "m"));
+        assert.equal(master.public_key, "0xb03ee7244d3dcaabbc9038c2b77e33a9116a530a61f7f9033138a06b80623403");
+        let child = zkdex.derive_public_key(master.public_key, master.chain_code, "m/1/2");
+        assert.equal(child, "0x1e50f110dfe5d4da873afd8ecb2527e13f26c34aca560f11c56914e1011677f4");
+        assert.throws(() => zkdex.derive_public_key(master.public_key, master.chain_code, "m/7'"));
+    });
+
+    it('test keystore', function () {
+        this.timeout(20000);
+        let keystore = '{"version":1,"public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a","crypto":{"cipher":"aes-256-gcm","cipherparams":{"nonce":"6465666768696a6b6c6d6e6f"},"ciphertext":"c24f0dd29173eefcf850b5d8d33a833f3e6442247314ce990b015063ccf09cef7b44b49fc0d07046c18033d5caec9e4c","kdf":"scrypt","kdfparams":{"dklen":32,"n":1024,"r":8,"p":1,"salt":"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"}}}';
+        assert.equal(zkdex.decrypt_keystore(keystore, "correct horse battery staple"), "0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe");
+        assert.throws(() => zkdex.decrypt_keystore(keystore, "wrong password"), (e) => e.startsWith("INVALID_KEYSTORE: "));
+
+        let encrypted = zkdex.encrypt_keystore(pri_key, "password");
+        assert.equal(JSON.parse(encrypted).public_key, "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa");
+        assert.equal(zkdex.decrypt_keystore(encrypted, "password"), pri_key);
+    });
+
+    it('test l2 key from eth signature', () => {
+        let eth_signature = "0xe95a2346431278a7ba90b34ed34cb6a3b54cecdcdb583f8ce1a130ba2bb08bf1127e20e256d7d3c5b98c460b1656f9b695e16d1163bbc50f4f94d4961fe537821b";
+        let key = JSON.parse(zkdex.l2_key_from_eth_signature(eth_signature, "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"));
+        assert.equal(key.private_key, "0x017dcf6e6b75b27c1a0bd215f839e4572e59702202ce28576e65b520182719a7");
+        let xy = JSON.parse(zkdex.private_key_to_pubkey_xy(key.private_key));
+        assert.equal(xy.x, key.public_key);
+
+        let wallet = new utils.Wallet("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let wallet_signature = wallet.signMessageSync(zkdex.onboarding_message());
+        let wallet_key = zkdex.l2_key_from_eth_signature(wallet_signature, wallet.address);
+        assert.equal(zkdex.l2_key_from_eth_signature(wallet_signature, wallet.address), wallet_key);
+    });
+
     it('test private key to public key xy', () => {
         let xy_str = zkdex.private_key_to_pubkey_xy(pri_key);
         let xy = JSON.parse(xy_str);
@@ -93,6 +161,11 @@ describe('test zkdex js function', function () {
         assert.equal(true, ret);
     });
 
+    it('test is on curve rejects small order key', () => {
+        let order_two = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        assert.throws(() => zkdex.is_on_curve(order_two, "0x0"), (e) => e.startsWith("INVALID_PUBLIC_KEY: "));
+    });
+
     it('test pub key to xy', () => {
         let xy_str = zkdex.public_key_to_xy("0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa")
         let xy = JSON.parse(xy_str)
@@ -114,11 +187,12 @@ describe('test zkdex js function', function () {
 
     it('test sign spot limit order', () => {
         let json = '{"nonce":"0","expiration_timestamp":"0","public_key":"0daed291535086c7569618ec99b090c220ac63add8ab019690c3ef3b40ca970a","amount_buy":"0","amount_sell":"0","amount_fee":"0","asset_buy":"0x01","asset_sell":"0x02","position_id":"1"}';
-        let sig_str = zkdex.sign_spot_limit_order(json, pri_key);
+        let sig_str = zkdex.sign_spot_limit_order_unchecked(json, pri_key);
         let hash = zkdex.hash_spot_limit_order(json);
         let sig = JSON.parse(sig_str);
         assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x,pub_key_y, hash), true);
         assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x,pub_key_y, err_hash), false);
+        assert.throws(() => zkdex.sign_spot_limit_order(json, pri_key), (e) => e.startsWith("INVALID_FIELDS: "));
     })
 
     it('test sign spot withdrawal', () => {
@@ -158,6 +232,11 @@ describe('test zkdex js function', function () {
 
         let hash = zkdex.unified_hash_withdrawal(json);
         assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x,pub_key_y, hash), true);
+
+        let envelope = JSON.parse(zkdex.unified_sign_withdrawal_envelope(json, pri_key));
+        assert.equal(envelope.type, 'UNIFIED_WITHDRAWAL');
+        assert.deepEqual(envelope.signature, sig);
+        assert.equal(envelope.hash, hash);
     })
 
     it('test unified sign transfer', ()=> {
@@ -284,6 +363,54 @@ describe('test zkdex js function', function () {
         assert.equal(zkdex.verify_signature(sig.signature_b.r, sig.signature_b.s, pub_key_x,pub_key_y, hash.hash_b), true);
     })
 
+    it('test unified verify perpetual trade', ()=> {
+        let json = `
+                {
+                    "party_a_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":true,
+                        "nonce":"1210484339",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"4805234",
+                        "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                    },
+                    "party_b_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":false,
+                        "nonce":"12104",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"48052349",
+                        "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+                    },
+                    "actual_a_fee":"87916620",
+                    "actual_b_fee":"-9309",
+                    "actual_collateral":"775817",
+                    "actual_synthetic":"1530808"
+                }
+        `;
+
+        let sig = JSON.parse(zkdex.unified_sign_perpetual_trade(json, pri_key, pri_key));
+        let tx = JSON.parse(json);
+        tx.party_a_order.signature = sig.signature_a;
+        tx.party_b_order.signature = sig.signature_b;
+        let ret = JSON.parse(zkdex.unified_verify_perpetual_trade(JSON.stringify(tx)));
+        assert.equal(ret.party_a, true);
+        assert.equal(ret.party_b, false);
+        assert.equal(zkdex.unified_verify_perpetual_limit_order(JSON.stringify(tx.party_a_order)), true);
+    })
+
     it('test unified sign oracle', ()=> {
         let json = `
                           {
@@ -386,8 +513,131 @@ describe('test zkdex js function', function () {
         let hash = zkdex.unified_hash_perpetual_limit_order(json);
         assert.equal(zkdex.verify_signature(sig.r, sig.s, pub_key_x,pub_key_y, hash), true);
     })
-})
 
+    it('test sign any', ()=> {
+        let json = `
+                          {
+                         "type":"PERP_CROSS",
+                         "amount_collateral":"15334874",
+                         "amount_fee":"1767749",
+                         "amount_synthetic":"15460142",
+                         "asset_id_collateral":"0x57d05d",
+                         "asset_id_synthetic":"0x2",
+                         "expiration_timestamp":"3608164305",
+                         "is_buying_synthetic":true,
+                         "nonce":"1210484339",
+                         "order_type":"LIMIT_ORDER_WITH_FEES",
+                         "position_id":"4805234",
+                         "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                }
+        `;
+
+        let sig_str = zkdex.sign_any(json, pri_key);
+        let sig = JSON.parse(sig_str);
+        assert.equal(sig.r,'0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5');
+        assert.equal(sig.s,'0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b');
+
+        let hash = zkdex.hash_any(json);
+        assert.equal(hash, zkdex.unified_hash_perpetual_limit_order(json));
+        assert.equal(zkdex.verify_any(json, sig.r, sig.s, pub_key_x), true);
+    })
 
+    it('test batch sign any', ()=> {
+        let json = `
+                [{
+                         "type":"PERP_CROSS",
+                         "amount_collateral":"15334874",
+                         "amount_fee":"1767749",
+                         "amount_synthetic":"15460142",
+                         "asset_id_collateral":"0x57d05d",
+                         "asset_id_synthetic":"0x2",
+                         "expiration_timestamp":"3608164305",
+                         "is_buying_synthetic":true,
+                         "nonce":"1210484339",
+                         "order_type":"LIMIT_ORDER_WITH_FEES",
+                         "position_id":"4805234",
+                         "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                },
+                {"type":"DEPOSIT"}]
+        `;
+
+        let sigs = JSON.parse(zkdex.batch_sign_any(json, pri_key));
+        assert.equal(sigs.length, 2);
+        assert.equal(sigs[0].r,'0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5');
+        assert.equal(sigs[0].s,'0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b');
+        assert.equal(sigs[1].error.code, 'MALFORMED_JSON');
+
+        let hashes = JSON.parse(zkdex.batch_hash_any(json));
+        assert.equal(hashes[0], zkdex.hash_any(JSON.stringify(JSON.parse(json)[0])));
+        assert.equal(hashes[1].error.code, 'MALFORMED_JSON');
+    })
+
+    it('test batch verify', function () {
+        let signed = (hash) => ({
+            signature: {r: "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749", s: "0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f"},
+            public_key: pub_key_x,
+            hash: hash,
+        });
+        let malformed = signed("0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9");
+        malformed.public_key = "0x1";
+        let valid = JSON.parse(zkdex.batch_verify(JSON.stringify([signed("0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9"), signed(err_hash), malformed])));
+        assert.equal(valid.length, 3);
+        assert.equal(valid[0], true);
+        assert.equal(valid[1], false);
+        assert.equal(valid[2].error.code, 'INVALID_PUBLIC_KEY');
+        assert.throws(() => zkdex.batch_verify("{}"), (e) => e.startsWith("MALFORMED_JSON: "));
+    })
 
+    it('test secret key', function () {
+        this.timeout(20000);
+        let hash = "0x4068df25a7d520d7b11133a1c6ef27d009400e55bba6bf9b59c6cef63cb37d12";
+        let key = new zkdex.SecretKey(pri_key);
+        assert.equal(key.public_key(), "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa");
+        let sig = JSON.parse(key.sign(hash));
+        assert.equal(sig.r, "0x1fb6b3bd2d5cf21862d38a189a16056926d00dc37cdc36586b60b2c9115c762c");
+        assert.equal(sig.s, "0x002b7e082743fd090f407430dc28e7031af4191f7e876b1abb01c901583170ca");
+        assert.equal(key.sign(hash), zkdex.sign(pri_key, hash));
+        assert.equal(JSON.parse(key.batch_sign_any('[{"type":"DEPOSIT"}]'))[0].error.code, 'MALFORMED_JSON');
+        key.set_nonce_mode("hedged");
+        let hedged = JSON.parse(key.sign(hash));
+        assert.notEqual(hedged.r, sig.r);
+        assert.equal(zkdex.verify_signature(hedged.r, hedged.s, "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa", "", hash), true);
+        key.set_nonce_mode("deterministic");
+        assert.equal(key.sign(hash), zkdex.sign(pri_key, hash));
+        key.set_verify_signatures(true);
+        assert.equal(key.sign(hash), zkdex.sign(pri_key, hash));
+        assert.throws(() => key.set_nonce_mode("random"), (e) => e.startsWith("INVALID_ARGUMENT: "));
+        key.free();
+
+        let keystore = zkdex.encrypt_keystore(pri_key, "password");
+        let loaded = zkdex.SecretKey.from_keystore(keystore, "password");
+        assert.equal(loaded.public_key(), "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa");
+        loaded.free();
+        assert.throws(() => new zkdex.SecretKey("0xzz"), (e) => e.startsWith("INVALID_PRIVATE_KEY: "));
+    })
+
+    it('test verifier', function () {
+        this.timeout(20000);
+        let sig_r = "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749";
+        let sig_s = "0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f";
+        let pub_key = "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa";
+        let msg = "0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9";
+        let err_msg = "0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520";
+        let verifier = new zkdex.Verifier(16);
+        assert.equal(verifier.verify_signature(sig_r, sig_s, pub_key, msg), true);
+        assert.equal(verifier.verify_signature(sig_r, sig_s, pub_key, err_msg), false);
+        assert.deepEqual(JSON.parse(verifier.stats()), {hits: 1, misses: 1, len: 1, tables: 0, capacity: 16});
+        let order_two = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        assert.throws(() => verifier.verify_signature(sig_r, sig_s, order_two, msg), (e) => e.startsWith("INVALID_PUBLIC_KEY: "));
+        verifier.clear();
+        assert.equal(JSON.parse(verifier.stats()).len, 0);
+        verifier.free();
+    })
 
+    it('test poseidon hash', function () {
+        let hash = "0x1ae3633579d8817e55590943ae6e61019d09b0728a1736c0e7bb873bdbcd5619";
+        assert.equal(zkdex.poseidon_hash_field_elements('["0x1", "0x2"]'), hash);
+        assert.equal(zkdex.poseidon_hash_u64_limbs('["1", "0", "0", "0", "2"]'), hash);
+        assert.throws(() => zkdex.poseidon_hash_field_elements('["0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"]'), (e) => e.startsWith("INVALID_ARGUMENT: "));
+    })
+})
@@ -1,54 +1,119 @@
 mod model;
 
 use std::panic;
+use pyo3::create_exception;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use serde::Serialize;
 use zkdex_sdk::zkw::JubjubSignature;
 use crate::model::PublicKey;
 
+// Raised with args `(code, message)` when the sdk rejects the input, see `ZkdexError::code`.
+create_exception!(zkdex_python_sdk, ZkdexError, PyValueError);
+
+fn to_py_err(e: zkdex_sdk::ZkdexError) -> PyErr {
+    ZkdexError::new_err((e.code(), e.to_string()))
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn zkdex_python_sdk(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("ZkdexError", _py.get_type::<ZkdexError>())?;
+    m.add_class::<SecretKey>()?;
+    m.add_class::<Verifier>()?;
     m.add_function(wrap_pyfunction!(verify_signature, m)?)?;
     m.add_function(wrap_pyfunction!(sign_withdraw, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_withdraw_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_transfer_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_limit_order_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_liquidate, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_liquidate_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_signed_oracle_price, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_signed_oracle_price_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(hash_withdraw, m)?)?;
     m.add_function(wrap_pyfunction!(hash_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_conditional_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_conditional_transfer_unchecked, m)?)?;
+    m.add_function(wrap_pyfunction!(hash_conditional_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_conditional_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(poseidon_hash_field_elements, m)?)?;
+    m.add_function(wrap_pyfunction!(poseidon_hash_u64_limbs, m)?)?;
     m.add_function(wrap_pyfunction!(hash_limit_order, m)?)?;
     m.add_function(wrap_pyfunction!(hash_liquidate, m)?)?;
     m.add_function(wrap_pyfunction!(hash_signed_oracle_price, m)?)?;
     m.add_function(wrap_pyfunction!(sign, m)?)?;
     m.add_function(wrap_pyfunction!(eth_sign, m)?)?;
     m.add_function(wrap_pyfunction!(private_key_from_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_private_key, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_private_key, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_extended_public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(encrypt_keystore, m)?)?;
+    m.add_function(wrap_pyfunction!(decrypt_keystore, m)?)?;
+    m.add_function(wrap_pyfunction!(onboarding_message, m)?)?;
+    m.add_function(wrap_pyfunction!(l2_key_from_eth_signature, m)?)?;
     m.add_function(wrap_pyfunction!(is_on_curve, m)?)?;
     m.add_function(wrap_pyfunction!(public_key_to_xy, m)?)?;
     m.add_function(wrap_pyfunction!(private_key_to_public_key_xy, m)?)?;
     m.add_function(wrap_pyfunction!(sign_spot_withdrawal, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_spot_withdrawal_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_spot_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_spot_transfer_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(sign_spot_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_spot_limit_order_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(hash_spot_withdrawal, m)?)?;
     m.add_function(wrap_pyfunction!(hash_spot_limit_order, m)?)?;
     m.add_function(wrap_pyfunction!(hash_spot_transfer, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_withdrawal, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_withdrawal_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_withdrawal, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_transfer_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_transfer, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_spot_trade, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_spot_trade_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_spot_trade, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_perpetual_trade, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_perpetual_trade_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_perpetual_trade, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_oracle_price, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_oracle_price_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_oracle_price, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_liquidate, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_liquidate_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_liquidate, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_spot_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_spot_limit_order_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_spot_limit_order, m)?)?;
     m.add_function(wrap_pyfunction!(unified_sign_perpetual_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_perpetual_limit_order_unchecked, m)?)?;
     m.add_function(wrap_pyfunction!(unified_hash_perpetual_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_any, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_any_unchecked, m)?)?;
+    m.add_function(wrap_pyfunction!(hash_any, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_any, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_sign_any, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_hash_any, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_withdrawal, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_transfer, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_oracle_price, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_liquidate, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_spot_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_perpetual_limit_order, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_spot_trade, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_verify_perpetual_trade, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_withdrawal_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_transfer_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_oracle_price_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_liquidate_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_spot_limit_order_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_perpetual_limit_order_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_spot_trade_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(unified_sign_perpetual_trade_envelope, m)?)?;
 
     Ok(())
 }
@@ -63,9 +128,9 @@ fn verify_signature(
 ) -> PyResult<bool> {
     match panic::catch_unwind(|| {
         zkdex_sdk::verify_signature(&sig_r, &sig_s, &pub_key_x, &pub_key_y, &msg)
-            .expect("Couldn't get verify_signature result")
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -74,11 +139,25 @@ fn verify_signature(
 #[pyfunction]
 fn sign_withdraw(json: String, pri_key: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_withdraw(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_withdraw(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_withdraw_unchecked(json: String, pri_key: String) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_withdraw_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -89,11 +168,28 @@ fn sign_transfer(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_transfer(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_transfer(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_transfer_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_transfer_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -104,11 +200,28 @@ fn sign_limit_order(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_limit_order(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_limit_order(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_limit_order_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_limit_order_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -119,11 +232,28 @@ fn sign_liquidate(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_liquidate(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_liquidate(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_liquidate_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_liquidate_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -134,11 +264,28 @@ fn sign_signed_oracle_price(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_signed_oracle_price(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_signed_oracle_price(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_signed_oracle_price_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_signed_oracle_price_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -148,9 +295,10 @@ fn hash_withdraw(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_withdraw(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_withdraw(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -158,9 +306,109 @@ fn hash_withdraw(
 #[pyfunction]
 fn hash_transfer(json: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_transfer(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_transfer(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn verify_transfer(
+    json: String,
+    sig_r: String,
+    sig_s: String,
+    pub_key: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+        zkdex_sdk::verify_transfer(&json, sig, &pub_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_conditional_transfer(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_conditional_transfer(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_conditional_transfer_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_conditional_transfer_unchecked(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn hash_conditional_transfer(json: String) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::hash_conditional_transfer(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn verify_conditional_transfer(
+    json: String,
+    sig_r: String,
+    sig_s: String,
+    pub_key: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+        zkdex_sdk::verify_conditional_transfer(&json, sig, &pub_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn poseidon_hash_field_elements(json: String) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::poseidon_hash_field_elements_json(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn poseidon_hash_u64_limbs(json: String) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::poseidon_hash_u64_limbs_json(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -168,9 +416,10 @@ fn hash_transfer(json: String) -> PyResult<String> {
 #[pyfunction]
 fn hash_limit_order(json: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_limit_order(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_limit_order(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -178,9 +427,10 @@ fn hash_limit_order(json: String) -> PyResult<String> {
 #[pyfunction]
 fn hash_liquidate(json: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_liquidate(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_liquidate(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -190,9 +440,10 @@ fn hash_signed_oracle_price(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_signed_oracle_price(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_signed_oracle_price(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -200,11 +451,12 @@ fn hash_signed_oracle_price(
 #[pyfunction]
 fn sign(private_key: String, msg: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign(&private_key, &msg).expect("Couldn't sign msg");
+        let sig = zkdex_sdk::sign(&private_key, &msg)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -215,11 +467,12 @@ fn eth_sign(
     msg: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::l2_sign(&msg, &private_key).expect("Couldn't sign msg");
+        let sig = zkdex_sdk::l2_sign(&msg, &private_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -229,9 +482,111 @@ fn private_key_from_seed(
     seed: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::private_key_from_seed(seed.as_bytes()).expect("Couldn't derive private key from seed")
+        zkdex_sdk::private_key_from_seed(seed.as_bytes())
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn generate_private_key() -> PyResult<String> {
+    match panic::catch_unwind(zkdex_sdk::generate_private_key) {
+        Ok(Ok(ret)) => Ok(serde_json::to_string(&ret).expect("Couldn't serialize key")),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn derive_private_key(
+    seed: String,
+    path: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::derive_private_key(seed.as_bytes(), &path)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn derive_extended_public_key(
+    seed: String,
+    path: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::derive_extended_public_key(seed.as_bytes(), &path)
+    }) {
+        Ok(Ok(ret)) => Ok(serde_json::to_string(&ret).expect("Couldn't serialize public key")),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn derive_public_key(
+    public_key: String,
+    chain_code: String,
+    path: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::derive_public_key(&public_key, &chain_code, &path)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn encrypt_keystore(
+    py: Python,
+    private_key: String,
+    password: String,
+) -> PyResult<String> {
+    match py.allow_threads(|| panic::catch_unwind(|| {
+        zkdex_sdk::encrypt_keystore(&private_key, &password)
+    })) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn decrypt_keystore(
+    py: Python,
+    keystore: String,
+    password: String,
+) -> PyResult<String> {
+    match py.allow_threads(|| panic::catch_unwind(|| {
+        zkdex_sdk::decrypt_keystore(&keystore, &password)
+    })) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn onboarding_message() -> String {
+    zkdex_sdk::onboarding_message()
+}
+
+#[pyfunction]
+fn l2_key_from_eth_signature(
+    eth_signature: String,
+    address: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::l2_key_from_eth_signature(&eth_signature, &address)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(serde_json::to_string(&ret).expect("Couldn't serialize l2 key")),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -242,9 +597,10 @@ fn is_on_curve(
     y: String,
 ) -> PyResult<bool> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::is_on_curve(&x, &y).expect("Couldn't get verify xy is on curve")
+        zkdex_sdk::is_on_curve(&x, &y)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -252,13 +608,14 @@ fn is_on_curve(
 #[pyfunction]
 fn public_key_to_xy(public_key: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::pub_key_to_xy(&public_key).expect("Couldn't convert public key to xy")
+        zkdex_sdk::pub_key_to_xy(&public_key)
     }) {
-        Ok(ret) => {
+        Ok(Ok(ret)) => {
             let pk = PublicKey::new(ret.0, ret.1);
             let pk_json = serde_json::to_string(&pk).expect("Couldn't serialize public key");
             Ok(pk_json)
         }
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => {
             Err(PyValueError::new_err(format!("{:?}", e)))
         }
@@ -268,13 +625,14 @@ fn public_key_to_xy(public_key: String) -> PyResult<String> {
 #[pyfunction]
 fn private_key_to_public_key_xy(private_key: String) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::private_key_to_pubkey_xy(&private_key).expect("Couldn't convert private key to public key xy")
+        zkdex_sdk::private_key_to_pubkey_xy(&private_key)
     }) {
-        Ok(ret) => {
+        Ok(Ok(ret)) => {
             let pk = PublicKey::new(ret.0, ret.1);
             let pk_json = serde_json::to_string(&pk).expect("Couldn't serialize public key");
             Ok(pk_json)
         }
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => {
             Err(PyValueError::new_err(format!("{:?}", e)))
         }
@@ -287,11 +645,28 @@ fn sign_spot_transfer(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_spot_transfer(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_spot_transfer(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_spot_transfer_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_spot_transfer_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -301,9 +676,10 @@ fn hash_spot_transfer(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_spot_transfer(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_spot_transfer(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -314,11 +690,28 @@ fn sign_spot_limit_order(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_spot_limit_order(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_spot_limit_order(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_spot_limit_order_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_spot_limit_order_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -328,9 +721,10 @@ fn hash_spot_limit_order(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_spot_limit_order(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_spot_limit_order(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -341,11 +735,28 @@ fn sign_spot_withdrawal(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::sign_spot_withdrawal(&json, &pri_key).expect("Couldn get jubjubSignature");
+        let sig = zkdex_sdk::sign_spot_withdrawal(&json, &pri_key)?;
+        let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_spot_withdrawal_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_spot_withdrawal_unchecked(&json, &pri_key)?;
         let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -355,9 +766,10 @@ fn hash_spot_withdrawal(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::hash_spot_withdrawal(&json).expect("Couldn't get hash")
+        zkdex_sdk::hash_spot_withdrawal(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -368,10 +780,26 @@ fn unified_sign_withdrawal(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_withdrawal(&json, &pri_key).expect("Couldn't get signature");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_withdrawal(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_withdrawal_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_withdrawal_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -381,9 +809,10 @@ fn unified_hash_withdrawal(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_withdrawal(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_withdrawal(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -394,10 +823,26 @@ fn unified_sign_transfer(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_transfer(&json, &pri_key).expect("Couldn't get hash");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_transfer(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_transfer_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_transfer_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -407,9 +852,10 @@ fn unified_hash_transfer(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_transfer(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_transfer(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -421,9 +867,31 @@ fn unified_sign_spot_trade(
     pri_key_b: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_sign_spot_trade(&json, &pri_key_a, &pri_key_b).expect("Couldn't get hash")
+        zkdex_sdk::unified_sign_spot_trade(&json, &pri_key_a, &pri_key_b)
+    }) {
+        Ok(Ok(ret)) => {
+            let sig = ComposeSignature {
+                signature_a: ret.0,
+                signature_b: ret.1,
+            };
+            let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+            Ok(json)
+        }
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_spot_trade_unchecked(
+    json: String,
+    pri_key_a: String,
+    pri_key_b: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_spot_trade_unchecked(&json, &pri_key_a, &pri_key_b)
     }) {
-        Ok(ret) => {
+        Ok(Ok(ret)) => {
             let sig = ComposeSignature {
                 signature_a: ret.0,
                 signature_b: ret.1,
@@ -431,6 +899,7 @@ fn unified_sign_spot_trade(
             let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
             Ok(json)
         }
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -440,15 +909,16 @@ fn unified_hash_spot_trade(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let ret = zkdex_sdk::unified_hash_spot_trade(&json).expect("Couldn't get hash");
+        let ret = zkdex_sdk::unified_hash_spot_trade(&json)?;
         let hash = ComposeHash {
             hash_a: ret.0,
             hash_b: ret.1,
         };
         let json = serde_json::to_string(&hash).expect("Couldn't serialize hash");
-        json
+        Ok(json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -460,9 +930,9 @@ fn unified_sign_perpetual_trade(
     pri_key_b: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_sign_perpetual_trade(&json, &pri_key_a, &pri_key_b).expect("Couldn't get hash")
+        zkdex_sdk::unified_sign_perpetual_trade(&json, &pri_key_a, &pri_key_b)
     }) {
-        Ok(ret) => {
+        Ok(Ok(ret)) => {
             let sig = ComposeSignature {
                 signature_a: ret.0,
                 signature_b: ret.1,
@@ -470,26 +940,50 @@ fn unified_sign_perpetual_trade(
             let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
             Ok(json)
         }
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
 
 #[pyfunction]
-fn unified_hash_perpetual_trade(
+fn unified_sign_perpetual_trade_unchecked(
     json: String,
+    pri_key_a: String,
+    pri_key_b: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let ret = zkdex_sdk::unified_hash_perpetual_trade(&json).expect("Couldn't get hash");
-        let hash = ComposeHash {
-            hash_a: ret.0,
-            hash_b: ret.1,
-        };
-        let json = serde_json::to_string(&hash).expect("Couldn't serialize hash");
-        json
+        zkdex_sdk::unified_sign_perpetual_trade_unchecked(&json, &pri_key_a, &pri_key_b)
     }) {
-        Ok(ret) => Ok(ret),
-        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
-    }
+        Ok(Ok(ret)) => {
+            let sig = ComposeSignature {
+                signature_a: ret.0,
+                signature_b: ret.1,
+            };
+            let json = serde_json::to_string(&sig).expect("Couldn't serialize signature");
+            Ok(json)
+        }
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_hash_perpetual_trade(
+    json: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let ret = zkdex_sdk::unified_hash_perpetual_trade(&json)?;
+        let hash = ComposeHash {
+            hash_a: ret.0,
+            hash_b: ret.1,
+        };
+        let json = serde_json::to_string(&hash).expect("Couldn't serialize hash");
+        Ok(json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
 }
 
 #[pyfunction]
@@ -498,10 +992,26 @@ fn unified_sign_oracle_price(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_oracle_price(&json, &pri_key).expect("Couldn't get hash");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_oracle_price(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_oracle_price_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_oracle_price_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -511,9 +1021,10 @@ fn unified_hash_oracle_price(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_oracle_price(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_oracle_price(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -524,10 +1035,26 @@ fn unified_sign_liquidate(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_liquidate(&json, &pri_key).expect("Couldn't get hash");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_liquidate(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_liquidate_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_liquidate_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -537,9 +1064,10 @@ fn unified_hash_liquidate(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_liquidate(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_liquidate(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -550,10 +1078,26 @@ fn unified_sign_spot_limit_order(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_spot_limit_order(&json, &pri_key).expect("Couldn't get hash");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_spot_limit_order(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_spot_limit_order_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_spot_limit_order_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -563,9 +1107,10 @@ fn unified_hash_spot_limit_order(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_spot_limit_order(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_spot_limit_order(&json)
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -576,10 +1121,26 @@ fn unified_sign_perpetual_limit_order(
     pri_key: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        let sig = zkdex_sdk::unified_sign_perpetual_limit_order(&json, &pri_key).expect("Couldn't get hash");
-        serde_json::to_string(&sig).expect("Couldn't serialize signature")
+        let sig = zkdex_sdk::unified_sign_perpetual_limit_order(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_perpetual_limit_order_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::unified_sign_perpetual_limit_order_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -589,9 +1150,346 @@ fn unified_hash_perpetual_limit_order(
     json: String,
 ) -> PyResult<String> {
     match panic::catch_unwind(|| {
-        zkdex_sdk::unified_hash_perpetual_limit_order(&json).expect("Couldn't get hash")
+        zkdex_sdk::unified_hash_perpetual_limit_order(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_any(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_any(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
     }) {
-        Ok(ret) => Ok(ret),
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn sign_any_unchecked(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let sig = zkdex_sdk::sign_any_unchecked(&json, &pri_key)?;
+        Ok(serde_json::to_string(&sig).expect("Couldn't serialize signature"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn hash_any(
+    json: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::hash_any(&json).map(|ret| match ret {
+            zkdex_sdk::AnyHash::Single(hash) => hash,
+            ret => serde_json::to_string(&ret).expect("Couldn't serialize hash"),
+        })
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn verify_any(
+    json: String,
+    sig_r: String,
+    sig_s: String,
+    pub_key: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+        zkdex_sdk::verify_any(&json, sig, &pub_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_withdrawal(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_withdrawal(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_transfer(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_transfer(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_oracle_price(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_oracle_price(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_liquidate(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_liquidate(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_spot_limit_order(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_spot_limit_order(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_perpetual_limit_order(
+    json: String,
+) -> PyResult<bool> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_verify_perpetual_limit_order(&json)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_spot_trade(
+    json: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let ret = zkdex_sdk::unified_verify_spot_trade(&json)?;
+        Ok(serde_json::to_string(&ret).expect("Couldn't serialize verification"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_verify_perpetual_trade(
+    json: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        let ret = zkdex_sdk::unified_verify_perpetual_trade(&json)?;
+        Ok(serde_json::to_string(&ret).expect("Couldn't serialize verification"))
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_withdrawal_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_withdrawal_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_transfer_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_transfer_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_oracle_price_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_oracle_price_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_liquidate_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_liquidate_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_spot_limit_order_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_spot_limit_order_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_perpetual_limit_order_envelope(
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_perpetual_limit_order_envelope(&json, &pri_key)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_spot_trade_envelope(
+    json: String,
+    pri_key_a: String,
+    pri_key_b: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_spot_trade_envelope(&json, &pri_key_a, &pri_key_b)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn unified_sign_perpetual_trade_envelope(
+    json: String,
+    pri_key_a: String,
+    pri_key_b: String,
+) -> PyResult<String> {
+    match panic::catch_unwind(|| {
+        zkdex_sdk::unified_sign_perpetual_trade_envelope(&json, &pri_key_a, &pri_key_b)
+    }) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+// The batch runs on the sdk thread pool, the GIL is released meanwhile.
+#[pyfunction]
+fn batch_sign_any(
+    py: Python,
+    json: String,
+    pri_key: String,
+) -> PyResult<String> {
+    match py.allow_threads(|| panic::catch_unwind(|| {
+        zkdex_sdk::batch_sign_any(&json, &pri_key).map(|ret| {
+            let items: Vec<zkdex_sdk::BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+            serde_json::to_string(&items).expect("Couldn't serialize signatures")
+        })
+    })) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn batch_hash_any(
+    py: Python,
+    json: String,
+) -> PyResult<String> {
+    match py.allow_threads(|| panic::catch_unwind(|| {
+        zkdex_sdk::batch_hash_any(&json).map(|ret| {
+            let items: Vec<zkdex_sdk::BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+            serde_json::to_string(&items).expect("Couldn't serialize hashes")
+        })
+    })) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
+        Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+    }
+}
+
+#[pyfunction]
+fn batch_verify(
+    py: Python,
+    json: String,
+) -> PyResult<String> {
+    match py.allow_threads(|| panic::catch_unwind(|| {
+        zkdex_sdk::batch_verify(&json).map(|ret| {
+            let items: Vec<zkdex_sdk::BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+            serde_json::to_string(&items).expect("Couldn't serialize results")
+        })
+    })) {
+        Ok(Ok(ret)) => Ok(ret),
+        Ok(Err(e)) => Err(to_py_err(e)),
         Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
     }
 }
@@ -606,4 +1504,124 @@ struct ComposeSignature {
 struct ComposeHash {
     hash_a: String,
     hash_b: String,
-}
\ No newline at end of file
+}
+
+/// A private key parsed once and reused for many signatures, wiped when collected.
+#[pyclass]
+struct SecretKey {
+    key: zkdex_sdk::SecretKey,
+}
+
+#[pymethods]
+impl SecretKey {
+    #[new]
+    fn new(pri_key: String) -> PyResult<Self> {
+        match zkdex_sdk::SecretKey::from_hex(&pri_key) {
+            Ok(key) => Ok(Self { key }),
+            Err(e) => Err(to_py_err(e)),
+        }
+    }
+
+    #[staticmethod]
+    fn from_keystore(
+        py: Python,
+        keystore: String,
+        password: String,
+    ) -> PyResult<Self> {
+        match py.allow_threads(|| panic::catch_unwind(|| {
+            zkdex_sdk::keystore::Keystore::from_json(&keystore)?.decrypt(&password)
+        })) {
+            Ok(Ok(key)) => Ok(Self { key }),
+            Ok(Err(e)) => Err(to_py_err(e)),
+            Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+        }
+    }
+
+    fn public_key(&self) -> String {
+        self.key.public_key().to_string()
+    }
+
+    fn set_nonce_mode(&mut self, mode: String) -> PyResult<()> {
+        let mode = mode.parse::<zkdex_sdk::NonceMode>().map_err(to_py_err)?;
+        self.key.set_nonce_mode(mode);
+        Ok(())
+    }
+
+    fn set_verify_signatures(&mut self, verify: bool) {
+        self.key.set_verify_signatures(verify);
+    }
+
+    fn sign(&self, msg: String) -> PyResult<String> {
+        match panic::catch_unwind(|| {
+            self.key.sign(&msg)
+        }) {
+            Ok(Ok(ret)) => Ok(serde_json::to_string(&ret).expect("Couldn't serialize signature")),
+            Ok(Err(e)) => Err(to_py_err(e)),
+            Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+        }
+    }
+
+    fn sign_any(&self, json: String) -> PyResult<String> {
+        match panic::catch_unwind(|| {
+            self.key.sign_any(&json)
+        }) {
+            Ok(Ok(ret)) => Ok(serde_json::to_string(&ret).expect("Couldn't serialize signature")),
+            Ok(Err(e)) => Err(to_py_err(e)),
+            Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+        }
+    }
+
+    fn batch_sign_any(&self, py: Python, json: String) -> PyResult<String> {
+        let key = &self.key;
+        match py.allow_threads(|| panic::catch_unwind(|| {
+            zkdex_sdk::batch_sign_any_with(&json, key).map(|ret| {
+                let items: Vec<zkdex_sdk::BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                serde_json::to_string(&items).expect("Couldn't serialize signatures")
+            })
+        })) {
+            Ok(Ok(ret)) => Ok(ret),
+            Ok(Err(e)) => Err(to_py_err(e)),
+            Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+        }
+    }
+}
+
+/// Verifies signatures with a cache of the public keys it has seen.
+#[pyclass]
+struct Verifier {
+    verifier: zkdex_sdk::Verifier,
+}
+
+#[pymethods]
+impl Verifier {
+    #[new]
+    fn new(capacity: usize) -> Self {
+        Self { verifier: zkdex_sdk::Verifier::new(capacity) }
+    }
+
+    fn verify_signature(
+        &self,
+        py: Python,
+        sig_r: String,
+        sig_s: String,
+        pub_key: String,
+        msg: String,
+    ) -> PyResult<bool> {
+        let verifier = &self.verifier;
+        match py.allow_threads(|| panic::catch_unwind(|| {
+            verifier.verify_signature(&sig_r, &sig_s, &pub_key, &msg)
+        })) {
+            Ok(Ok(ret)) => Ok(ret),
+            Ok(Err(e)) => Err(to_py_err(e)),
+            Err(e) => Err(PyValueError::new_err(format!("{:?}", e)))
+        }
+    }
+
+    fn stats(&self) -> String {
+        serde_json::to_string(&self.verifier.stats()).expect("Couldn't serialize stats")
+    }
+
+    fn clear(&self) {
+        self.verifier.clear();
+    }
+}
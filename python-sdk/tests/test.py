@@ -24,12 +24,24 @@ class TestZKDEX(unittest.TestCase):
         self.assertEqual('0xa5d62dbb0566a1b69162df475097fbfca6a317535ea59ea3275580dce2d7043e', sig['r'])
         self.assertEqual('0x03c61d342a339d329341494ee136ccadf10675b9f8f90894e6a9e86ac6a19dec', sig['s'])
 
+    def test_sign_withdraw_error_code(self):
+        json_str = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"position_id\":\"2\",\"amount\":\"3\",\"eth_address\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"asset_id\":\"0x1\"}";
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.sign_withdraw(json_str, "0xzz")
+        self.assertEqual("INVALID_PRIVATE_KEY", ctx.exception.args[0])
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.hash_withdraw("{\"nonce\":")
+        self.assertEqual("MALFORMED_JSON", ctx.exception.args[0])
+
     def test_sign_transfer(self):
         json_str = "{\"nonce\":\"0\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"0\",\"sender_position_id\":\"0\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"0\",\"amount\":\"0\",\"asset_id\":\"0xa\"}";
-        r = zkdex_python_sdk.sign_transfer(json_str, pri_key)
+        r = zkdex_python_sdk.sign_transfer_unchecked(json_str, pri_key)
         sig = json.loads(r)
         self.assertEqual('0xa5920612d2b265813f31ee169b9e96e89548bdd53e9f4541e53fcdb1205c9c9a', sig['r'])
         self.assertEqual('0x0028bdb4cc8f9f70c6ad081c03d662599fe732c118f268e537da019e3b473a09', sig['s'])
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.sign_transfer(json_str, pri_key)
+        self.assertEqual("INVALID_FIELDS", ctx.exception.args[0])
 
     def test_sign_limit_order(self):
         json_str = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"2\",\"amount_synthetic\":\"3\",\"amount_collateral\":\"4\",\"amount_fee\":\"5\",\"asset_id_synthetic\":\"0x6\",\"asset_id_collateral\":\"0x7\",\"position_id\":\"8\",\"is_buying_synthetic\":false}";
@@ -90,11 +102,62 @@ class TestZKDEX(unittest.TestCase):
         r = zkdex_python_sdk.is_on_curve(x, y)
         self.assertTrue(r)
 
+    def test_is_on_curve_rejects_small_order_key(self):
+        order_two = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000"
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.is_on_curve(order_two, "0x0")
+        self.assertEqual("INVALID_PUBLIC_KEY", ctx.exception.args[0])
+
     def test_private_key_from_seed(self):
         seed = "hello world good life 996 very nice"
         r = zkdex_python_sdk.private_key_from_seed(seed)
         self.assertEqual('0x02aca28609503a6474ec0a115b8662dbf760b6da6109e17c757dbbd3835c93f9',r)
 
+    def test_generate_private_key(self):
+        key = json.loads(zkdex_python_sdk.generate_private_key())
+        self.assertEqual(66, len(key['private_key']))
+        pk = json.loads(zkdex_python_sdk.private_key_to_public_key_xy(key['private_key']))
+        self.assertEqual(pk['x'], key['public_key'])
+        self.assertNotEqual(key['private_key'], json.loads(zkdex_python_sdk.generate_private_key())['private_key'])
+
+    def test_derive_keys(self):
+        seed = "hello world good life 996 very nice"
+        pri_key = zkdex_python_sdk.derive_private_key(seed, "m/7'/3")
+        self.assertEqual("0x006dacb284fb197c484075cb03f14ba8be86c07c96ab08a50ebef9813278b33f", pri_key)
+        pk = json.loads(zkdex_python_sdk.private_key_to_public_key_xy(pri_key))
+        self.assertEqual("0x9826359c2ffc1bc0b602d89bcd1fc6448a8823792ea57e4c1dd8a8afec46df8f", pk['x'])
+        master = json.loads(zkdex_python_sdk.derive_extended_public_key(seed, "m"))
+        self.assertEqual("0x5aa33b47fa41321555e4fd679ededc4bc9886a414930ff242f7a7a60fb5eb499", master['chain_code'])
+        child = zkdex_python_sdk.derive_public_key(master['public_key'], master['chain_code'], "m/1/2")
+        self.assertEqual("0x1e50f110dfe5d4da873afd8ecb2527e13f26c34aca560f11c56914e1011677f4", child)
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.derive_public_key(master['public_key'], master['chain_code'], "m/7'")
+        self.assertEqual("INVALID_ARGUMENT", ctx.exception.args[0])
+
+    def test_keystore(self):
+        keystore = '{"version":1,"public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a","crypto":{"cipher":"aes-256-gcm","cipherparams":{"nonce":"6465666768696a6b6c6d6e6f"},"ciphertext":"c24f0dd29173eefcf850b5d8d33a833f3e6442247314ce990b015063ccf09cef7b44b49fc0d07046c18033d5caec9e4c","kdf":"scrypt","kdfparams":{"dklen":32,"n":1024,"r":8,"p":1,"salt":"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"}}}'
+        self.assertEqual("0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe", zkdex_python_sdk.decrypt_keystore(keystore, "correct horse battery staple"))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.decrypt_keystore(keystore, "wrong password")
+        self.assertEqual("INVALID_KEYSTORE", ctx.exception.args[0])
+
+        pri_key = '0x028dd913a169cf3732c306959e9c2a66a0075663e54e086977ed71c61fd7c273'
+        encrypted = zkdex_python_sdk.encrypt_keystore(pri_key, "password")
+        self.assertEqual('0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa', json.loads(encrypted)['public_key'])
+        self.assertEqual(pri_key, zkdex_python_sdk.decrypt_keystore(encrypted, "password"))
+
+    def test_l2_key_from_eth_signature(self):
+        self.assertEqual("Sign this message to create your zkDEX key. Only sign this message on a trusted site.", zkdex_python_sdk.onboarding_message())
+        eth_signature = "0xe95a2346431278a7ba90b34ed34cb6a3b54cecdcdb583f8ce1a130ba2bb08bf1127e20e256d7d3c5b98c460b1656f9b695e16d1163bbc50f4f94d4961fe537821b"
+        address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        key = json.loads(zkdex_python_sdk.l2_key_from_eth_signature(eth_signature, address))
+        self.assertEqual("0x017dcf6e6b75b27c1a0bd215f839e4572e59702202ce28576e65b520182719a7", key['private_key'])
+        pk = json.loads(zkdex_python_sdk.private_key_to_public_key_xy(key['private_key']))
+        self.assertEqual(pk['x'], key['public_key'])
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.l2_key_from_eth_signature(eth_signature, "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb")
+        self.assertEqual("INVALID_SIGNATURE", ctx.exception.args[0])
+
     def test_private_key_to_public_key_xy(self):
         pri_key = '0x028dd913a169cf3732c306959e9c2a66a0075663e54e086977ed71c61fd7c273'
         r = zkdex_python_sdk.private_key_to_public_key_xy(pri_key)
@@ -128,12 +191,15 @@ class TestZKDEX(unittest.TestCase):
         self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'],sig['s'], pk_x, pk_y, hash))
     def test_sign_spot_limit_order(self):
         json_str = "{\"nonce\":\"0\",\"expiration_timestamp\":\"0\",\"public_key\":\"0x0daed291535086c7569618ec99b090c220ac63add8ab019690c3ef3b40ca970a\",\"amount_buy\":\"0\",\"amount_sell\":\"0\",\"amount_fee\":\"0\",\"asset_buy\":\"0x01\",\"asset_sell\":\"0x02\",\"position_id\":\"1\"}"
-        r = zkdex_python_sdk.sign_spot_limit_order(json_str, pri_key)
+        r = zkdex_python_sdk.sign_spot_limit_order_unchecked(json_str, pri_key)
         sig = json.loads(r)
         self.assertEqual('0x01aabe43b11787a211f9960a2abd2de3667965c52b5ff23ac853a91ebfc9b6c2', sig['r'])
         self.assertEqual('0x01ffebd7ab388ae453baa839f123116bdfac8b57931bbbc463cf8dfcfab6fc02', sig['s'])
         hash = zkdex_python_sdk.hash_spot_limit_order(json_str)
         self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'],sig['s'], pk_x, pk_y, hash))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.sign_spot_limit_order(json_str, pri_key)
+        self.assertEqual("INVALID_FIELDS", ctx.exception.args[0])
 
     def test_sign_spot_withdrawal(self):
         json_str = "{\"nonce\":\"1\",\"public_key\":\"0x0daed291535086c7569618ec99b090c220ac63add8ab019690c3ef3b40ca970a\",\"expiration_timestamp\":\"3608164305\",\"amount\":\"1000000\",\"asset_id\":\"0x00001\",\"position_id\":\"1\",\"chain_id\":\"1\",\"eth_address\":\"0x0\"}"
@@ -165,6 +231,11 @@ class TestZKDEX(unittest.TestCase):
         hash = zkdex_python_sdk.unified_hash_withdrawal(json_str)
         self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'],sig['s'], pk_x, pk_y, hash))
 
+        envelope = json.loads(zkdex_python_sdk.unified_sign_withdrawal_envelope(json_str, pri_key))
+        self.assertEqual("UNIFIED_WITHDRAWAL", envelope['type'])
+        self.assertEqual(sig, envelope['signature'])
+        self.assertEqual(hash, envelope['hash'])
+
     def test_unified_sign_transfer(self):
         json_str = """
         {
@@ -323,6 +394,57 @@ class TestZKDEX(unittest.TestCase):
         hash = zkdex_python_sdk.unified_hash_liquidate(json_str)
         self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'],sig['s'], pk_x, pk_y, hash))
 
+    def test_unified_verify_perpetual_trade(self):
+        json_str = """
+        {
+                    "party_a_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":true,
+                        "nonce":"1210484339",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"4805234",
+                        "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+                    },
+                    "party_b_order":{
+                        "type":"PERP_CROSS",
+                        "amount_collateral":"15334874",
+                        "amount_fee":"1767749",
+                        "amount_synthetic":"15460142",
+                        "asset_id_collateral":"0x57d05d",
+                        "asset_id_synthetic":"0x2",
+                        "expiration_timestamp":"3608164305",
+                        "is_buying_synthetic":false,
+                        "nonce":"12104",
+                        "order_type":"LIMIT_ORDER_WITH_FEES",
+                        "position_id":"48052349",
+                        "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+                    },
+                    "actual_a_fee":"87916620",
+                    "actual_b_fee":"-9309",
+                    "actual_collateral":"775817",
+                    "actual_synthetic":"1530808"
+                }
+        """
+        sig = json.loads(zkdex_python_sdk.unified_sign_perpetual_trade(json_str, pri_key, pri_key))
+        tx = json.loads(json_str)
+        tx['party_a_order']['signature'] = sig['signature_a']
+        tx['party_b_order']['signature'] = sig['signature_b']
+        ret = json.loads(zkdex_python_sdk.unified_verify_perpetual_trade(json.dumps(tx)))
+        self.assertTrue(ret['party_a'])
+        self.assertFalse(ret['party_b'])
+        self.assertTrue(zkdex_python_sdk.unified_verify_perpetual_limit_order(json.dumps(tx['party_a_order'])))
+
+        del tx['party_b_order']['signature']
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.unified_verify_perpetual_trade(json.dumps(tx))
+        self.assertEqual("INVALID_SIGNATURE", ctx.exception.args[0])
+
     def test_unified_sign_spot_limit_order(self):
         json_str = """
         {
@@ -368,5 +490,151 @@ class TestZKDEX(unittest.TestCase):
         hash = zkdex_python_sdk.unified_hash_perpetual_limit_order(json_str)
         self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'],sig['s'], pk_x, pk_y, hash))
 
+    def test_sign_any(self):
+        json_str = """
+       {
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484339",
+        "order_type":"LIMIT_ORDER_WITH_FEES",
+        "position_id":"4805234",
+        "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+        }
+        """
+        sig_str = zkdex_python_sdk.sign_any(json_str, pri_key)
+        sig = json.loads(sig_str)
+        self.assertEqual('0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5', sig['r'])
+        self.assertEqual('0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b', sig['s'])
+        hash = zkdex_python_sdk.hash_any(json_str)
+        self.assertEqual(zkdex_python_sdk.unified_hash_perpetual_limit_order(json_str), hash)
+        self.assertTrue(zkdex_python_sdk.verify_any(json_str, sig['r'], sig['s'], pk_x))
+
+    def test_batch_sign_any(self):
+        json_str = """
+       [{
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484339",
+        "order_type":"LIMIT_ORDER_WITH_FEES",
+        "position_id":"4805234",
+        "public_key":"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa"
+        },
+        {"type":"DEPOSIT"}]
+        """
+        sigs = json.loads(zkdex_python_sdk.batch_sign_any(json_str, pri_key))
+        self.assertEqual(2, len(sigs))
+        self.assertEqual('0x05b3949d9397f8aa5bff3e2858f493e16691965d5d09e59d94213583ba2b85a5', sigs[0]['r'])
+        self.assertEqual('0x01f87f794dc75a3e157b8b2b8ebd3781842d84404c91b76c624cb94f8566cb2b', sigs[0]['s'])
+        self.assertEqual('MALFORMED_JSON', sigs[1]['error']['code'])
+
+        hashes = json.loads(zkdex_python_sdk.batch_hash_any(json_str))
+        self.assertEqual(zkdex_python_sdk.hash_any(json.dumps(json.loads(json_str)[0])), hashes[0])
+        self.assertEqual('MALFORMED_JSON', hashes[1]['error']['code'])
+
+    def test_batch_verify(self):
+        def signed(hash):
+            return {
+                "signature": {"r": "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749", "s": "0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f"},
+                "public_key": pk_x,
+                "hash": hash,
+            }
+        malformed = signed("0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9")
+        malformed["public_key"] = "0x1"
+        items = [signed("0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9"), signed("0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520"), malformed]
+        valid = json.loads(zkdex_python_sdk.batch_verify(json.dumps(items)))
+        self.assertEqual([True, False], valid[:2])
+        self.assertEqual('INVALID_PUBLIC_KEY', valid[2]['error']['code'])
+        with self.assertRaises(zkdex_python_sdk.ZkdexError):
+            zkdex_python_sdk.batch_verify("{}")
+
+    def test_secret_key(self):
+        hash = '0x4068df25a7d520d7b11133a1c6ef27d009400e55bba6bf9b59c6cef63cb37d12'
+        key = zkdex_python_sdk.SecretKey(pri_key)
+        self.assertEqual('0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa', key.public_key())
+        sig = json.loads(key.sign(hash))
+        self.assertEqual('0x1fb6b3bd2d5cf21862d38a189a16056926d00dc37cdc36586b60b2c9115c762c', sig['r'])
+        self.assertEqual('0x002b7e082743fd090f407430dc28e7031af4191f7e876b1abb01c901583170ca', sig['s'])
+        self.assertEqual(zkdex_python_sdk.sign(pri_key, hash), key.sign(hash))
+        sigs = json.loads(key.batch_sign_any('[{"type":"DEPOSIT"}]'))
+        self.assertEqual('MALFORMED_JSON', sigs[0]['error']['code'])
+        key.set_nonce_mode("hedged")
+        hedged = json.loads(key.sign(hash))
+        self.assertNotEqual(sig['r'], hedged['r'])
+        self.assertTrue(zkdex_python_sdk.verify_signature(hedged['r'], hedged['s'], key.public_key(), "", hash))
+        key.set_nonce_mode("deterministic")
+        self.assertEqual(zkdex_python_sdk.sign(pri_key, hash), key.sign(hash))
+        key.set_verify_signatures(True)
+        self.assertEqual(zkdex_python_sdk.sign(pri_key, hash), key.sign(hash))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            key.set_nonce_mode("random")
+        self.assertEqual("INVALID_ARGUMENT", ctx.exception.args[0])
+
+        keystore = zkdex_python_sdk.encrypt_keystore(pri_key, "password")
+        self.assertEqual(key.public_key(), zkdex_python_sdk.SecretKey.from_keystore(keystore, "password").public_key())
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.SecretKey("0xzz")
+        self.assertEqual("INVALID_PRIVATE_KEY", ctx.exception.args[0])
+
+    def test_verifier(self):
+        sig_r = '0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749'
+        sig_s = '0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f'
+        pub_key = '0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa'
+        msg = '0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9'
+        err_msg = '0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520'
+        verifier = zkdex_python_sdk.Verifier(16)
+        self.assertTrue(verifier.verify_signature(sig_r, sig_s, pub_key, msg))
+        self.assertFalse(verifier.verify_signature(sig_r, sig_s, pub_key, err_msg))
+        self.assertEqual({'hits': 1, 'misses': 1, 'len': 1, 'tables': 0, 'capacity': 16}, json.loads(verifier.stats()))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            verifier.verify_signature(sig_r, sig_s, '0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000', msg)
+        self.assertEqual("INVALID_PUBLIC_KEY", ctx.exception.args[0])
+        verifier.clear()
+        self.assertEqual(0, json.loads(verifier.stats())['len'])
+
+    def test_sign_transfer_with_fee(self):
+        json_str = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fee\":\"5\",\"fee_asset_id\":\"0xb\",\"fee_position_id\":\"4\"}"
+        sig = json.loads(zkdex_python_sdk.sign_transfer(json_str, pri_key))
+        hash = zkdex_python_sdk.hash_transfer(json_str)
+        self.assertEqual('0x18f326b750d7c82f4cb4a3b0fec1f21e704bd14c192566f45fbbfaeaefb1ef44', hash)
+        self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'], sig['s'], pk_x, pk_y, hash))
+        self.assertTrue(zkdex_python_sdk.verify_transfer(json_str, sig['r'], sig['s'], pk_x))
+        self.assertFalse(zkdex_python_sdk.verify_transfer(json_str.replace('"fee":"5"', '"fee":"6"'), sig['r'], sig['s'], pk_x))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.hash_transfer(json_str.replace('"position_id":"4",', ''))
+        self.assertEqual("MALFORMED_JSON", ctx.exception.args[0])
+
+    def test_sign_conditional_transfer(self):
+        json_str = "{\"nonce\":\"1\",\"public_key\":\"0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa\",\"expiration_timestamp\":\"1684832800\",\"sender_position_id\":\"2\",\"receiver_public_key\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"receiver_position_id\":\"3\",\"amount\":\"100\",\"asset_id\":\"0xa\",\"fact_registry_address\":\"0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb\",\"fact\":\"0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b\"}"
+        sig = json.loads(zkdex_python_sdk.sign_conditional_transfer(json_str, pri_key))
+        hash = zkdex_python_sdk.hash_conditional_transfer(json_str)
+        self.assertEqual('0x0e82549aeb0699bb64a2f9273ebaeec466b4866db5200a491b9704856ee76bb3', hash)
+        self.assertNotEqual(zkdex_python_sdk.hash_transfer(json_str), hash)
+        self.assertTrue(zkdex_python_sdk.verify_signature(sig['r'], sig['s'], pk_x, pk_y, hash))
+        self.assertTrue(zkdex_python_sdk.verify_conditional_transfer(json_str, sig['r'], sig['s'], pk_x))
+        self.assertFalse(zkdex_python_sdk.verify_conditional_transfer(json_str, sig['r'], sig['s'], '0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a'))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.hash_conditional_transfer(json_str.replace("0xB6aD5EfB", "0xB6aD"))
+        self.assertEqual("MALFORMED_JSON", ctx.exception.args[0])
+
+    def test_poseidon_hash(self):
+        hash = '0x1ae3633579d8817e55590943ae6e61019d09b0728a1736c0e7bb873bdbcd5619'
+        self.assertEqual(hash, zkdex_python_sdk.poseidon_hash_field_elements('["0x1", "0x2"]'))
+        self.assertEqual(hash, zkdex_python_sdk.poseidon_hash_u64_limbs('["1", "0", "0", "0", "2"]'))
+        with self.assertRaises(zkdex_python_sdk.ZkdexError) as ctx:
+            zkdex_python_sdk.poseidon_hash_field_elements('["0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"]')
+        self.assertEqual("INVALID_ARGUMENT", ctx.exception.args[0])
+
 if __name__ == '__main__':
     unittest.main()
\ No newline at end of file
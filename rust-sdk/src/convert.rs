@@ -6,6 +6,8 @@ use num::{
     BigUint,
 };
 
+use crate::error::{Result, ZkdexError};
+
 pub fn trim_0x(s: &str) -> &str {
     s.trim_start_matches("0x")
 }
@@ -21,9 +23,13 @@ pub fn biguint_to_big_decimal(num: BigUint) -> BigDecimal {
     ratio_to_big_decimal(&Ratio::from(num), 0)
 }
 
-pub fn big_decimal_to_ratio(num: &BigDecimal) -> Result<Ratio<BigUint>, anyhow::Error> {
+pub fn big_decimal_to_ratio(num: &BigDecimal) -> Result<Ratio<BigUint>> {
     let (big_int, exp) = num.as_bigint_and_exponent();
-    anyhow::ensure!(!big_int.is_negative(), "BigDecimal should be unsigned");
+    if big_int.is_negative() {
+        return Err(ZkdexError::InvalidArgument(
+            "BigDecimal should be unsigned".to_string(),
+        ));
+    }
     let big_uint = big_int.to_biguint().unwrap();
     let ten_pow = BigUint::from(10_u32).pow(exp as u128 as u32);
     Ok(Ratio::new(big_uint, ten_pow))
@@ -63,7 +69,7 @@ pub fn scaled_u64_to_ratio(num: u64) -> Ratio<BigUint> {
     Ratio::from(BigUint::from(num)) / BigUint::from(SUBSIDY_USD_AMOUNTS_SCALE)
 }
 
-pub fn scaled_big_decimal_to_ratio(num: BigDecimal) -> Result<Ratio<BigUint>, anyhow::Error> {
+pub fn scaled_big_decimal_to_ratio(num: BigDecimal) -> Result<Ratio<BigUint>> {
     let scale = BigDecimal::from(SUBSIDY_USD_AMOUNTS_SCALE);
 
     let unscaled = num / scale;
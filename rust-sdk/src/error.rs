@@ -0,0 +1,175 @@
+use serde::de::DeserializeOwned;
+use serde_json::error::Category;
+use serde_path_to_error::Segment;
+use thiserror::Error;
+
+use crate::validate::Violation;
+
+pub type Result<T> = std::result::Result<T, ZkdexError>;
+
+/// Error returned by every public function of the sdk.
+/// `code` gives a stable name for each variant so the bindings can classify failures.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ZkdexError {
+    #[error("malformed json at `{path}`: {reason}")]
+    MalformedJson { path: String, reason: String },
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("amount out of range at `{field}`: {reason}")]
+    AmountOutOfRange { field: String, reason: String },
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("invalid hash: {0}")]
+    InvalidHash(String),
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("invalid keystore: {0}")]
+    InvalidKeystore(String),
+    #[error("invalid fields: {}", join_violations(.0))]
+    InvalidFields(Vec<Violation>),
+}
+
+fn join_violations(violations: &[Violation]) -> String {
+    let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    violations.join("; ")
+}
+
+impl ZkdexError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ZkdexError::MalformedJson { .. } => "MALFORMED_JSON",
+            ZkdexError::InvalidPublicKey(_) => "INVALID_PUBLIC_KEY",
+            ZkdexError::InvalidPrivateKey(_) => "INVALID_PRIVATE_KEY",
+            ZkdexError::AmountOutOfRange { .. } => "AMOUNT_OUT_OF_RANGE",
+            ZkdexError::InvalidSignature(_) => "INVALID_SIGNATURE",
+            ZkdexError::InvalidHash(_) => "INVALID_HASH",
+            ZkdexError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            ZkdexError::InvalidTransaction(_) => "INVALID_TRANSACTION",
+            ZkdexError::InvalidKeystore(_) => "INVALID_KEYSTORE",
+            ZkdexError::InvalidFields(_) => "INVALID_FIELDS",
+        }
+    }
+
+    pub(crate) fn amount(field: &str, reason: &str) -> Self {
+        ZkdexError::AmountOutOfRange {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+fn is_public_key_field(field: &str) -> bool {
+    field.ends_with("_key")
+}
+
+fn is_amount_field(field: &str) -> bool {
+    field.starts_with("amount")
+        || field.starts_with("actual_")
+        || field.ends_with("_fee")
+        || field.ends_with("price")
+}
+
+/// The messages serde gives a json of the wrong shape, any other data error is a value the field
+/// rejected.
+const SHAPE_ERRORS: [&str; 6] = [
+    "invalid type",
+    "invalid length",
+    "missing field",
+    "unknown field",
+    "unknown variant",
+    "duplicate field",
+];
+
+fn is_shape_error(reason: &str) -> bool {
+    SHAPE_ERRORS.iter().any(|prefix| reason.starts_with(prefix))
+}
+
+/// A json of the wrong shape is malformed, a value rejected by its field is classified by the
+/// path: a `*_key` field, anything under a `signature`, then an amount or price field.
+impl From<serde_path_to_error::Error<serde_json::Error>> for ZkdexError {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = err.path().to_string();
+        let keys: Vec<&str> = err
+            .path()
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Map { key } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        let field = match err.path().iter().last() {
+            Some(Segment::Map { key }) => key.clone(),
+            _ => String::new(),
+        };
+        let err = err.into_inner();
+        let reason = err.to_string();
+        if err.classify() != Category::Data || is_shape_error(&reason) {
+            ZkdexError::MalformedJson { path, reason }
+        } else if is_public_key_field(&field) {
+            ZkdexError::InvalidPublicKey(format!("{}: {}", path, reason))
+        } else if keys.contains(&"signature") {
+            ZkdexError::InvalidSignature(format!("{}: {}", path, reason))
+        } else if is_amount_field(&field) {
+            ZkdexError::AmountOutOfRange {
+                field: path,
+                reason,
+            }
+        } else {
+            ZkdexError::MalformedJson { path, reason }
+        }
+    }
+}
+
+/// Deserialize `json`, reporting the path of the offending field on failure.
+pub(crate) fn parse_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    let mut de = serde_json::Deserializer::from_str(json);
+    let value = serde_path_to_error::deserialize(&mut de)?;
+    de.end().map_err(|e| ZkdexError::MalformedJson {
+        path: ".".to_string(),
+        reason: e.to_string(),
+    })?;
+    Ok(value)
+}
+
+/// Same as [`parse_json`] for an already parsed json value.
+pub(crate) fn parse_json_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    Ok(serde_path_to_error::deserialize(value)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::unified::transactions::Transfer;
+    use crate::unified::SignedHash;
+
+    #[test]
+    fn test_parse_json_classifies_errors() {
+        let err = parse_json::<Transfer>("{").unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+
+        let json = r#"{"amount":"1","receiver_public_key":"0xzz"}"#;
+        let err = parse_json::<Transfer>(json).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PUBLIC_KEY");
+        assert!(err.to_string().contains("receiver_public_key"));
+
+        let json = r#"{"amount":"-1"}"#;
+        match parse_json::<Transfer>(json).unwrap_err() {
+            ZkdexError::AmountOutOfRange { field, .. } => assert_eq!(field, "amount"),
+            err => panic!("unexpected error {:?}", err),
+        }
+
+        let json = r#"{"amount":1}"#;
+        let err = parse_json::<Transfer>(json).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+        assert!(err.to_string().contains("invalid type"));
+
+        let json = r#"{"signature":{"r":"0xzz","s":"0x1"}}"#;
+        let err = parse_json::<SignedHash>(json).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        assert!(err.to_string().contains("signature.r"));
+    }
+}
@@ -0,0 +1,45 @@
+//! Fixed point numbers with 32 fractional bits, as used by funding indices and internal prices.
+//!
+//! A value `x` is stored as the integer `x * 2^32`. Converting back to an integer rounds, the
+//! caller picks the direction.
+
+use crate::constant::FXP_32_ONE;
+
+pub type Fxp32 = i128;
+
+pub fn from_int(value: i64) -> Fxp32 {
+    value as i128 * FXP_32_ONE as i128
+}
+
+/// The largest integer not above `value`.
+pub fn floor(value: Fxp32) -> i128 {
+    value >> 32
+}
+
+/// The smallest integer not below `value`.
+pub fn ceil(value: Fxp32) -> i128 {
+    -(-value >> 32)
+}
+
+/// `a * b` for a fixed point `a` and an integer `b`, still fixed point. `None` on overflow.
+pub fn mul_int(a: Fxp32, b: i64) -> Option<Fxp32> {
+    a.checked_mul(b as i128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding() {
+        let half = FXP_32_ONE as i128 / 2;
+        assert_eq!(floor(from_int(3) + half), 3);
+        assert_eq!(ceil(from_int(3) + half), 4);
+        assert_eq!(floor(from_int(-3) - half), -4);
+        assert_eq!(ceil(from_int(-3) - half), -3);
+        assert_eq!(floor(from_int(-3)), -3);
+        assert_eq!(ceil(from_int(-3)), -3);
+        assert_eq!(mul_int(half, 5), Some(from_int(2) + half));
+        assert_eq!(mul_int(i128::MAX, 2), None);
+    }
+}
@@ -1,6 +1,18 @@
+//! Poseidon hashing of transaction fields.
+//!
+//! Every hash of the sdk is Poseidon over the BN254 scalar field, width 9, rate 8, with 8 full and
+//! 63 partial rounds. The input is a sequence of u64 limbs, each 4 limbs read as a little endian
+//! 256 bit integer make one field element. A transaction field fills its own 4 limbs, zero padded,
+//! and a 256 bit field has its top bit cleared, so a packed public key fits. The elements are
+//! absorbed 8 at a time after a 1 and then zeros are appended up to a multiple of 8, the hash is
+//! the first rate element of the state after the last block. `test-vectors/poseidon.json` has
+//! vectors of [`poseidon_hash_field_elements`] and [`poseidon_hash_u64_limbs`].
+
 use primitive_types::U256;
 
+use crate::error::{Result, ZkdexError};
 use crate::tx::public_key_type::PublicKeyType;
+use crate::zkw::{PoseidonHasher, MODULUS};
 
 pub trait Hasher {
     fn update_single<T: ToHashable>(&mut self, _data: &T);
@@ -106,15 +118,43 @@ pub fn hash2<T1: ToHashable, T2: ToHashable>(a: &T1, b: &T2) -> U256 {
     hasher.finalize()
 }
 
+/// Poseidon of `elements`, each one below the field modulus.
+pub fn poseidon_hash_field_elements(elements: &[U256]) -> Result<U256> {
+    let mut hasher = PoseidonHasher::new();
+    for element in elements {
+        if *element >= U256(MODULUS) {
+            return Err(ZkdexError::InvalidArgument(format!(
+                "{:#x} is not below the field modulus",
+                element
+            )));
+        }
+        for limb in element.0 {
+            hasher.update(limb);
+        }
+    }
+    Ok(U256(hasher.finalize()))
+}
+
+/// Poseidon of `limbs`, zero padded to a multiple of 4. Each 4 limbs are taken modulo the field,
+/// no bit is cleared.
+pub fn poseidon_hash_u64_limbs(limbs: &[u64]) -> U256 {
+    let mut hasher = PoseidonHasher::new();
+    for limb in limbs {
+        hasher.update(*limb);
+    }
+    U256(hasher.finalize())
+}
+
 #[cfg(test)]
 mod test {
     use std::thread::{spawn, JoinHandle};
 
     use primitive_types::U256;
+    use serde_json::Value;
 
     use crate::hash::{hash2, new_hasher};
 
-    use super::Hasher;
+    use super::*;
 
     #[test]
     fn test_concurrent_hash() {
@@ -135,6 +175,62 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_poseidon_vectors() {
+        let vectors: Value =
+            serde_json::from_str(include_str!("../test-vectors/poseidon.json")).unwrap();
+        let hex = |value: &Value| U256::from_str_radix(value.as_str().unwrap(), 16).unwrap();
+        for vector in vectors["field_elements"].as_array().unwrap() {
+            let elements: Vec<U256> = vector["input"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(hex)
+                .collect();
+            assert_eq!(
+                poseidon_hash_field_elements(&elements).unwrap(),
+                hex(&vector["output"]),
+                "{}",
+                vector
+            );
+        }
+        for vector in vectors["u64_limbs"].as_array().unwrap() {
+            let limbs: Vec<u64> = vector["input"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|limb| limb.as_str().unwrap().parse().unwrap())
+                .collect();
+            assert_eq!(
+                poseidon_hash_u64_limbs(&limbs),
+                hex(&vector["output"]),
+                "{}",
+                vector
+            );
+        }
+    }
+
+    #[test]
+    fn test_poseidon_packing() {
+        // a 256 bit field has its top bit cleared
+        let (a, b) = (U256::from(1), (U256::one() << 255) + 5);
+        let masked = U256::from(5);
+        let expected = poseidon_hash_field_elements(&[a, masked]).unwrap();
+        assert_eq!(hash2(&a, &b), expected);
+
+        let mut limbs = a.0.to_vec();
+        limbs.extend(&masked.0);
+        assert_eq!(poseidon_hash_u64_limbs(&limbs), expected);
+
+        let mut hasher = new_hasher();
+        hasher.update_single(&7u64);
+        hasher.update_single(&a);
+        assert_eq!(hasher.finalize(), poseidon_hash_u64_limbs(&[7, 0, 0, 0, 1]));
+
+        let err = poseidon_hash_field_elements(&[U256(MODULUS)]).unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+    }
+
     #[test]
     fn test_hash_1() {
         let mut hasher = new_hasher();
@@ -1,27 +1,54 @@
 #[cfg(feature = "java")]
 pub mod java_bridge {
+    use std::collections::HashMap;
     use std::panic;
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::{Arc, Mutex};
 
     use jni::objects::*;
-    use jni::sys::{jboolean, jstring};
+    use jni::sys::{jboolean, jint, jlong, jstring};
     use jni::JNIEnv;
+    use once_cell::sync::Lazy;
     use serde::Serialize;
 
+    use crate::keystore::Keystore;
     use crate::unified::{
-        unified_hash_liquidate, unified_hash_oracle_price, unified_hash_perpetual_trade,
-        unified_hash_spot_trade, unified_hash_transfer, unified_hash_withdrawal,
-        unified_sign_liquidate, unified_sign_oracle_price, unified_sign_perpetual_trade,
-        unified_sign_spot_trade, unified_sign_transfer, unified_sign_withdrawal,
+        batch_hash_any, batch_sign_any, batch_sign_any_with, batch_verify, hash_any, sign_any,
+        sign_any_unchecked, unified_hash_liquidate, unified_hash_oracle_price,
+        unified_hash_perpetual_trade, unified_hash_spot_trade, unified_hash_transfer,
+        unified_hash_withdrawal, unified_sign_liquidate, unified_sign_liquidate_envelope,
+        unified_sign_liquidate_unchecked, unified_sign_oracle_price,
+        unified_sign_oracle_price_envelope, unified_sign_oracle_price_unchecked,
+        unified_sign_perpetual_limit_order_envelope, unified_sign_perpetual_trade,
+        unified_sign_perpetual_trade_envelope, unified_sign_perpetual_trade_unchecked,
+        unified_sign_spot_limit_order_envelope, unified_sign_spot_trade,
+        unified_sign_spot_trade_envelope, unified_sign_spot_trade_unchecked, unified_sign_transfer,
+        unified_sign_transfer_envelope, unified_sign_transfer_unchecked, unified_sign_withdrawal,
+        unified_sign_withdrawal_envelope, unified_sign_withdrawal_unchecked,
+        unified_verify_liquidate, unified_verify_oracle_price,
+        unified_verify_perpetual_limit_order, unified_verify_perpetual_trade,
+        unified_verify_spot_limit_order, unified_verify_spot_trade, unified_verify_transfer,
+        unified_verify_withdrawal, verify_any, AnyHash, BatchItem,
     };
     use crate::zkw::JubjubSignature;
     use crate::{
-        hash_limit_order, hash_liquidate, hash_signed_oracle_price, hash_spot_limit_order,
-        hash_spot_transfer, hash_spot_withdrawal, hash_transfer, hash_withdraw, is_on_curve,
-        l2_sign, l2_verify, private_key_from_seed, private_key_to_pubkey_xy, pub_key_to_xy, sign,
-        sign_limit_order, sign_liquidate, sign_signed_oracle_price, sign_spot_limit_order,
-        sign_spot_transfer, sign_spot_withdrawal, sign_transfer, sign_withdraw,
+        decrypt_keystore, derive_extended_public_key, derive_private_key, derive_public_key,
+        encrypt_keystore, generate_private_key, hash_conditional_transfer, hash_limit_order,
+        hash_liquidate, hash_signed_oracle_price, hash_spot_limit_order, hash_spot_transfer,
+        hash_spot_withdrawal, hash_transfer, hash_withdraw, is_on_curve, l2_key_from_eth_signature,
+        l2_sign, l2_verify, onboarding_message, poseidon_hash_field_elements_json,
+        poseidon_hash_u64_limbs_json, private_key_from_seed, private_key_to_pubkey_xy,
+        pub_key_to_xy, sign, sign_conditional_transfer, sign_conditional_transfer_unchecked,
+        sign_limit_order, sign_limit_order_unchecked, sign_liquidate, sign_liquidate_unchecked,
+        sign_signed_oracle_price, sign_signed_oracle_price_unchecked, sign_spot_limit_order,
+        sign_spot_limit_order_unchecked, sign_spot_transfer, sign_spot_transfer_unchecked,
+        sign_spot_withdrawal, sign_spot_withdrawal_unchecked, sign_transfer,
+        sign_transfer_unchecked, sign_withdraw, sign_withdraw_unchecked,
         unified_hash_perpetual_limit_order, unified_hash_spot_limit_order,
-        unified_sign_perpetual_limit_order, unified_sign_spot_limit_order, verify_signature,
+        unified_sign_perpetual_limit_order, unified_sign_perpetual_limit_order_unchecked,
+        unified_sign_spot_limit_order, unified_sign_spot_limit_order_unchecked,
+        verify_conditional_transfer, verify_signature, verify_transfer, NonceMode, SecretKey,
+        Verifier, ZkdexError,
     };
 
     #[no_mangle]
@@ -45,13 +72,15 @@ pub mod java_bridge {
             let pub_key_x: String = pub_key_x.expect("Couldn't get java pub_key_x").into();
             let pub_key_y: String = pub_key_y.expect("Couldn't get java pub_key_x").into();
             let msg: String = msg.expect("Couldn't get java msg").into();
-            let result = verify_signature(&sig_r, &sig_s, &pub_key_x, &pub_key_y, &msg)
-                .expect("Couldn't get verify_signature result");
-            jboolean::from(result)
+            verify_signature(&sig_r, &sig_s, &pub_key_x, &pub_key_y, &msg)
         });
 
         match result {
-            Ok(r) => r,
+            Ok(Ok(r)) => jboolean::from(r),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -74,14 +103,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_withdraw(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_withdraw(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -92,7 +125,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signWithdrawUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -104,14 +137,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_transfer(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_withdraw_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -122,7 +159,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -134,14 +171,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_limit_order(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_transfer(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -152,7 +193,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signLiquidate<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signTransferUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -164,14 +205,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_liquidate(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_transfer_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -182,7 +227,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signSignedOraclePrice<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signLimitOrder<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -194,14 +239,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_signed_oracle_price(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_limit_order(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -212,23 +261,33 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashWithdraw<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signLimitOrderUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_withdraw(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_limit_order_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -236,23 +295,33 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signLiquidate<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_transfer(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_liquidate(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -260,23 +329,33 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signLiquidateUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_limit_order(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_liquidate_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -284,23 +363,33 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashLiquidate<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signSignedOraclePrice<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_liquidate(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_signed_oracle_price(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -308,23 +397,33 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashSignedOraclePrice<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signSignedOraclePriceUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_signed_oracle_price(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_signed_oracle_price_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -332,25 +431,24 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_sign<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_hashWithdraw<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        private_key: JString<'local>,
-        msg: JString<'local>,
+        json: JString<'local>,
     ) -> jstring {
-        let private_key = env.get_string(&private_key);
-        let msg = env.get_string(&msg);
+        let json = env.get_string(&json);
         match panic::catch_unwind(|| {
-            let msg: String = msg.expect("Couldn't get java msg").into();
-            let private_key: String = private_key.expect("Couldn't get java json").into();
-            sign(&private_key, &msg).expect("Couldn't sign msg")
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_withdraw(&json)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -361,25 +459,24 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_ethSign<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_hashTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        private_key: JString<'local>,
-        msg: JString<'local>,
+        json: JString<'local>,
     ) -> jstring {
-        let private_key = env.get_string(&private_key);
-        let msg = env.get_string(&msg);
+        let json = env.get_string(&json);
         match panic::catch_unwind(|| {
-            let msg: String = msg.expect("Couldn't get java msg").into();
-            let private_key: String = private_key.expect("Couldn't get java json").into();
-            l2_sign(&msg, &private_key).expect("Couldn't sign msg")
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_transfer(&json)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -390,34 +487,32 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_l2Verify<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_verifyTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        x: JString<'local>,
-        y: JString<'local>,
-        s: JString<'local>,
-        pk_x: JString<'local>,
-        pk_y: JString<'local>,
-        msg: JString<'local>,
+        json: JString<'local>,
+        sig_r: JString<'local>,
+        sig_s: JString<'local>,
+        pub_key: JString<'local>,
     ) -> jboolean {
-        let x = env.get_string(&x);
-        let y = env.get_string(&y);
-        let s = env.get_string(&s);
-        let pk_x = env.get_string(&pk_x);
-        let pk_y = env.get_string(&pk_y);
-        let msg = env.get_string(&msg);
+        let json = env.get_string(&json);
+        let sig_r = env.get_string(&sig_r);
+        let sig_s = env.get_string(&sig_s);
+        let pub_key = env.get_string(&pub_key);
 
         match panic::catch_unwind(|| {
-            let x: String = x.expect("Couldn't get java x").into();
-            let y: String = y.expect("Couldn't get java y").into();
-            let s: String = s.expect("Couldn't get java s").into();
-            let pk_x: String = pk_x.expect("Couldn't get java pk_x").into();
-            let pk_y: String = pk_y.expect("Couldn't get java pk_y").into();
-            let msg: String = msg.expect("Couldn't get java msg").into();
-
-            l2_verify(&x, &y, &s, &pk_x, &pk_y, &msg).expect("Couldn't verify")
+            let json: String = json.expect("Couldn't get java json").into();
+            let sig_r: String = sig_r.expect("Couldn't get java sig_r").into();
+            let sig_s: String = sig_s.expect("Couldn't get java sig_s").into();
+            let pub_key: String = pub_key.expect("Couldn't get java pub_key").into();
+            let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+            verify_transfer(&json, sig, &pub_key)
         }) {
-            Ok(ret) => jboolean::from(ret),
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -428,84 +523,67 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_privateKeyFromSeed<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signConditionalTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        seed: JString<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
-        let seed = env.get_string(&seed);
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
 
         match panic::catch_unwind(|| {
-            let seed: String = seed.expect("Couldn't get java seed").into();
-            private_key_from_seed(seed.as_bytes()).expect("Couldn't derive private key from seed")
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_conditional_transfer(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
-                env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
-                    .expect("throw");
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
                 std::ptr::null_mut()
             }
-        }
-    }
-
-    #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_isOnCurve<'local>(
-        mut env: JNIEnv<'local>,
-        _class: JClass<'local>,
-        x: JString<'local>,
-        y: JString<'local>,
-    ) -> jboolean {
-        let x = env.get_string(&x);
-        let y = env.get_string(&y);
-        match panic::catch_unwind(|| {
-            let x: String = x.expect("Couldn't get java string x").into();
-            let y: String = y.expect("Couldn't get java string x").into();
-            is_on_curve(&x, &y).expect("Couldn't get verify xy is on curve")
-        }) {
-            Ok(ret) => jboolean::from(ret),
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
-                jboolean::from(false)
+                std::ptr::null_mut()
             }
         }
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_privateKeyToPublicKeyXY<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signConditionalTransferUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        private_key: JString<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
-        let private_key = env.get_string(&private_key);
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
 
         match panic::catch_unwind(|| {
-            let private_key: String = private_key
-                .expect("Couldn't get java private key string")
-                .into();
-            private_key_to_pubkey_xy(&private_key)
-                .expect("Couldn't convert private key to public key xy")
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_conditional_transfer_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                #[derive(Serialize)]
-                struct XY {
-                    x: String,
-                    y: String,
-                }
-                let xy = XY { x: ret.0, y: ret.1 };
+            Ok(Ok(ret)) => {
                 let output = env
-                    .new_string(serde_json::to_string(&xy).unwrap())
+                    .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -513,29 +591,24 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_publicKeyToXY<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_hashConditionalTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        public_key: JString<'local>,
+        json: JString<'local>,
     ) -> jstring {
-        let public_key = env.get_string(&public_key);
-
+        let json = env.get_string(&json);
         match panic::catch_unwind(|| {
-            let public_key: String = public_key.expect("Couldn't get java string").into();
-            pub_key_to_xy(&public_key).expect("Couldn't convert public key to xy")
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_conditional_transfer(&json)
         }) {
-            Ok(ret) => {
-                #[derive(Serialize)]
-                struct XY {
-                    x: String,
-                    y: String,
-                }
-                let xy = XY { x: ret.0, y: ret.1 };
-                let output = env
-                    .new_string(serde_json::to_string(&xy).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -546,26 +619,1779 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signSpotWithdrawal<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_verifyConditionalTransfer<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
-        pri_key: JString<'local>,
-    ) -> jstring {
+        sig_r: JString<'local>,
+        sig_s: JString<'local>,
+        pub_key: JString<'local>,
+    ) -> jboolean {
         let json = env.get_string(&json);
-        let pri_key = env.get_string(&pri_key);
+        let sig_r = env.get_string(&sig_r);
+        let sig_s = env.get_string(&sig_s);
+        let pub_key = env.get_string(&pub_key);
 
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_spot_withdrawal(&json, &pri_key).expect("Couldn get jubjubSignature")
+            let sig_r: String = sig_r.expect("Couldn't get java sig_r").into();
+            let sig_s: String = sig_s.expect("Couldn't get java sig_s").into();
+            let pub_key: String = pub_key.expect("Couldn't get java pub_key").into();
+            let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+            verify_conditional_transfer(&json, sig, &pub_key)
         }) {
-            Ok(ret) => {
-                let output = env
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_poseidonHashFieldElements<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            poseidon_hash_field_elements_json(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_poseidonHashU64Limbs<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            poseidon_hash_u64_limbs_json(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashLiquidate<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_liquidate(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashSignedOraclePrice<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_signed_oracle_price(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_sign<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        private_key: JString<'local>,
+        msg: JString<'local>,
+    ) -> jstring {
+        let private_key = env.get_string(&private_key);
+        let msg = env.get_string(&msg);
+        match panic::catch_unwind(|| {
+            let msg: String = msg.expect("Couldn't get java msg").into();
+            let private_key: String = private_key.expect("Couldn't get java json").into();
+            sign(&private_key, &msg)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_ethSign<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        private_key: JString<'local>,
+        msg: JString<'local>,
+    ) -> jstring {
+        let private_key = env.get_string(&private_key);
+        let msg = env.get_string(&msg);
+        match panic::catch_unwind(|| {
+            let msg: String = msg.expect("Couldn't get java msg").into();
+            let private_key: String = private_key.expect("Couldn't get java json").into();
+            l2_sign(&msg, &private_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_l2Verify<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        x: JString<'local>,
+        y: JString<'local>,
+        s: JString<'local>,
+        pk_x: JString<'local>,
+        pk_y: JString<'local>,
+        msg: JString<'local>,
+    ) -> jboolean {
+        let x = env.get_string(&x);
+        let y = env.get_string(&y);
+        let s = env.get_string(&s);
+        let pk_x = env.get_string(&pk_x);
+        let pk_y = env.get_string(&pk_y);
+        let msg = env.get_string(&msg);
+
+        match panic::catch_unwind(|| {
+            let x: String = x.expect("Couldn't get java x").into();
+            let y: String = y.expect("Couldn't get java y").into();
+            let s: String = s.expect("Couldn't get java s").into();
+            let pk_x: String = pk_x.expect("Couldn't get java pk_x").into();
+            let pk_y: String = pk_y.expect("Couldn't get java pk_y").into();
+            let msg: String = msg.expect("Couldn't get java msg").into();
+
+            l2_verify(&x, &y, &s, &pk_x, &pk_y, &msg)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_privateKeyFromSeed<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        seed: JString<'local>,
+    ) -> jstring {
+        let seed = env.get_string(&seed);
+
+        match panic::catch_unwind(|| {
+            let seed: String = seed.expect("Couldn't get java seed").into();
+            private_key_from_seed(seed.as_bytes())
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_generatePrivateKey<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+    ) -> jstring {
+        match panic::catch_unwind(generate_private_key) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_derivePrivateKey<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        seed: JString<'local>,
+        path: JString<'local>,
+    ) -> jstring {
+        let seed = env.get_string(&seed);
+        let path = env.get_string(&path);
+
+        match panic::catch_unwind(|| {
+            let seed: String = seed.expect("Couldn't get java seed").into();
+            let path: String = path.expect("Couldn't get java path").into();
+            derive_private_key(seed.as_bytes(), &path)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_deriveExtendedPublicKey<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        seed: JString<'local>,
+        path: JString<'local>,
+    ) -> jstring {
+        let seed = env.get_string(&seed);
+        let path = env.get_string(&path);
+
+        match panic::catch_unwind(|| {
+            let seed: String = seed.expect("Couldn't get java seed").into();
+            let path: String = path.expect("Couldn't get java path").into();
+            derive_extended_public_key(seed.as_bytes(), &path)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_derivePublicKey<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        public_key: JString<'local>,
+        chain_code: JString<'local>,
+        path: JString<'local>,
+    ) -> jstring {
+        let public_key = env.get_string(&public_key);
+        let chain_code = env.get_string(&chain_code);
+        let path = env.get_string(&path);
+
+        match panic::catch_unwind(|| {
+            let public_key: String = public_key.expect("Couldn't get java public_key").into();
+            let chain_code: String = chain_code.expect("Couldn't get java chain_code").into();
+            let path: String = path.expect("Couldn't get java path").into();
+            derive_public_key(&public_key, &chain_code, &path)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_encryptKeystore<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        private_key: JString<'local>,
+        password: JString<'local>,
+    ) -> jstring {
+        let private_key = env.get_string(&private_key);
+        let password = env.get_string(&password);
+
+        match panic::catch_unwind(|| {
+            let private_key: String = private_key.expect("Couldn't get java private_key").into();
+            let password: String = password.expect("Couldn't get java password").into();
+            encrypt_keystore(&private_key, &password)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_decryptKeystore<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        keystore: JString<'local>,
+        password: JString<'local>,
+    ) -> jstring {
+        let keystore = env.get_string(&keystore);
+        let password = env.get_string(&password);
+
+        match panic::catch_unwind(|| {
+            let keystore: String = keystore.expect("Couldn't get java keystore").into();
+            let password: String = password.expect("Couldn't get java password").into();
+            decrypt_keystore(&keystore, &password)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_onboardingMessage<'local>(
+        env: JNIEnv<'local>,
+        _class: JClass<'local>,
+    ) -> jstring {
+        let output = env
+            .new_string(onboarding_message())
+            .expect("Couldn't create java string!");
+        output.into_raw()
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_l2KeyFromEthSignature<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        eth_signature: JString<'local>,
+        address: JString<'local>,
+    ) -> jstring {
+        let eth_signature = env.get_string(&eth_signature);
+        let address = env.get_string(&address);
+
+        match panic::catch_unwind(|| {
+            let eth_signature: String = eth_signature
+                .expect("Couldn't get java eth_signature")
+                .into();
+            let address: String = address.expect("Couldn't get java address").into();
+            l2_key_from_eth_signature(&eth_signature, &address)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_isOnCurve<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        x: JString<'local>,
+        y: JString<'local>,
+    ) -> jboolean {
+        let x = env.get_string(&x);
+        let y = env.get_string(&y);
+        match panic::catch_unwind(|| {
+            let x: String = x.expect("Couldn't get java string x").into();
+            let y: String = y.expect("Couldn't get java string x").into();
+            is_on_curve(&x, &y)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_privateKeyToPublicKeyXY<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        private_key: JString<'local>,
+    ) -> jstring {
+        let private_key = env.get_string(&private_key);
+
+        match panic::catch_unwind(|| {
+            let private_key: String = private_key
+                .expect("Couldn't get java private key string")
+                .into();
+            private_key_to_pubkey_xy(&private_key)
+        }) {
+            Ok(Ok(ret)) => {
+                #[derive(Serialize)]
+                struct XY {
+                    x: String,
+                    y: String,
+                }
+                let xy = XY { x: ret.0, y: ret.1 };
+                let output = env
+                    .new_string(serde_json::to_string(&xy).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_publicKeyToXY<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        public_key: JString<'local>,
+    ) -> jstring {
+        let public_key = env.get_string(&public_key);
+
+        match panic::catch_unwind(|| {
+            let public_key: String = public_key.expect("Couldn't get java string").into();
+            pub_key_to_xy(&public_key)
+        }) {
+            Ok(Ok(ret)) => {
+                #[derive(Serialize)]
+                struct XY {
+                    x: String,
+                    y: String,
+                }
+                let xy = XY { x: ret.0, y: ret.1 };
+                let output = env
+                    .new_string(serde_json::to_string(&xy).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotWithdrawal<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_withdrawal(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotWithdrawalUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_withdrawal_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotTransfer<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_transfer(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotTransferUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_transfer_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_limit_order(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signSpotLimitOrderUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_spot_limit_order_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotWithdrawal<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_spot_withdrawal(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotTransfer<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_spot_transfer(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_spot_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignWithdrawal<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_withdrawal(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignWithdrawalUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_withdrawal_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashWithdrawal<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_withdrawal(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignTransfer<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_transfer(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignTransferUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_transfer_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashTransfer<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_transfer(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key_a: JString<'local>,
+        pri_key_b: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key_a = env.get_string(&pri_key_a);
+        let pri_key_b = env.get_string(&pri_key_b);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
+            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
+            unified_sign_spot_trade(&json, &pri_key_a, &pri_key_b)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_sig = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_sig).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotTradeUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key_a: JString<'local>,
+        pri_key_b: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key_a = env.get_string(&pri_key_a);
+        let pri_key_b = env.get_string(&pri_key_b);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
+            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
+            unified_sign_spot_trade_unchecked(&json, &pri_key_a, &pri_key_b)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_sig = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_sig).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashSpotTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_spot_trade(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_h = ComposeHash {
+                    hash_a: ret.0,
+                    hash_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_h).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key_a: JString<'local>,
+        pri_key_b: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key_a = env.get_string(&pri_key_a);
+        let pri_key_b = env.get_string(&pri_key_b);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
+            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
+            unified_sign_perpetual_trade(&json, &pri_key_a, &pri_key_b)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_sig = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_sig).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualTradeUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key_a: JString<'local>,
+        pri_key_b: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key_a = env.get_string(&pri_key_a);
+        let pri_key_b = env.get_string(&pri_key_b);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
+            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
+            unified_sign_perpetual_trade_unchecked(&json, &pri_key_a, &pri_key_b)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_sig = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_sig).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashPerpetualTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_perpetual_trade(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let c_h = ComposeHash {
+                    hash_a: ret.0,
+                    hash_b: ret.1,
+                };
+                let output = env
+                    .new_string(serde_json::to_string(&c_h).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignOraclePrice<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_oracle_price(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignOraclePriceUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_oracle_price_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashOraclePrice<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_oracle_price(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignLiquidate<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_liquidate(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignLiquidateUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_liquidate_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashLiquidate<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_liquidate(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_spot_limit_order(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotLimitOrderUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_spot_limit_order_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashSpotLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_spot_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_perpetual_limit_order(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualLimitOrderUnchecked<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_perpetual_limit_order_unchecked(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashPerpetualLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_hash_perpetual_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_signAny<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        pri_key: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            sign_any(&json, &pri_key)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -574,8 +2400,9 @@ pub mod java_bridge {
             }
         }
     }
+
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signSpotTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_signAnyUnchecked<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -587,14 +2414,18 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_spot_transfer(&json, &pri_key).expect("Couldn get jubjubSignature")
+            sign_any_unchecked(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(err) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
@@ -605,29 +2436,253 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_signSpotLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_hashAny<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
-        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
-        let pri_key = env.get_string(&pri_key);
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            hash_any(&json).map(|ret| match ret {
+                AnyHash::Single(hash) => hash,
+                ret => serde_json::to_string(&ret).unwrap(),
+            })
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_verifyAny<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+        sig_r: JString<'local>,
+        sig_s: JString<'local>,
+        pub_key: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+        let sig_r = env.get_string(&sig_r);
+        let sig_s = env.get_string(&sig_s);
+        let pub_key = env.get_string(&pub_key);
 
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            sign_spot_limit_order(&json, &pri_key).expect("Couldn get jubjubSignature")
+            let sig_r: String = sig_r.expect("Couldn't get java sig_r").into();
+            let sig_s: String = sig_s.expect("Couldn't get java sig_s").into();
+            let pub_key: String = pub_key.expect("Couldn't get java pub_key").into();
+            let sig = JubjubSignature::try_from_str(&sig_r, &sig_s)?;
+            verify_any(&json, sig, &pub_key)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyWithdrawal<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_withdrawal(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyTransfer<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_transfer(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyOraclePrice<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_oracle_price(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyLiquidate<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_liquidate(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifySpotLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_spot_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyPerpetualLimitOrder<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jboolean {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_perpetual_limit_order(&json)
+        }) {
+            Ok(Ok(ret)) => jboolean::from(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
+            }
+            Err(err) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                    .expect("throw");
+                jboolean::from(false)
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifySpotTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_spot_trade(&json)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -635,20 +2690,59 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotWithdrawal<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedVerifyPerpetualTrade<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        json: JString<'local>,
+    ) -> jstring {
+        let json = env.get_string(&json);
+
+        match panic::catch_unwind(|| {
+            let json: String = json.expect("Couldn't get java json").into();
+            unified_verify_perpetual_trade(&json)
+        }) {
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
+                output.into_raw()
+            }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+                std::ptr::null_mut()
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignWithdrawalEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_spot_withdrawal(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_withdrawal_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -659,20 +2753,28 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignTransferEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_spot_transfer(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_transfer_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -683,20 +2785,28 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_hashSpotLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignOraclePriceEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            hash_spot_limit_order(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_oracle_price_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -707,7 +2817,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignWithdrawal<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignLiquidateEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -719,17 +2829,19 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_withdrawal(&json, &pri_key).expect("Couldn't get jubjubSignature")
+            unified_sign_liquidate_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -737,20 +2849,28 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashWithdrawal<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotLimitOrderEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_withdrawal(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            unified_sign_spot_limit_order_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -761,7 +2881,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualLimitOrderEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -773,17 +2893,19 @@ pub mod java_bridge {
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_transfer(&json, &pri_key).expect("Couldn't get jubjubSignature")
+            unified_sign_perpetual_limit_order_envelope(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -791,20 +2913,31 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashTransfer<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotTradeEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key_a: JString<'local>,
+        pri_key_b: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key_a = env.get_string(&pri_key_a);
+        let pri_key_b = env.get_string(&pri_key_b);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_transfer(&json).expect("Couldn't get hash")
+            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
+            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
+            unified_sign_spot_trade_envelope(&json, &pri_key_a, &pri_key_b)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -815,7 +2948,7 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotTrade<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualTradeEnvelope<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
@@ -830,22 +2963,19 @@ pub mod java_bridge {
             let json: String = json.expect("Couldn't get java json").into();
             let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
             let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
-            unified_sign_spot_trade(&json, &pri_key_a, &pri_key_b)
-                .expect("Couldn't get jubjubSignature")
+            unified_sign_perpetual_trade_envelope(&json, &pri_key_a, &pri_key_b)
         }) {
-            Ok(ret) => {
-                let c_sig = ComposeSignature {
-                    signature_a: ret.0,
-                    signature_b: ret.1,
-                };
-                let output = env
-                    .new_string(serde_json::to_string(&c_sig).unwrap())
-                    .expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env.new_string(ret).expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -853,26 +2983,31 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashSpotTrade<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_batchSignAny<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
+        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+        let pri_key = env.get_string(&pri_key);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_spot_trade(&json).expect("Couldn't get hash")
+            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
+            batch_sign_any(&json, &pri_key)
         }) {
-            Ok(ret) => {
-                let c_h = ComposeHash {
-                    hash_a: ret.0,
-                    hash_b: ret.1,
-                };
+            Ok(Ok(ret)) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
                 let output = env
-                    .new_string(serde_json::to_string(&c_h).unwrap())
+                    .new_string(serde_json::to_string(&items).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -883,37 +3018,31 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualTrade<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_batchHashAny<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
-        pri_key_a: JString<'local>,
-        pri_key_b: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
-        let pri_key_a = env.get_string(&pri_key_a);
-        let pri_key_b = env.get_string(&pri_key_b);
 
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            let pri_key_a: String = pri_key_a.expect("Coludn't get java pri_key a").into();
-            let pri_key_b: String = pri_key_b.expect("Coludn't get java pri_key b").into();
-            unified_sign_perpetual_trade(&json, &pri_key_a, &pri_key_b)
-                .expect("Couldn't get jubjubSignature")
+            batch_hash_any(&json)
         }) {
-            Ok(ret) => {
-                let c_sig = ComposeSignature {
-                    signature_a: ret.0,
-                    signature_b: ret.1,
-                };
+            Ok(Ok(ret)) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
                 let output = env
-                    .new_string(serde_json::to_string(&c_sig).unwrap())
+                    .new_string(serde_json::to_string(&items).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -921,26 +3050,28 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashPerpetualTrade<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_batchVerify<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
         json: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_perpetual_trade(&json).expect("Couldn't get hash")
+            batch_verify(&json)
         }) {
-            Ok(ret) => {
-                let c_h = ComposeHash {
-                    hash_a: ret.0,
-                    hash_b: ret.1,
-                };
+            Ok(Ok(ret)) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
                 let output = env
-                    .new_string(serde_json::to_string(&c_h).unwrap())
+                    .new_string(serde_json::to_string(&items).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -951,104 +3082,148 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignOraclePrice<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeyNew<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
         pri_key: JString<'local>,
-    ) -> jstring {
-        let json = env.get_string(&json);
+    ) -> jlong {
         let pri_key = env.get_string(&pri_key);
 
         match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_oracle_price(&json, &pri_key).expect("Couldn't get jubjubSignature")
+            let pri_key: String = pri_key.expect("Couldn't get java pri_key").into();
+            SecretKey::from_hex(&pri_key)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
-                output.into_raw()
+            Ok(Ok(ret)) => register_secret_key(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                0
             }
-            Err(err) => {
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
-                std::ptr::null_mut()
+                0
             }
         }
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashOraclePrice<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeyFromKeystore<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
-    ) -> jstring {
-        let json = env.get_string(&json);
+        keystore: JString<'local>,
+        password: JString<'local>,
+    ) -> jlong {
+        let keystore = env.get_string(&keystore);
+        let password = env.get_string(&password);
+
         match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_oracle_price(&json).expect("Couldn't get hash")
+            let keystore: String = keystore.expect("Couldn't get java keystore").into();
+            let password: String = password.expect("Couldn't get java password").into();
+            Keystore::from_json(&keystore)?.decrypt(&password)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
-                output.into_raw()
+            Ok(Ok(ret)) => register_secret_key(ret),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                0
             }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
-                std::ptr::null_mut()
+                0
             }
         }
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignLiquidate<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeyFree<'local>(
+        _env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        handle: jlong,
+    ) {
+        SECRET_KEYS.lock().unwrap().remove(&handle);
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeySetNonceMode<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
-        pri_key: JString<'local>,
-    ) -> jstring {
-        let json = env.get_string(&json);
-        let pri_key = env.get_string(&pri_key);
+        handle: jlong,
+        mode: JString<'local>,
+    ) {
+        let mode = env.get_string(&mode);
 
         match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_liquidate(&json, &pri_key).expect("Couldn't get jubjubSignature")
+            let mode: String = mode.expect("Couldn't get java mode").into();
+            let mode: NonceMode = mode.parse()?;
+            update_secret_key(handle, |key| key.set_nonce_mode(mode))
         }) {
-            Ok(ret) => {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => throw_zkdex_error(&mut env, &err),
+            Err(e) => {
+                env.exception_clear().expect("clear");
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
+                    .expect("throw");
+            }
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeySetVerifySignatures<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        handle: jlong,
+        verify: jboolean,
+    ) {
+        if let Err(err) = update_secret_key(handle, |key| key.set_verify_signatures(verify != 0)) {
+            throw_zkdex_error(&mut env, &err);
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeyPublicKey<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        handle: jlong,
+    ) -> jstring {
+        match secret_key(handle) {
+            Ok(key) => {
                 let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .new_string(key.public_key().to_string())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
             Err(err) => {
-                env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
-                    .expect("throw");
+                throw_zkdex_error(&mut env, &err);
                 std::ptr::null_mut()
             }
         }
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashLiquidate<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeySign<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
+        handle: jlong,
+        msg: JString<'local>,
     ) -> jstring {
-        let json = env.get_string(&json);
+        let msg = env.get_string(&msg);
+
         match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_liquidate(&json).expect("Couldn't get hash")
+            let msg: String = msg.expect("Couldn't get java msg").into();
+            secret_key(handle)?.sign(&msg)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let output = env
+                    .new_string(serde_json::to_string(&ret).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -1059,29 +3234,31 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignSpotLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeySignAny<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
+        handle: jlong,
         json: JString<'local>,
-        pri_key: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
-        let pri_key = env.get_string(&pri_key);
 
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_spot_limit_order(&json, &pri_key).expect("Couldn't get jubjubSignature")
+            secret_key(handle)?.sign_any(&json)
         }) {
-            Ok(ret) => {
+            Ok(Ok(ret)) => {
                 let output = env
                     .new_string(serde_json::to_string(&ret).unwrap())
                     .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(err) => {
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
                 std::ptr::null_mut()
             }
@@ -1089,20 +3266,29 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashSpotLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_secretKeyBatchSignAny<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
+        handle: jlong,
         json: JString<'local>,
     ) -> jstring {
         let json = env.get_string(&json);
+
         match panic::catch_unwind(|| {
             let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_spot_limit_order(&json).expect("Couldn't get hash")
+            batch_sign_any_with(&json, &secret_key(handle)?)
         }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+            Ok(Ok(ret)) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                let output = env
+                    .new_string(serde_json::to_string(&items).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                std::ptr::null_mut()
+            }
             Err(e) => {
                 env.exception_clear().expect("clear");
                 env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
@@ -1113,60 +3299,159 @@ pub mod java_bridge {
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedSignPerpetualLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_verifierNew<'local>(
+        _env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        capacity: jint,
+    ) -> jlong {
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+        let verifier = Verifier::new(capacity.max(0) as usize);
+        VERIFIERS.lock().unwrap().insert(handle, Arc::new(verifier));
+        handle
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_verifierFree<'local>(
+        _env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        handle: jlong,
+    ) {
+        VERIFIERS.lock().unwrap().remove(&handle);
+    }
+
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_verifierVerifySignature<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
-        pri_key: JString<'local>,
-    ) -> jstring {
-        let json = env.get_string(&json);
-        let pri_key = env.get_string(&pri_key);
+        handle: jlong,
+        sig_r: JString<'local>,
+        sig_s: JString<'local>,
+        pub_key: JString<'local>,
+        msg: JString<'local>,
+    ) -> jboolean {
+        let sig_r = env.get_string(&sig_r);
+        let sig_s = env.get_string(&sig_s);
+        let pub_key = env.get_string(&pub_key);
+        let msg = env.get_string(&msg);
 
         match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            let pri_key: String = pri_key.expect("Coludn't get java pri_key").into();
-            unified_sign_perpetual_limit_order(&json, &pri_key)
-                .expect("Couldn't get jubjubSignature")
+            let sig_r: String = sig_r.expect("Couldn't get java sig_r").into();
+            let sig_s: String = sig_s.expect("Couldn't get java sig_s").into();
+            let pub_key: String = pub_key.expect("Couldn't get java pub_key").into();
+            let msg: String = msg.expect("Couldn't get java msg").into();
+            verifier(handle)?.verify_signature(&sig_r, &sig_s, &pub_key, &msg)
         }) {
-            Ok(ret) => {
-                let output = env
-                    .new_string(serde_json::to_string(&ret).unwrap())
-                    .expect("Couldn't create java string!");
-                output.into_raw()
+            Ok(Ok(r)) => jboolean::from(r),
+            Ok(Err(err)) => {
+                throw_zkdex_error(&mut env, &err);
+                jboolean::from(false)
             }
-            Err(err) => {
+            Err(e) => {
                 env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{err:?}"))
+                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
                     .expect("throw");
-                std::ptr::null_mut()
+                jboolean::from(false)
             }
         }
     }
 
     #[no_mangle]
-    pub extern "system" fn Java_com_okx_ZKDEX_unifiedHashPerpetualLimitOrder<'local>(
+    pub extern "system" fn Java_com_okx_ZKDEX_verifierStats<'local>(
         mut env: JNIEnv<'local>,
         _class: JClass<'local>,
-        json: JString<'local>,
+        handle: jlong,
     ) -> jstring {
-        let json = env.get_string(&json);
-        match panic::catch_unwind(|| {
-            let json: String = json.expect("Couldn't get java json").into();
-            unified_hash_perpetual_limit_order(&json).expect("Couldn't get hash")
-        }) {
-            Ok(ret) => {
-                let output = env.new_string(ret).expect("Couldn't create java string!");
+        match verifier(handle) {
+            Ok(verifier) => {
+                let output = env
+                    .new_string(serde_json::to_string(&verifier.stats()).unwrap())
+                    .expect("Couldn't create java string!");
                 output.into_raw()
             }
-            Err(e) => {
-                env.exception_clear().expect("clear");
-                env.throw_new("Ljava/lang/Exception;", format!("{e:?}"))
-                    .expect("throw");
+            Err(err) => {
+                throw_zkdex_error(&mut env, &err);
                 std::ptr::null_mut()
             }
         }
     }
 
+    #[no_mangle]
+    pub extern "system" fn Java_com_okx_ZKDEX_verifierClear<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        handle: jlong,
+    ) {
+        match verifier(handle) {
+            Ok(verifier) => verifier.clear(),
+            Err(err) => throw_zkdex_error(&mut env, &err),
+        }
+    }
+
+    /// Keys handed to java as opaque handles, an unknown or freed handle fails instead of
+    /// touching freed memory.
+    static SECRET_KEYS: Lazy<Mutex<HashMap<jlong, Arc<SecretKey>>>> = Lazy::new(Default::default);
+    static NEXT_HANDLE: AtomicI64 = AtomicI64::new(1);
+
+    fn register_secret_key(key: SecretKey) -> jlong {
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+        SECRET_KEYS.lock().unwrap().insert(handle, Arc::new(key));
+        handle
+    }
+
+    fn secret_key(handle: jlong) -> Result<Arc<SecretKey>, ZkdexError> {
+        SECRET_KEYS
+            .lock()
+            .unwrap()
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| unknown_secret_key(handle))
+    }
+
+    /// A key in use by another thread is shared, so the handle gets an updated copy instead.
+    fn update_secret_key<F>(handle: jlong, update: F) -> Result<(), ZkdexError>
+    where
+        F: FnOnce(&mut SecretKey),
+    {
+        let mut keys = SECRET_KEYS.lock().unwrap();
+        let key = keys
+            .get_mut(&handle)
+            .ok_or_else(|| unknown_secret_key(handle))?;
+        let mut copy = key.duplicate();
+        update(&mut copy);
+        *key = Arc::new(copy);
+        Ok(())
+    }
+
+    fn unknown_secret_key(handle: jlong) -> ZkdexError {
+        ZkdexError::InvalidArgument(format!("no secret key {}", handle))
+    }
+
+    static VERIFIERS: Lazy<Mutex<HashMap<jlong, Arc<Verifier>>>> = Lazy::new(Default::default);
+
+    fn verifier(handle: jlong) -> Result<Arc<Verifier>, ZkdexError> {
+        VERIFIERS
+            .lock()
+            .unwrap()
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| ZkdexError::InvalidArgument(format!("no verifier {}", handle)))
+    }
+
+    /// Throw a `com.okx.ZKDEXException` carrying the error code, so callers can classify failures.
+    fn throw_zkdex_error(env: &mut JNIEnv, err: &ZkdexError) {
+        env.exception_clear().expect("clear");
+        let code = env.new_string(err.code()).expect("Couldn't create java string!");
+        let message = env.new_string(err.to_string()).expect("Couldn't create java string!");
+        let exception = env
+            .new_object(
+                "com/okx/ZKDEXException",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[JValue::Object(&code), JValue::Object(&message)],
+            )
+            .expect("Couldn't create ZKDEXException");
+        env.throw(JThrowable::from(exception)).expect("throw");
+    }
+
     #[derive(Serialize)]
     struct ComposeSignature {
         signature_a: JubjubSignature,
@@ -1,22 +1,44 @@
 #[cfg(feature = "js")]
 pub mod javascript_bridge {
+    use crate::keystore::Keystore;
     use crate::unified::{
-        unified_hash_liquidate, unified_hash_oracle_price, unified_hash_perpetual_trade,
-        unified_hash_spot_trade, unified_hash_transfer, unified_hash_withdrawal,
-        unified_sign_liquidate, unified_sign_oracle_price, unified_sign_perpetual_trade,
-        unified_sign_spot_trade, unified_sign_transfer, unified_sign_withdrawal,
+        batch_hash_any, batch_sign_any, batch_sign_any_with, batch_verify, hash_any, sign_any,
+        sign_any_unchecked, unified_hash_liquidate, unified_hash_oracle_price,
+        unified_hash_perpetual_trade, unified_hash_spot_trade, unified_hash_transfer,
+        unified_hash_withdrawal, unified_sign_liquidate, unified_sign_liquidate_envelope,
+        unified_sign_liquidate_unchecked, unified_sign_oracle_price,
+        unified_sign_oracle_price_envelope, unified_sign_oracle_price_unchecked,
+        unified_sign_perpetual_limit_order_envelope, unified_sign_perpetual_trade,
+        unified_sign_perpetual_trade_envelope, unified_sign_perpetual_trade_unchecked,
+        unified_sign_spot_limit_order_envelope, unified_sign_spot_trade,
+        unified_sign_spot_trade_envelope, unified_sign_spot_trade_unchecked, unified_sign_transfer,
+        unified_sign_transfer_envelope, unified_sign_transfer_unchecked, unified_sign_withdrawal,
+        unified_sign_withdrawal_envelope, unified_sign_withdrawal_unchecked,
+        unified_verify_liquidate, unified_verify_oracle_price,
+        unified_verify_perpetual_limit_order, unified_verify_perpetual_trade,
+        unified_verify_spot_limit_order, unified_verify_spot_trade, unified_verify_transfer,
+        unified_verify_withdrawal, verify_any, AnyHash, BatchItem,
     };
     use crate::utils::set_panic_hook;
     use crate::zkw::JubjubSignature;
     use crate::{
-        hash_limit_order, hash_liquidate, hash_signed_oracle_price, hash_spot_limit_order,
-        hash_spot_transfer, hash_spot_withdrawal, hash_transfer, hash_withdraw, is_on_curve,
-        l2_sign, private_key_from_seed, private_key_to_pubkey_xy, pub_key_to_xy, sign,
-        sign_eth_address, sign_limit_order, sign_liquidate, sign_signed_oracle_price,
-        sign_spot_limit_order, sign_spot_transfer, sign_spot_withdrawal, sign_transfer,
-        sign_withdraw, unified_hash_perpetual_limit_order, unified_hash_spot_limit_order,
-        unified_sign_perpetual_limit_order, unified_sign_spot_limit_order, verify_signature,
-        JUBJUB_PARAMS, RESCUE_PARAMS,
+        decrypt_keystore, derive_extended_public_key, derive_private_key, derive_public_key,
+        encrypt_keystore, generate_private_key, hash_conditional_transfer, hash_limit_order,
+        hash_liquidate, hash_signed_oracle_price, hash_spot_limit_order, hash_spot_transfer,
+        hash_spot_withdrawal, hash_transfer, hash_withdraw, is_on_curve, l2_key_from_eth_signature,
+        l2_sign, onboarding_message, poseidon_hash_field_elements_json,
+        poseidon_hash_u64_limbs_json, private_key_from_seed, private_key_to_pubkey_xy,
+        pub_key_to_xy, sign, sign_conditional_transfer, sign_conditional_transfer_unchecked,
+        sign_eth_address, sign_limit_order, sign_limit_order_unchecked, sign_liquidate,
+        sign_liquidate_unchecked, sign_signed_oracle_price, sign_signed_oracle_price_unchecked,
+        sign_spot_limit_order, sign_spot_limit_order_unchecked, sign_spot_transfer,
+        sign_spot_transfer_unchecked, sign_spot_withdrawal, sign_spot_withdrawal_unchecked,
+        sign_transfer, sign_transfer_unchecked, sign_withdraw, sign_withdraw_unchecked,
+        unified_hash_perpetual_limit_order, unified_hash_spot_limit_order,
+        unified_sign_perpetual_limit_order, unified_sign_perpetual_limit_order_unchecked,
+        unified_sign_spot_limit_order, unified_sign_spot_limit_order_unchecked,
+        verify_conditional_transfer, verify_signature, verify_transfer, NonceMode, SecretKey,
+        Verifier, ZkdexError, GENERATOR_TABLE, JUBJUB_PARAMS, RESCUE_PARAMS,
     };
     use serde::Serialize;
     use wasm_bindgen::prelude::wasm_bindgen;
@@ -26,8 +48,9 @@ pub mod javascript_bridge {
     /// This method initializes params for current thread, otherwise they will be initialized when signing
     /// first message.
     pub fn zkdex_init() {
-        JUBJUB_PARAMS.with(|_| {});
-        RESCUE_PARAMS.with(|_| {});
+        lazy_static::initialize(&JUBJUB_PARAMS);
+        lazy_static::initialize(&RESCUE_PARAMS);
+        lazy_static::initialize(&GENERATOR_TABLE);
         set_panic_hook();
     }
 
@@ -39,7 +62,19 @@ pub mod javascript_bridge {
     pub fn js_sign_transfer(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_transfer(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_transfer_unchecked, same as sign_transfer without the range checks.
+    /// @param {string} json  json of transfer transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of transfer transaction.
+    #[wasm_bindgen(js_name = sign_transfer_unchecked, skip_jsdoc)]
+    pub fn js_sign_transfer_unchecked(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_transfer_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -50,7 +85,107 @@ pub mod javascript_bridge {
     pub fn js_hash_transfer(json: &str) -> Result<String, JsValue> {
         match hash_transfer(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// verify_transfer, verify a signature over a transfer, with or without fee.
+    /// @param {string} json  json of transfer transaction.
+    /// @param {string} sig_r  r of signature.
+    /// @param {string} sig_s  s of signature.
+    /// @param {string} pub_key  public key of the sender.
+    /// @returns {bool} whether the sender signed the transfer.
+    #[wasm_bindgen(js_name = verify_transfer, skip_jsdoc)]
+    pub fn js_verify_transfer(
+        json: &str,
+        sig_r: &str,
+        sig_s: &str,
+        pub_key: &str,
+    ) -> Result<bool, JsValue> {
+        let signature = JubjubSignature::try_from_str(sig_r, sig_s).map_err(js_error)?;
+        match verify_transfer(json, signature, pub_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_conditional_transfer, sign a transfer that waits for a fact in the L1 fact registry.
+    /// @param {string} json  json of conditional transfer, a transfer with fact_registry_address and fact.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of conditional transfer.
+    #[wasm_bindgen(js_name = sign_conditional_transfer, skip_jsdoc)]
+    pub fn js_sign_conditional_transfer(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_conditional_transfer(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_conditional_transfer_unchecked, same as sign_conditional_transfer without the range checks.
+    /// @param {string} json  json of conditional transfer.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of conditional transfer.
+    #[wasm_bindgen(js_name = sign_conditional_transfer_unchecked, skip_jsdoc)]
+    pub fn js_sign_conditional_transfer_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match sign_conditional_transfer_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// hash_conditional_transfer, hash a conditional transfer.
+    /// @param {string} json  json of conditional transfer.
+    /// @returns {string} string hash of conditional transfer with 0x prefix.
+    #[wasm_bindgen(js_name = hash_conditional_transfer, skip_jsdoc)]
+    pub fn js_hash_conditional_transfer(json: &str) -> Result<String, JsValue> {
+        match hash_conditional_transfer(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// verify_conditional_transfer, verify a signature over a conditional transfer.
+    /// @param {string} json  json of conditional transfer.
+    /// @param {string} sig_r  r of signature.
+    /// @param {string} sig_s  s of signature.
+    /// @param {string} pub_key  public key of the sender.
+    /// @returns {bool} whether the sender signed the conditional transfer.
+    #[wasm_bindgen(js_name = verify_conditional_transfer, skip_jsdoc)]
+    pub fn js_verify_conditional_transfer(
+        json: &str,
+        sig_r: &str,
+        sig_s: &str,
+        pub_key: &str,
+    ) -> Result<bool, JsValue> {
+        let signature = JubjubSignature::try_from_str(sig_r, sig_s).map_err(js_error)?;
+        match verify_conditional_transfer(json, signature, pub_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// poseidon_hash_field_elements, the poseidon hash every transaction hash is built on.
+    /// @param {string} json  json array of field elements hex with 0x prefix, each below the field modulus.
+    /// @returns {string} string hash with 0x prefix.
+    #[wasm_bindgen(js_name = poseidon_hash_field_elements, skip_jsdoc)]
+    pub fn js_poseidon_hash_field_elements(json: &str) -> Result<String, JsValue> {
+        match poseidon_hash_field_elements_json(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// poseidon_hash_u64_limbs, the poseidon hash of u64 limbs, 4 limbs make a field element.
+    /// @param {string} json  json array of u64 limbs as decimal strings.
+    /// @returns {string} string hash with 0x prefix.
+    #[wasm_bindgen(js_name = poseidon_hash_u64_limbs, skip_jsdoc)]
+    pub fn js_poseidon_hash_u64_limbs(json: &str) -> Result<String, JsValue> {
+        match poseidon_hash_u64_limbs_json(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -63,7 +198,20 @@ pub mod javascript_bridge {
         let withdraw = sign_withdraw(json, private_key);
         match withdraw {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_withdraw_unchecked, same as sign_withdraw without the range checks.
+    /// @param {string} json  json of withdraw transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of withdraw transaction.
+    #[wasm_bindgen(js_name = sign_withdraw_unchecked, skip_jsdoc)]
+    pub fn js_sign_withdraw_unchecked(json: &str, private_key: &str) -> Result<String, JsValue> {
+        let withdraw = sign_withdraw_unchecked(json, private_key);
+        match withdraw {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -74,7 +222,7 @@ pub mod javascript_bridge {
     pub fn js_hash_withdraw(json: &str) -> Result<String, JsValue> {
         match hash_withdraw(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -86,7 +234,19 @@ pub mod javascript_bridge {
     pub fn js_sign_limit_order(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_limit_order(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_limit_order_unchecked, same as sign_limit_order without the range checks.
+    /// @param {string} json  json of limit order transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of limit order transaction.
+    #[wasm_bindgen(js_name = sign_limit_order_unchecked, skip_jsdoc)]
+    pub fn js_sign_limit_order_unchecked(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_limit_order_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -97,7 +257,7 @@ pub mod javascript_bridge {
     pub fn js_hash_limit_order(json: &str) -> Result<String, JsValue> {
         match hash_limit_order(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -109,7 +269,19 @@ pub mod javascript_bridge {
     pub fn js_sign_liquidate(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_liquidate(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_liquidate_unchecked, same as sign_liquidate without the range checks.
+    /// @param {string} json  json of liquidate transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of liquidate transaction.
+    #[wasm_bindgen(js_name = sign_liquidate_unchecked, skip_jsdoc)]
+    pub fn js_sign_liquidate_unchecked(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_liquidate_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -120,7 +292,7 @@ pub mod javascript_bridge {
     pub fn js_hash_liquidate(json: &str) -> Result<String, JsValue> {
         match hash_liquidate(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -132,7 +304,22 @@ pub mod javascript_bridge {
     pub fn js_sign_signed_oracle_price(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_signed_oracle_price(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_signed_oracle_price_unchecked, same as sign_signed_oracle_price without the range checks.
+    /// @param {string} json  json of liquidate transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of liquidate transaction.
+    #[wasm_bindgen(js_name = sign_signed_oracle_price_unchecked, skip_jsdoc)]
+    pub fn js_sign_signed_oracle_price_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match sign_signed_oracle_price_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -143,7 +330,7 @@ pub mod javascript_bridge {
     pub fn js_hash_signed_oracle_price(json: &str) -> Result<String, JsValue> {
         match hash_signed_oracle_price(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -164,7 +351,7 @@ pub mod javascript_bridge {
     ) -> Result<bool, JsValue> {
         match verify_signature(sig_r, sig_s, pub_key_x, pub_key_y, msg) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -180,7 +367,7 @@ pub mod javascript_bridge {
                 Ok(r)
             }
 
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -201,7 +388,7 @@ pub mod javascript_bridge {
         match sign_eth_address(chain_id,contract_address,address, pubkey, l2_private_key) {
             Ok(ret) => Ok(ret),
 
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -213,7 +400,7 @@ pub mod javascript_bridge {
     pub fn js_is_on_curve(pub_key_x: &str, pub_key_y: &str) -> Result<bool, JsValue> {
         match is_on_curve(pub_key_x, pub_key_y) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -225,7 +412,7 @@ pub mod javascript_bridge {
     pub fn js_sign(private_key: &str, msg: &str) -> Result<String, JsValue> {
         match sign(private_key, msg) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -236,7 +423,105 @@ pub mod javascript_bridge {
     pub fn js_private_key_from_seed(seed: &str) -> Result<String, JsValue> {
         match private_key_from_seed(seed.as_bytes()) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// generate_private_key, generate a new private key from the secure random source of the platform.
+    /// @returns {string} json string of the private key and the packed public key.
+    #[wasm_bindgen(js_name = generate_private_key, skip_jsdoc)]
+    pub fn js_generate_private_key() -> Result<String, JsValue> {
+        match generate_private_key() {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// derive_private_key, derive the private key of a sub-account from the master seed.
+    /// @param {string} seed  the master seed, anything string.
+    /// @param {string} path  derivation path like m/7'/3', ' or h marks a hardened index, a key
+    /// handed out should sit at a hardened index, a normal one reveals its parent.
+    /// @returns {string} string of private coding in hex with 0x prefix.
+    #[wasm_bindgen(js_name = derive_private_key, skip_jsdoc)]
+    pub fn js_derive_private_key(seed: &str, path: &str) -> Result<String, JsValue> {
+        match derive_private_key(seed.as_bytes(), path) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// derive_extended_public_key, derive the public key and chain code of a sub-account from the master seed.
+    /// @param {string} seed  the master seed, anything string.
+    /// @param {string} path  derivation path like m/7'/3, ' or h marks a hardened index.
+    /// @returns {string} json string of the packed public key and the chain code.
+    #[wasm_bindgen(js_name = derive_extended_public_key, skip_jsdoc)]
+    pub fn js_derive_extended_public_key(seed: &str, path: &str) -> Result<String, JsValue> {
+        match derive_extended_public_key(seed.as_bytes(), path) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// derive_public_key, derive a child public key below an extended public key, without the seed.
+    /// @param {string} public_key  packed public key with 0x prefix.
+    /// @param {string} chain_code  32 bytes chain code in hex with 0x prefix.
+    /// @param {string} path  derivation path like m/3/0, hardened indexes are not allowed.
+    /// @returns {string} packed public key with 0x prefix.
+    #[wasm_bindgen(js_name = derive_public_key, skip_jsdoc)]
+    pub fn js_derive_public_key(
+        public_key: &str,
+        chain_code: &str,
+        path: &str,
+    ) -> Result<String, JsValue> {
+        match derive_public_key(public_key, chain_code, path) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// encrypt_keystore, encrypt a private key into a password protected keystore.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @param {string} password  the password protecting the key.
+    /// @returns {string} json string of the keystore.
+    #[wasm_bindgen(js_name = encrypt_keystore, skip_jsdoc)]
+    pub fn js_encrypt_keystore(private_key: &str, password: &str) -> Result<String, JsValue> {
+        match encrypt_keystore(private_key, password) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// decrypt_keystore, recover the private key of a keystore.
+    /// @param {string} keystore  json string of the keystore.
+    /// @param {string} password  the password protecting the key.
+    /// @returns {string} string of private coding in hex with 0x prefix.
+    #[wasm_bindgen(js_name = decrypt_keystore, skip_jsdoc)]
+    pub fn js_decrypt_keystore(keystore: &str, password: &str) -> Result<String, JsValue> {
+        match decrypt_keystore(keystore, password) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// onboarding_message, the message the wallet signs with personal_sign to onboard.
+    /// @returns {string} the onboarding message text.
+    #[wasm_bindgen(js_name = onboarding_message, skip_jsdoc)]
+    pub fn js_onboarding_message() -> String {
+        onboarding_message()
+    }
+
+    /// l2_key_from_eth_signature, derive the l2 key from the wallet signature of the onboarding message.
+    /// @param {string} eth_signature 65 bytes signature in hex with 0x prefix.
+    /// @param {string} address wallet address with 0x prefix.
+    /// @returns {string} json string of the private key and the packed public key.
+    #[wasm_bindgen(js_name = l2_key_from_eth_signature, skip_jsdoc)]
+    pub fn js_l2_key_from_eth_signature(
+        eth_signature: &str,
+        address: &str,
+    ) -> Result<String, JsValue> {
+        match l2_key_from_eth_signature(eth_signature, address) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -254,7 +539,7 @@ pub mod javascript_bridge {
                 }
                 Ok(serde_json::to_string(&XY { x: ret.0, y: ret.1 }).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -272,7 +557,7 @@ pub mod javascript_bridge {
                 }
                 Ok(serde_json::to_string(&XY { x: ret.0, y: ret.1 }).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -284,7 +569,22 @@ pub mod javascript_bridge {
     pub fn js_sign_spot_transfer(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_spot_transfer(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_spot_transfer_unchecked, same as sign_spot_transfer without the range checks.
+    /// @param {string} json  json of spot transfer transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of spot transfer transaction.
+    #[wasm_bindgen(js_name = sign_spot_transfer_unchecked, skip_jsdoc)]
+    pub fn js_sign_spot_transfer_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match sign_spot_transfer_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -295,7 +595,7 @@ pub mod javascript_bridge {
     pub fn js_hash_spot_transfer(json: &str) -> Result<String, JsValue> {
         match hash_spot_transfer(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -307,7 +607,22 @@ pub mod javascript_bridge {
     pub fn js_sign_spot_withdrawal(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_spot_withdrawal(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_spot_withdrawal_unchecked, same as sign_spot_withdrawal without the range checks.
+    /// @param {string} json  json of spot withdrawal transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of spot withdrawal transaction.
+    #[wasm_bindgen(js_name = sign_spot_withdrawal_unchecked, skip_jsdoc)]
+    pub fn js_sign_spot_withdrawal_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match sign_spot_withdrawal_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -318,7 +633,7 @@ pub mod javascript_bridge {
     pub fn js_hash_spot_withdrawal(json: &str) -> Result<String, JsValue> {
         match hash_spot_withdrawal(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -330,7 +645,22 @@ pub mod javascript_bridge {
     pub fn js_sign_spot_limit_order(json: &str, private_key: &str) -> Result<String, JsValue> {
         match sign_spot_limit_order(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_spot_limit_order_unchecked, same as sign_spot_limit_order without the range checks.
+    /// @param {string} json  json of spot LimitOrder transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of spot LimitOrder transaction.
+    #[wasm_bindgen(js_name = sign_spot_limit_order_unchecked, skip_jsdoc)]
+    pub fn js_sign_spot_limit_order_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match sign_spot_limit_order_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -341,7 +671,7 @@ pub mod javascript_bridge {
     pub fn js_hash_spot_limit_order(json: &str) -> Result<String, JsValue> {
         match hash_spot_limit_order(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -353,7 +683,22 @@ pub mod javascript_bridge {
     pub fn js_unified_sign_transfer(json: &str, private_key: &str) -> Result<String, JsValue> {
         match unified_sign_transfer(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_transfer_unchecked, same as unified_sign_transfer without the range checks.
+    /// @param {string} json of transfer transaction.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json signature of transfer transaction.
+    #[wasm_bindgen(js_name = unified_sign_transfer_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_transfer_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_transfer_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -364,7 +709,7 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_transfer(json: &str) -> Result<String, JsValue> {
         match unified_hash_transfer(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -376,7 +721,22 @@ pub mod javascript_bridge {
     pub fn js_unified_sign_withdrawal(json: &str, private_key: &str) -> Result<String, JsValue> {
         match unified_sign_withdrawal(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_withdrawal_unchecked, same as unified_sign_withdrawal without the range checks.
+    /// @param {string} json  of withdrawal transaction.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json signature of withdrawal transaction.
+    #[wasm_bindgen(js_name = unified_sign_withdrawal_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_withdrawal_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_withdrawal_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -387,7 +747,7 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_withdrawal(json: &str) -> Result<String, JsValue> {
         match unified_hash_withdrawal(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -411,7 +771,31 @@ pub mod javascript_bridge {
 
                 Ok(serde_json::to_string(&ret).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_spot_trade_unchecked, same as unified_sign_spot_trade without the range checks.
+    /// @param {string} json of spot trade transaction.
+    /// @param {string} private key A hex with 0x prefix.
+    /// @param {string} private key B hex with 0x prefix.
+    /// @returns {string} json signature of spot trade transaction.
+    #[wasm_bindgen(js_name = unified_sign_spot_trade_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_spot_trade_unchecked(
+        json: &str,
+        private_key_a: &str,
+        private_key_b: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_spot_trade_unchecked(json, private_key_a, private_key_b) {
+            Ok(ret) => {
+                let ret = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+
+                Ok(serde_json::to_string(&ret).unwrap())
+            }
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -429,7 +813,7 @@ pub mod javascript_bridge {
 
                 Ok(serde_json::to_string(&ret).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -453,7 +837,31 @@ pub mod javascript_bridge {
 
                 Ok(serde_json::to_string(&ret).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_perpetual_trade_unchecked, same as unified_sign_perpetual_trade without the range checks.
+    /// @param {string} json of spot trade transaction.
+    /// @param {string} private key A hex with 0x prefix.
+    /// @param {string} private key B hex with 0x prefix.
+    /// @returns {string} json signature of perpetual trade transaction.
+    #[wasm_bindgen(js_name = unified_sign_perpetual_trade_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_perpetual_trade_unchecked(
+        json: &str,
+        private_key_a: &str,
+        private_key_b: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_perpetual_trade_unchecked(json, private_key_a, private_key_b) {
+            Ok(ret) => {
+                let ret = ComposeSignature {
+                    signature_a: ret.0,
+                    signature_b: ret.1,
+                };
+
+                Ok(serde_json::to_string(&ret).unwrap())
+            }
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -471,7 +879,7 @@ pub mod javascript_bridge {
 
                 Ok(serde_json::to_string(&ret).unwrap())
             }
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -483,7 +891,22 @@ pub mod javascript_bridge {
     pub fn js_unified_sign_oracle_price(json: &str, private_key: &str) -> Result<String, JsValue> {
         match unified_sign_oracle_price(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_oracle_price_unchecked, same as unified_sign_oracle_price without the range checks.
+    /// @param {string} json  of oracle price transaction.
+    /// @param {string}  private key hex with 0x prefix.
+    /// @returns {string} json signature of oracle price transaction.
+    #[wasm_bindgen(js_name = unified_sign_oracle_price_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_oracle_price_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_oracle_price_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -494,7 +917,7 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_oracle_price(json: &str) -> Result<String, JsValue> {
         match unified_hash_oracle_price(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -506,7 +929,22 @@ pub mod javascript_bridge {
     pub fn js_unified_sign_liquidate(json: &str, private_key: &str) -> Result<String, JsValue> {
         match unified_sign_liquidate(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_liquidate_unchecked, same as unified_sign_liquidate without the range checks.
+    /// @param {string} json of liquidate transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of liquidate transaction.
+    #[wasm_bindgen(js_name = unified_sign_liquidate_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_liquidate_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_liquidate_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -517,7 +955,7 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_liquidate(json: &str) -> Result<String, JsValue> {
         match unified_hash_liquidate(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -532,7 +970,22 @@ pub mod javascript_bridge {
     ) -> Result<String, JsValue> {
         match unified_sign_spot_limit_order(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_spot_limit_order_unchecked, same as unified_sign_spot_limit_order without the range checks.
+    /// @param {string} json of spot limit order transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of spot limit order transaction.
+    #[wasm_bindgen(js_name = unified_sign_spot_limit_order_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_spot_limit_order_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_spot_limit_order_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -543,7 +996,7 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_spot_limit_order(json: &str) -> Result<String, JsValue> {
         match unified_hash_spot_limit_order(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -558,7 +1011,22 @@ pub mod javascript_bridge {
     ) -> Result<String, JsValue> {
         match unified_sign_perpetual_limit_order(json, private_key) {
             Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_perpetual_limit_order_unchecked, same as unified_sign_perpetual_limit_order without the range checks.
+    /// @param {string} json of perpetual limit order transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature of perpetual limit order transaction.
+    #[wasm_bindgen(js_name = unified_sign_perpetual_limit_order_unchecked, skip_jsdoc)]
+    pub fn js_unified_sign_perpetual_limit_order_unchecked(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_perpetual_limit_order_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
         }
     }
 
@@ -569,10 +1037,463 @@ pub mod javascript_bridge {
     pub fn js_unified_hash_perpetual_limit_order(json: &str) -> Result<String, JsValue> {
         match unified_hash_perpetual_limit_order(json) {
             Ok(ret) => Ok(ret),
-            Err(e) => Err(JsValue::from_str(e.to_string().as_str())),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_any, sign any unified transaction by its "type" field, owned by the private key.
+    /// @param {string} json of unified transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature, a trade only carries the orders owned by the private key.
+    #[wasm_bindgen(js_name = sign_any, skip_jsdoc)]
+    pub fn js_sign_any(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_any(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// sign_any_unchecked, same as sign_any without the range checks.
+    /// @param {string} json of unified transaction.
+    /// @param {string} private_key private key hex with 0x prefix.
+    /// @returns {string} json signature, a trade only carries the orders owned by the private key.
+    #[wasm_bindgen(js_name = sign_any_unchecked, skip_jsdoc)]
+    pub fn js_sign_any_unchecked(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match sign_any_unchecked(json, private_key) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// hash_any, hash any unified transaction by its "type" field.
+    /// @param {string} json of unified transaction.
+    /// @returns {string} hash, or json of both hashes for a trade.
+    #[wasm_bindgen(js_name = hash_any, skip_jsdoc)]
+    pub fn js_hash_any(json: &str) -> Result<String, JsValue> {
+        match hash_any(json) {
+            Ok(AnyHash::Single(hash)) => Ok(hash),
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// verify_any, verify a signature over any unified transaction by its "type" field.
+    /// @param {string} json of unified transaction.
+    /// @param {string} sig_r  r of signature.
+    /// @param {string} sig_s  s of signature.
+    /// @param {string} pub_key  public key of the signer.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = verify_any, skip_jsdoc)]
+    pub fn js_verify_any(
+        json: &str,
+        sig_r: &str,
+        sig_s: &str,
+        pub_key: &str,
+    ) -> Result<bool, JsValue> {
+        let signature = JubjubSignature::try_from_str(sig_r, sig_s).map_err(js_error)?;
+        match verify_any(json, signature, pub_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_withdrawal, verify the signature embedded in a withdrawal transaction against its own public key.
+    /// @param {string} json of withdrawal transaction with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_withdrawal, skip_jsdoc)]
+    pub fn js_unified_verify_withdrawal(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_withdrawal(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_transfer, verify the signature embedded in a transfer transaction against its own public key.
+    /// @param {string} json of transfer transaction with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_transfer, skip_jsdoc)]
+    pub fn js_unified_verify_transfer(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_transfer(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_oracle_price, verify the signature embedded in a oracle price against its own public key.
+    /// @param {string} json of oracle price with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_oracle_price, skip_jsdoc)]
+    pub fn js_unified_verify_oracle_price(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_oracle_price(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_liquidate, verify the signature embedded in a liquidate transaction against its own public key.
+    /// @param {string} json of liquidate transaction with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_liquidate, skip_jsdoc)]
+    pub fn js_unified_verify_liquidate(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_liquidate(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_spot_limit_order, verify the signature embedded in a spot limit order against its own public key.
+    /// @param {string} json of spot limit order with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_spot_limit_order, skip_jsdoc)]
+    pub fn js_unified_verify_spot_limit_order(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_spot_limit_order(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_perpetual_limit_order, verify the signature embedded in a perpetual limit order against its own public key.
+    /// @param {string} json of perpetual limit order with its signature.
+    /// @returns {bool} whether the signature is valid.
+    #[wasm_bindgen(js_name = unified_verify_perpetual_limit_order, skip_jsdoc)]
+    pub fn js_unified_verify_perpetual_limit_order(json: &str) -> Result<bool, JsValue> {
+        match unified_verify_perpetual_limit_order(json) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_spot_trade, verify the signatures embedded in both orders of a spot trade.
+    /// @param {string} json of spot trade with the signature of each order.
+    /// @returns {string} json of the result of each order, {"party_a":bool,"party_b":bool}.
+    #[wasm_bindgen(js_name = unified_verify_spot_trade, skip_jsdoc)]
+    pub fn js_unified_verify_spot_trade(json: &str) -> Result<String, JsValue> {
+        match unified_verify_spot_trade(json) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_verify_perpetual_trade, verify the signatures embedded in both orders of a perpetual trade.
+    /// @param {string} json of perpetual trade with the signature of each order.
+    /// @returns {string} json of the result of each order, {"party_a":bool,"party_b":bool}.
+    #[wasm_bindgen(js_name = unified_verify_perpetual_trade, skip_jsdoc)]
+    pub fn js_unified_verify_perpetual_trade(json: &str) -> Result<String, JsValue> {
+        match unified_verify_perpetual_trade(json) {
+            Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_withdrawal_envelope, sign a withdrawal transaction and attach the signature and hash to it.
+    /// @param {string} json of withdrawal transaction.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed withdrawal transaction.
+    #[wasm_bindgen(js_name = unified_sign_withdrawal_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_withdrawal_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_withdrawal_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_transfer_envelope, sign a transfer transaction and attach the signature and hash to it.
+    /// @param {string} json of transfer transaction.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed transfer transaction.
+    #[wasm_bindgen(js_name = unified_sign_transfer_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_transfer_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_transfer_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_oracle_price_envelope, sign a oracle price and attach the signature and hash to it.
+    /// @param {string} json of oracle price.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed oracle price.
+    #[wasm_bindgen(js_name = unified_sign_oracle_price_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_oracle_price_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_oracle_price_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_liquidate_envelope, sign a liquidate transaction and attach the signature and hash to it.
+    /// @param {string} json of liquidate transaction.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed liquidate transaction.
+    #[wasm_bindgen(js_name = unified_sign_liquidate_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_liquidate_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_liquidate_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_spot_limit_order_envelope, sign a spot limit order and attach the signature and hash to it.
+    /// @param {string} json of spot limit order.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed spot limit order.
+    #[wasm_bindgen(js_name = unified_sign_spot_limit_order_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_spot_limit_order_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_spot_limit_order_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
         }
     }
 
+    /// unified_sign_perpetual_limit_order_envelope, sign a perpetual limit order and attach the signature and hash to it.
+    /// @param {string} json of perpetual limit order.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json of the signed perpetual limit order.
+    #[wasm_bindgen(js_name = unified_sign_perpetual_limit_order_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_perpetual_limit_order_envelope(
+        json: &str,
+        private_key: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_perpetual_limit_order_envelope(json, private_key) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_spot_trade_envelope, sign both orders of a spot trade and attach the signature and hash to each of them.
+    /// @param {string} json of spot trade transaction.
+    /// @param {string} private key A hex with 0x prefix.
+    /// @param {string} private key B hex with 0x prefix.
+    /// @returns {string} json of the signed spot trade transaction.
+    #[wasm_bindgen(js_name = unified_sign_spot_trade_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_spot_trade_envelope(
+        json: &str,
+        private_key_a: &str,
+        private_key_b: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_spot_trade_envelope(json, private_key_a, private_key_b) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// unified_sign_perpetual_trade_envelope, sign both orders of a perpetual trade and attach the signature and hash to each of them.
+    /// @param {string} json of perpetual trade transaction.
+    /// @param {string} private key A hex with 0x prefix.
+    /// @param {string} private key B hex with 0x prefix.
+    /// @returns {string} json of the signed perpetual trade transaction.
+    #[wasm_bindgen(js_name = unified_sign_perpetual_trade_envelope, skip_jsdoc)]
+    pub fn js_unified_sign_perpetual_trade_envelope(
+        json: &str,
+        private_key_a: &str,
+        private_key_b: &str,
+    ) -> Result<String, JsValue> {
+        match unified_sign_perpetual_trade_envelope(json, private_key_a, private_key_b) {
+            Ok(ret) => Ok(ret),
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// batch_sign_any, sign a json array of unified transactions, each one like sign_any.
+    /// @param {string} json array of unified transactions.
+    /// @param {string} private key hex with 0x prefix.
+    /// @returns {string} json array with the signature of each transaction in input order, or
+    /// {"error":{"code","message"}} for a transaction that failed.
+    #[wasm_bindgen(js_name = batch_sign_any, skip_jsdoc)]
+    pub fn js_batch_sign_any(json: &str, private_key: &str) -> Result<String, JsValue> {
+        match batch_sign_any(json, private_key) {
+            Ok(ret) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                Ok(serde_json::to_string(&items).unwrap())
+            }
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// batch_hash_any, hash a json array of unified transactions, each one like hash_any.
+    /// @param {string} json array of unified transactions.
+    /// @returns {string} json array with the hash of each transaction in input order, or
+    /// {"error":{"code","message"}} for a transaction that failed.
+    #[wasm_bindgen(js_name = batch_hash_any, skip_jsdoc)]
+    pub fn js_batch_hash_any(json: &str) -> Result<String, JsValue> {
+        match batch_hash_any(json) {
+            Ok(ret) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                Ok(serde_json::to_string(&items).unwrap())
+            }
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// batch_verify, verify a json array of signed hashes in one batch.
+    /// @param {string} json array of {"signature":{"r","s"},"public_key","hash"}.
+    /// @returns {string} json array with whether each signature is valid in input order, or
+    /// {"error":{"code","message"}} for a malformed item.
+    #[wasm_bindgen(js_name = batch_verify, skip_jsdoc)]
+    pub fn js_batch_verify(json: &str) -> Result<String, JsValue> {
+        match batch_verify(json) {
+            Ok(ret) => {
+                let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                Ok(serde_json::to_string(&items).unwrap())
+            }
+            Err(e) => Err(js_error(e)),
+        }
+    }
+
+    /// Errors reach js as `"CODE: message"`, the code is one of `ZkdexError::code`.
+    /// SecretKey, a private key parsed once and reused for many signatures, free() wipes it.
+    #[wasm_bindgen(js_name = SecretKey)]
+    pub struct JsSecretKey(SecretKey);
+
+    #[wasm_bindgen(js_class = SecretKey)]
+    impl JsSecretKey {
+        /// new SecretKey, parse a private key.
+        /// @param {string} private_key private key hex with 0x prefix.
+        #[wasm_bindgen(constructor, skip_jsdoc)]
+        pub fn new(private_key: &str) -> Result<JsSecretKey, JsValue> {
+            match SecretKey::from_hex(private_key) {
+                Ok(ret) => Ok(JsSecretKey(ret)),
+                Err(e) => Err(js_error(e)),
+            }
+        }
+
+        /// from_keystore, decrypt the key of a keystore.
+        /// @param {string} keystore  json string of the keystore.
+        /// @param {string} password  the password protecting the key.
+        /// @returns {SecretKey} the secret key.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn from_keystore(keystore: &str, password: &str) -> Result<JsSecretKey, JsValue> {
+            match Keystore::from_json(keystore).and_then(|keystore| keystore.decrypt(password)) {
+                Ok(ret) => Ok(JsSecretKey(ret)),
+                Err(e) => Err(js_error(e)),
+            }
+        }
+
+        /// public_key, the packed public key of this key.
+        /// @returns {string} packed public key with 0x prefix.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn public_key(&self) -> String {
+            self.0.public_key().to_string()
+        }
+
+        /// set_nonce_mode, choose how the nonces of the following signatures are drawn.
+        /// @param {string} mode "deterministic", the default, or "hedged" to mix fresh randomness in.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn set_nonce_mode(&mut self, mode: &str) -> Result<(), JsValue> {
+            let mode = mode.parse::<NonceMode>().map_err(js_error)?;
+            self.0.set_nonce_mode(mode);
+            Ok(())
+        }
+
+        /// set_verify_signatures, check every signature against the public key before returning it.
+        /// @param {boolean} verify true to check, off by default as it about doubles the cost of signing.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn set_verify_signatures(&mut self, verify: bool) {
+            self.0.set_verify_signatures(verify);
+        }
+
+        /// sign, sign a msg like the sign function.
+        /// @param {string} msg  msg coding in hex with 0x prefix.
+        /// @returns {string} json string of the signature.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn sign(&self, msg: &str) -> Result<String, JsValue> {
+            match self.0.sign(msg) {
+                Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+                Err(e) => Err(js_error(e)),
+            }
+        }
+
+        /// sign_any, sign a unified transaction like the sign_any function.
+        /// @param {string} json of unified transaction.
+        /// @returns {string} json signature, a trade only carries the orders owned by this key.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn sign_any(&self, json: &str) -> Result<String, JsValue> {
+            match self.0.sign_any(json) {
+                Ok(ret) => Ok(serde_json::to_string(&ret).unwrap()),
+                Err(e) => Err(js_error(e)),
+            }
+        }
+
+        /// batch_sign_any, sign a json array of unified transactions like the batch_sign_any function.
+        /// @param {string} json array of unified transactions.
+        /// @returns {string} json array with the signature or the error of each transaction.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn batch_sign_any(&self, json: &str) -> Result<String, JsValue> {
+            match batch_sign_any_with(json, &self.0) {
+                Ok(ret) => {
+                    let items: Vec<BatchItem<_>> = ret.into_iter().map(Into::into).collect();
+                    Ok(serde_json::to_string(&items).unwrap())
+                }
+                Err(e) => Err(js_error(e)),
+            }
+        }
+    }
+
+    /// Verifier, verifies signatures with a cache of the public keys it has seen.
+    #[wasm_bindgen(js_name = Verifier)]
+    pub struct JsVerifier(Verifier);
+
+    #[wasm_bindgen(js_class = Verifier)]
+    impl JsVerifier {
+        /// new Verifier, the least recently used key is dropped when the cache is full.
+        /// @param {number} capacity  the number of public keys to cache, 0 caches none.
+        #[wasm_bindgen(constructor, skip_jsdoc)]
+        pub fn new(capacity: usize) -> JsVerifier {
+            JsVerifier(Verifier::new(capacity))
+        }
+
+        /// verify_signature, verify a signature like the verify_signature function.
+        /// @param {string} sig_r  r of signature.
+        /// @param {string} sig_s  s of signature.
+        /// @param {string} pub_key  packed public key.
+        /// @param {string} msg  msg hex with 0x prefix.
+        /// @returns {bool} whether the signature is valid.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn verify_signature(
+            &self,
+            sig_r: &str,
+            sig_s: &str,
+            pub_key: &str,
+            msg: &str,
+        ) -> Result<bool, JsValue> {
+            self.0
+                .verify_signature(sig_r, sig_s, pub_key, msg)
+                .map_err(js_error)
+        }
+
+        /// stats, the counters of the cache.
+        /// @returns {string} json of hits, misses, len, tables and capacity.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn stats(&self) -> String {
+            serde_json::to_string(&self.0.stats()).unwrap()
+        }
+
+        /// clear, drop the cached keys and reset the counters.
+        #[wasm_bindgen(skip_jsdoc)]
+        pub fn clear(&self) {
+            self.0.clear();
+        }
+    }
+
+    fn js_error(e: ZkdexError) -> JsValue {
+        JsValue::from_str(&format!("{}: {}", e.code(), e))
+    }
+
     #[derive(Serialize)]
     struct ComposeSignature {
         signature_a: JubjubSignature,
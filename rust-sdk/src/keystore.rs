@@ -0,0 +1,444 @@
+//! Password encrypted storage of a private key.
+//!
+//! The layout follows Ethereum's Web3 Secret Storage: scrypt stretches the password into a 256 bit
+//! key which encrypts the 32 big endian bytes of the private key with AES-256-GCM. The packed
+//! public key is stored in the clear so a keystore can be told apart without the password, it is
+//! also the associated data of the cipher so it cannot be swapped.
+//!
+//! ```json
+//! {
+//!     "version": 1,
+//!     "public_key": "0x0d4a...7c2a",
+//!     "crypto": {
+//!         "cipher": "aes-256-gcm",
+//!         "cipherparams": {"nonce": "..."},
+//!         "ciphertext": "...",
+//!         "kdf": "scrypt",
+//!         "kdfparams": {"dklen": 32, "n": 262144, "r": 8, "p": 1, "salt": "..."}
+//!     }
+//! }
+//! ```
+
+use std::convert::TryFrom;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use franklin_crypto::alt_babyjubjub::fs::Fs;
+use franklin_crypto::eddsa::PrivateKey;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::convert::trim_0x;
+use crate::error::{parse_json, Result, ZkdexError};
+use crate::tx::convert::FeConvert;
+use crate::tx::packed_public_key::PackedPublicKey;
+use crate::tx::secret_key::SecretKey;
+use crate::tx::sign::NonceMode;
+use crate::unified::AnySignature;
+use crate::zkw::JubjubSignature;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const CIPHER: &str = "aes-256-gcm";
+const KDF: &str = "scrypt";
+const KEY_SIZE: usize = 32;
+const SALT_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12;
+/// Bounds on the scrypt params of a keystore, so a crafted one cannot make decrypting take more
+/// than a gigabyte of memory or minutes. The cost `n * r * p` may be 4 times the default one.
+const MAX_SCRYPT_LOG_N: u32 = 20;
+const MAX_SCRYPT_R_P: u64 = 1 << 30;
+const MAX_SCRYPT_COST: u64 = 1 << 23;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub public_key: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: ScryptParams,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub dklen: usize,
+    pub n: u64,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+impl Default for ScryptParams {
+    /// The "standard" cost of Web3 Secret Storage, about 256MB of memory.
+    fn default() -> Self {
+        Self {
+            dklen: KEY_SIZE,
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+            salt: String::new(),
+        }
+    }
+}
+
+impl ScryptParams {
+    fn derive_key(&self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        if !self.n.is_power_of_two() || self.n < 2 || self.dklen != KEY_SIZE {
+            return Err(invalid(format!(
+                "unsupported scrypt n {} or dklen {}",
+                self.n, self.dklen
+            )));
+        }
+        let log_n = self.n.trailing_zeros();
+        let r_p = self.r as u64 * self.p as u64;
+        if log_n > MAX_SCRYPT_LOG_N
+            || r_p >= MAX_SCRYPT_R_P
+            || self.n.saturating_mul(r_p) > MAX_SCRYPT_COST
+        {
+            return Err(invalid(format!(
+                "scrypt n {}, r {} and p {} cost too much",
+                self.n, self.r, self.p
+            )));
+        }
+        let params = scrypt::Params::new(log_n as u8, self.r, self.p, self.dklen)
+            .map_err(|e| invalid(format!("scrypt params: {}", e)))?;
+        let salt = decode_hex("salt", &self.salt)?;
+        let mut key = Zeroizing::new(vec![0u8; self.dklen]);
+        scrypt::scrypt(password.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| invalid(format!("scrypt: {}", e)))?;
+        Ok(key)
+    }
+}
+
+impl Keystore {
+    /// Encrypt `secret_key` under `password`, the salt and the nonce are drawn at random.
+    pub fn encrypt(secret_key: &SecretKey, password: &str, params: ScryptParams) -> Result<Self> {
+        let mut salt = [0u8; SALT_SIZE];
+        let mut nonce = [0u8; NONCE_SIZE];
+        getrandom::getrandom(&mut salt)
+            .and_then(|_| getrandom::getrandom(&mut nonce))
+            .map_err(|e| invalid(format!("no randomness: {}", e)))?;
+        Self::encrypt_with(secret_key, password, params, &salt, &nonce)
+    }
+
+    fn encrypt_with(
+        secret_key: &SecretKey,
+        password: &str,
+        params: ScryptParams,
+        salt: &[u8],
+        nonce: &[u8],
+    ) -> Result<Self> {
+        let public_key = secret_key.public_key();
+        let kdfparams = ScryptParams {
+            salt: hex::encode(salt),
+            ..params
+        };
+        let key = kdfparams.derive_key(password)?;
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .encrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: &Zeroizing::new(secret_key.private_key().0.to_bytes()),
+                    aad: &packed_bytes(public_key),
+                },
+            )
+            .map_err(|_| invalid("encryption failed".to_string()))?;
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            public_key: public_key.to_string(),
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams {
+                    nonce: hex::encode(nonce),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf: KDF.to_string(),
+                kdfparams,
+            },
+        })
+    }
+
+    /// Recover the private key, a wrong password fails with `INVALID_KEYSTORE`.
+    pub fn decrypt(&self, password: &str) -> Result<SecretKey> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(invalid(format!("unsupported version {}", self.version)));
+        }
+        if self.crypto.cipher != CIPHER || self.crypto.kdf != KDF {
+            return Err(invalid(format!(
+                "unsupported cipher {} or kdf {}",
+                self.crypto.cipher, self.crypto.kdf
+            )));
+        }
+        let public_key = PackedPublicKey::try_from(trim_0x(&self.public_key))?;
+        let nonce = decode_hex("nonce", &self.crypto.cipherparams.nonce)?;
+        if nonce.len() != NONCE_SIZE {
+            return Err(invalid(format!("nonce should be {} bytes", NONCE_SIZE)));
+        }
+        let ciphertext = decode_hex("ciphertext", &self.crypto.ciphertext)?;
+
+        let key = self.crypto.kdfparams.derive_key(password)?;
+        let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &packed_bytes(&public_key),
+                },
+            )
+            .map_err(|_| invalid("wrong password or corrupted keystore".to_string()))?;
+        let plaintext = Zeroizing::new(plaintext);
+        let private_key =
+            Fs::from_bytes(&plaintext).map_err(|e| ZkdexError::InvalidPrivateKey(e.to_string()))?;
+        let secret_key = SecretKey::from_private_key(PrivateKey(private_key));
+        if *secret_key.public_key() != public_key {
+            return Err(invalid(format!(
+                "the private key does not match {}",
+                self.public_key
+            )));
+        }
+        Ok(secret_key)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        parse_json(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a keystore is always serializable")
+    }
+}
+
+/// encrypt_keystore, encrypt a private key into a keystore with the default scrypt cost.
+/// private_key: private key hex with 0x prefix
+/// password: the password protecting the key
+/// return: the keystore in json format
+pub fn encrypt_keystore(private_key: &str, password: &str) -> Result<String> {
+    let secret_key = SecretKey::from_hex(private_key)?;
+    Ok(Keystore::encrypt(&secret_key, password, ScryptParams::default())?.to_json())
+}
+
+/// decrypt_keystore, recover the private key of a keystore.
+/// keystore: the keystore in json format
+/// password: the password protecting the key
+/// return: private key hex with 0x prefix
+pub fn decrypt_keystore(keystore: &str, password: &str) -> Result<String> {
+    let secret_key = Keystore::from_json(keystore)?.decrypt(password)?;
+    Ok(format!("0x{}", secret_key.private_key().0.to_hex()))
+}
+
+/// Signs with the private key of a keystore, which is decrypted once when the signer is created.
+#[derive(Debug)]
+pub struct KeystoreSigner {
+    secret_key: SecretKey,
+}
+
+impl KeystoreSigner {
+    pub fn from_keystore(keystore: &str, password: &str) -> Result<Self> {
+        let secret_key = Keystore::from_json(keystore)?.decrypt(password)?;
+        Ok(Self { secret_key })
+    }
+
+    /// Load the keystore file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let keystore = std::fs::read_to_string(path)
+            .map_err(|e| invalid(format!("read {}: {}", path.display(), e)))?;
+        Self::from_keystore(&keystore, password)
+    }
+
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    /// Sign with `mode`, see [`SecretKey::with_nonce_mode`].
+    pub fn with_nonce_mode(mut self, mode: NonceMode) -> Self {
+        self.secret_key.set_nonce_mode(mode);
+        self
+    }
+
+    /// Check each signature, see [`SecretKey::with_verify_signatures`].
+    pub fn with_verify_signatures(mut self, verify: bool) -> Self {
+        self.secret_key.set_verify_signatures(verify);
+        self
+    }
+
+    /// The packed public key in hex format.
+    pub fn public_key(&self) -> String {
+        self.secret_key.public_key().to_string()
+    }
+
+    /// Same as [`crate::sign`] with the key of this signer.
+    pub fn sign(&self, msg: &str) -> Result<JubjubSignature> {
+        self.secret_key.sign(msg)
+    }
+
+    /// Same as [`crate::sign_any`] with the key of this signer.
+    pub fn sign_any(&self, json: &str) -> Result<AnySignature> {
+        self.secret_key.sign_any(json)
+    }
+}
+
+fn packed_bytes(public_key: &PackedPublicKey) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    public_key.0.to_big_endian(&mut bytes);
+    bytes
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(trim_0x(value)).map_err(|e| invalid(format!("{}: {}", field, e)))
+}
+
+fn invalid(reason: String) -> ZkdexError {
+    ZkdexError::InvalidKeystore(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unified::transactions::test::PRI_KEY;
+    use crate::{sign, sign_any};
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    // scrypt n = 1024 keeps the tests fast, real keystores use the default cost
+    const KEYSTORE: &str = r#"
+    {
+        "version": 1,
+        "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+        "crypto": {
+            "cipher": "aes-256-gcm",
+            "cipherparams": {"nonce": "6465666768696a6b6c6d6e6f"},
+            "ciphertext": "c24f0dd29173eefcf850b5d8d33a833f3e6442247314ce990b015063ccf09cef7b44b49fc0d07046c18033d5caec9e4c",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 1024,
+                "r": 8,
+                "p": 1,
+                "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+            }
+        }
+    }
+    "#;
+
+    fn light_params() -> ScryptParams {
+        ScryptParams {
+            n: 1024,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_keystore_vector() {
+        assert_eq!(decrypt_keystore(KEYSTORE, PASSWORD).unwrap(), PRI_KEY);
+
+        let secret_key = SecretKey::from_hex(PRI_KEY).unwrap();
+        let salt: Vec<u8> = (0..32).collect();
+        let nonce: Vec<u8> = (100..112).collect();
+        let keystore =
+            Keystore::encrypt_with(&secret_key, PASSWORD, light_params(), &salt, &nonce).unwrap();
+        assert_eq!(keystore, Keystore::from_json(KEYSTORE).unwrap());
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let secret_key = SecretKey::from_hex(PRI_KEY).unwrap();
+        let keystore = Keystore::encrypt(&secret_key, PASSWORD, light_params()).unwrap();
+        let other = Keystore::encrypt(&secret_key, PASSWORD, light_params()).unwrap();
+        assert_ne!(keystore.crypto.ciphertext, other.crypto.ciphertext);
+
+        let json = keystore.to_json();
+        assert_eq!(decrypt_keystore(&json, PASSWORD).unwrap(), PRI_KEY);
+        let err = decrypt_keystore(&json, "wrong password").unwrap_err();
+        assert_eq!(err.code(), "INVALID_KEYSTORE");
+    }
+
+    #[test]
+    fn test_keystore_rejects_tampering() {
+        let mut keystore = Keystore::from_json(KEYSTORE).unwrap();
+        keystore.public_key =
+            "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa".to_string();
+        let err = keystore.decrypt(PASSWORD).unwrap_err();
+        assert_eq!(err.code(), "INVALID_KEYSTORE");
+
+        let mut keystore = Keystore::from_json(KEYSTORE).unwrap();
+        keystore.version = 2;
+        assert_eq!(
+            keystore.decrypt(PASSWORD).unwrap_err().code(),
+            "INVALID_KEYSTORE"
+        );
+
+        let err = decrypt_keystore(r#"{"version":1}"#, PASSWORD).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
+    #[test]
+    fn test_keystore_rejects_costly_scrypt() {
+        let costly = |n: u64, r: u32, p: u32| {
+            let mut keystore = Keystore::from_json(KEYSTORE).unwrap();
+            keystore.crypto.kdfparams.n = n;
+            keystore.crypto.kdfparams.r = r;
+            keystore.crypto.kdfparams.p = p;
+            keystore.decrypt(PASSWORD).unwrap_err()
+        };
+        let err = costly(1 << 21, 1, 1);
+        assert_eq!(err.code(), "INVALID_KEYSTORE");
+        assert!(err.to_string().contains("cost too much"));
+        assert!(costly(1024, 1 << 15, 1 << 15)
+            .to_string()
+            .contains("cost too much"));
+        assert!(costly(1 << 20, 8, 4).to_string().contains("cost too much"));
+        // the default cost is well within the bounds
+        let default = ScryptParams::default();
+        assert!(default.n * default.r as u64 * default.p as u64 <= MAX_SCRYPT_COST);
+    }
+
+    #[test]
+    fn test_keystore_signer() {
+        let path = std::env::temp_dir().join("zkdex_keystore_signer_test.json");
+        std::fs::write(&path, KEYSTORE).unwrap();
+        let signer = KeystoreSigner::from_file(&path, PASSWORD).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            signer.public_key(),
+            "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+        );
+
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        assert_eq!(signer.sign(msg).unwrap(), sign(PRI_KEY, msg).unwrap());
+
+        let withdrawal = r#"{
+            "type": "UNIFIED_WITHDRAWAL",
+            "amount": "1682637359498011204",
+            "eth_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+            "expiration_timestamp": "2101470722",
+            "asset_id": "0x11111",
+            "nonce": "4265854110",
+            "position_id": "775817640",
+            "fee": "0",
+            "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+            "chain_id": "123"
+        }"#;
+        assert_eq!(
+            signer.sign_any(withdrawal).unwrap(),
+            sign_any(withdrawal, PRI_KEY).unwrap()
+        );
+
+        let missing = std::env::temp_dir().join("zkdex_keystore_missing.json");
+        let err = KeystoreSigner::from_file(missing, PASSWORD).err().unwrap();
+        assert_eq!(err.code(), "INVALID_KEYSTORE");
+    }
+}
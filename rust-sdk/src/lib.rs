@@ -5,17 +5,19 @@ extern crate test as other_test;
 use std::convert::TryFrom;
 use std::str::FromStr;
 
-use anyhow::{Error, Result};
 pub use convert::*;
+pub use error::ZkdexError;
 use ethers::abi::{encode_packed, AbiEncode, Token};
+use ethers::types::{Address, Signature as EthSignature};
+use ethers::utils::hash_message;
 pub use franklin_crypto::bellman::pairing::bn256::{Bn256 as Engine, Fr};
-use franklin_crypto::rescue::bn256::Bn256RescueParams;
 use franklin_crypto::{
-    alt_babyjubjub::{fs::FsRepr, AltJubjubBn256, FixedGenerators},
+    alt_babyjubjub::{fs::FsRepr, FixedGenerators},
     bellman::pairing::ff::{PrimeField, PrimeFieldRepr},
     eddsa::PublicKey,
     jubjub::JubjubEngine,
 };
+pub use hash::{poseidon_hash_field_elements, poseidon_hash_u64_limbs};
 use num::Integer;
 use num_bigint::BigInt;
 use num_traits::Num;
@@ -25,54 +27,61 @@ use sha2::{Digest as tDigest, Sha256};
 use sha3::Digest;
 use sha3::Keccak256;
 
+pub use keystore::{decrypt_keystore, encrypt_keystore, KeystoreSigner};
 pub use serde_wrapper::*;
-use types::HashType;
+pub use tx::secret_key::SecretKey;
+pub use tx::sign::NonceMode;
+pub use tx::verifier::{Verifier, VerifierStats};
+pub use tx::{GENERATOR_TABLE, JUBJUB_PARAMS, RESCUE_PARAMS};
+use types::{ConditionType, HashType};
 pub use unified::*;
+pub use validate::Validate;
 
+use crate::error::{parse_json, Result};
 use crate::felt::LeBytesConvert;
 use crate::hash_type::hash_type_to_string_with_0xprefix;
 use crate::transaction::limit_order::LimitOrderRequest;
 use crate::transaction::liquidate::Liquidate;
 use crate::transaction::oracle_price::{signed_oracle_price_hash, SignedOraclePrice};
-use crate::transaction::transfer::{transfer_hash, Transfer};
+use crate::transaction::transfer::{transfer_hash, ConditionalTransfer, Transfer};
 use crate::transaction::withdraw::{withdrawal_hash, Withdraw, WithdrawRequest};
 use crate::transaction::{limit_order, oracle_price, transfer, withdraw};
 use crate::tx::convert::FeConvert;
+use crate::tx::derivation::{ExtendedPrivateKey, ExtendedPublicKey};
 use crate::tx::packed_public_key::{
-    convert_to_pubkey, private_key_from_string, public_key_from_private, PackedPublicKey,
+    fr_to_u256, private_key_from_string, private_key_to_string, public_key_from_private,
+    random_private_key, PackedPublicKey, PrivateKeyType,
 };
-use crate::tx::packed_signature::{get_r_from_xy, PackedSignature};
+use crate::tx::packed_signature::try_get_r_from_xy;
 use crate::tx::sign::TxSignature;
-use crate::zkw::{BabyJubjubPoint, JubjubSignature};
+use crate::tx::verifier::VERIFIER;
+use crate::zkw::JubjubSignature;
 
 pub mod common;
 mod constant;
 mod convert;
-
+pub mod error;
+pub mod fxp;
 mod hash;
 pub mod java_bridge;
 pub mod javascript_bridge;
+pub mod keystore;
 mod models;
 pub mod serde_wrapper;
 pub mod spot;
 pub mod transaction;
 pub mod tx;
 pub mod types;
-mod unified;
+pub mod unified;
 mod utils;
+pub mod validate;
 pub mod zkw;
 
 pub type Fs = <Engine as JubjubEngine>::Fs;
 
-thread_local! {
-    pub static JUBJUB_PARAMS: AltJubjubBn256 = AltJubjubBn256::new();
-    pub static RESCUE_PARAMS: Bn256RescueParams = Bn256RescueParams::new_checked_2_into_1();
-}
-
-lazy_static::lazy_static! {
-    // pub static ref RESCUE_HASHER: BabyRescueHasher = BabyRescueHasher::default();
-    pub static ref RESCUE_PARAMS_CONST: Bn256RescueParams = Bn256RescueParams::new_checked_2_into_1();
-}
+/// The former name of [`RESCUE_PARAMS`].
+#[deprecated(note = "use RESCUE_PARAMS")]
+pub static RESCUE_PARAMS_CONST: &RESCUE_PARAMS = &RESCUE_PARAMS;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -82,7 +91,7 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 pub fn private_key_from_seed(seed: &[u8]) -> Result<String> {
     if seed.len() < 32 {
-        return Err(Error::msg("seed is too short"));
+        return Err(ZkdexError::InvalidArgument("seed is too short".to_string()));
     };
 
     let sha256_bytes = |input: &[u8]| -> Vec<u8> {
@@ -107,33 +116,279 @@ pub fn private_key_from_seed(seed: &[u8]) -> Result<String> {
     }
 }
 
+/// generate_private_key, a new key pair from the os CSPRNG, `crypto.getRandomValues` on wasm.
+/// return: the private key in hex format and its packed public key
+pub fn generate_private_key() -> Result<L2Key> {
+    let private_key = random_private_key()?;
+    Ok(L2Key {
+        private_key: format!("0x{}", private_key_to_string(&private_key)),
+        public_key: public_key_from_private(&private_key).to_string(),
+    })
+}
+
+/// Derive the private key of the sub-account at `path`, like `m/7'/3'`, from the master seed, see
+/// [`tx::derivation`]. A private key handed out should sit at a hardened index, a normal one
+/// reveals its parent to whoever holds the parent extended public key.
+pub fn derive_private_key(seed: &[u8], path: &str) -> Result<String> {
+    let key = ExtendedPrivateKey::from_seed(seed)?.derive_path(path)?;
+    Ok(format!("0x{}", private_key_to_string(&key.private_key)))
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
+pub struct DerivedPublicKey {
+    pub public_key: String,
+    pub chain_code: String,
+}
+
+/// The public key and chain code of the sub-account at `path`, enough to derive its normal
+/// children with [`derive_public_key`] without the seed.
+pub fn derive_extended_public_key(seed: &[u8], path: &str) -> Result<DerivedPublicKey> {
+    let key = ExtendedPrivateKey::from_seed(seed)?
+        .derive_path(path)?
+        .public_key();
+    Ok(DerivedPublicKey {
+        public_key: key.public_key.to_string(),
+        chain_code: format!("0x{}", hex::encode(key.chain_code)),
+    })
+}
+
+/// Derive the public key at `path`, made of normal indexes only, below an extended public key.
+pub fn derive_public_key(public_key: &str, chain_code: &str, path: &str) -> Result<String> {
+    let public_key = PackedPublicKey::try_from(trim_0x(public_key))?;
+    let mut code = [0u8; 32];
+    hex::decode_to_slice(trim_0x(chain_code), &mut code)
+        .map_err(|e| ZkdexError::InvalidArgument(format!("chain_code: {}", e)))?;
+    let key = ExtendedPublicKey {
+        public_key,
+        chain_code: code,
+    };
+    Ok(key.derive_path(path)?.public_key.to_string())
+}
+
+/// The EIP-191 message a wallet signs with `personal_sign` to onboard, the L2 key is derived from
+/// the signature so it must never change.
+pub const ONBOARDING_MESSAGE: &str =
+    "Sign this message to create your zkDEX key. Only sign this message on a trusted site.";
+
+#[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
+pub struct L2Key {
+    pub private_key: String,
+    pub public_key: String,
+}
+
+/// The onboarding message text to hand to the wallet.
+pub fn onboarding_message() -> String {
+    ONBOARDING_MESSAGE.to_string()
+}
+
+/// The EIP-191 hash of the onboarding message, which is what the wallet actually signs.
+pub fn onboarding_message_hash() -> String {
+    format!("{:#x}", hash_message(ONBOARDING_MESSAGE))
+}
+
+/// Half the order of secp256k1, an s above it is high.
+const SECP256K1_HALF_ORDER: U256 = U256([
+    0xdfe92f46681b20a0,
+    0x5d576e7357a4501d,
+    0xffffffffffffffff,
+    0x7fffffffffffffff,
+]);
+/// The order of secp256k1.
+const SECP256K1_ORDER: U256 = U256([
+    0xbfd25e8cd0364141,
+    0xbaaedce6af48a03b,
+    0xfffffffffffffffe,
+    0xffffffffffffffff,
+]);
+
+/// Derive the L2 private key from the wallet signature of [`ONBOARDING_MESSAGE`].
+/// eth_signature: the 65 bytes r || s || v signature in hex format, a high s is taken as its low
+/// counterpart so both forms of a signature derive the same key
+/// address: the wallet address, the signature must recover to it
+pub fn private_key_from_eth_signature(
+    eth_signature: &str,
+    address: &str,
+) -> Result<PrivateKeyType> {
+    let mut signature = EthSignature::from_str(eth_signature)
+        .map_err(|e| ZkdexError::InvalidSignature(format!("eth_signature: {}", e)))?;
+    // (r, n - s) signs as well as (r, s) with the other parity of v
+    if signature.s > SECP256K1_HALF_ORDER {
+        signature.s = SECP256K1_ORDER - signature.s;
+        signature.v = match signature.v {
+            0 | 27 => signature.v + 1,
+            1 | 28 => signature.v - 1,
+            v => v,
+        };
+    }
+    let address = address
+        .parse::<Address>()
+        .map_err(|e| ZkdexError::InvalidArgument(format!("address: {}", e)))?;
+    let signer = signature
+        .recover(ONBOARDING_MESSAGE)
+        .map_err(|e| ZkdexError::InvalidSignature(format!("eth_signature: {}", e)))?;
+    if signer != address {
+        return Err(ZkdexError::InvalidSignature(format!(
+            "eth_signature is signed by {:?}, not {:?}",
+            signer, address
+        )));
+    }
+
+    // v is left out, wallets disagree on 0/1 or 27/28
+    let mut rs = [0u8; 64];
+    signature.r.to_big_endian(&mut rs[..32]);
+    signature.s.to_big_endian(&mut rs[32..]);
+    let seed = Keccak256::digest(&rs);
+    private_key_from_string(&private_key_from_seed(seed.as_slice())?)
+}
+
+/// Derive the L2 key pair from the wallet signature of [`ONBOARDING_MESSAGE`], see
+/// [`private_key_from_eth_signature`].
+pub fn l2_key_from_eth_signature(eth_signature: &str, address: &str) -> Result<L2Key> {
+    let private_key = private_key_from_eth_signature(eth_signature, address)?;
+    Ok(L2Key {
+        private_key: format!("0x{}", private_key_to_string(&private_key)),
+        public_key: public_key_from_private(&private_key).to_string(),
+    })
+}
+
 pub fn sign_transfer(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: Transfer = serde_json::from_str(json).unwrap();
-    Ok(transfer::sign_transfer(req, private_key)?)
+    let req: Transfer = parse_json(json)?;
+    transfer::sign_transfer(req, private_key)
+}
+
+/// Same as [`sign_transfer`] without the field range checks.
+pub fn sign_transfer_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Transfer = parse_json(json)?;
+    transfer::sign_transfer_unchecked(req, private_key)
 }
 
 pub fn hash_transfer(json: &str) -> Result<String> {
-    let req: Transfer = serde_json::from_str(json).unwrap();
-    Ok(hash_type_to_string_with_0xprefix(transfer_hash(&req, 0)))
+    let req: Transfer = parse_json(json)?;
+    Ok(hash_type_to_string_with_0xprefix(transfer_hash(
+        &req,
+        ConditionType::zero(),
+    )))
+}
+
+/// Verify a signature over a transfer, with or without fee
+/// json: the transfer in json format
+/// signature: the JubjubSignature
+/// pub_key: the public key of the sender in hex format
+/// return: true if the public key is the sender and the signature is valid
+pub fn verify_transfer(json: &str, signature: JubjubSignature, pub_key: &str) -> Result<bool> {
+    let req: Transfer = parse_json(json)?;
+    let packed_pk = PackedPublicKey::try_from(pub_key)?;
+    if req.base.public_key.0 != packed_pk.0 {
+        return Ok(false);
+    }
+    let msg = hash_type_to_string_with_0xprefix(transfer_hash(&req, ConditionType::zero()));
+    verify_jubjub_signature(signature, pub_key, &msg)
+}
+
+/// Sign a conditional transfer, its condition is computed from the fact registry address and fact
+/// json: the conditional transfer in json format
+/// private_key: the private key in hex format
+/// return: the JubjubSignature
+pub fn sign_conditional_transfer(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: ConditionalTransfer = parse_json(json)?;
+    transfer::sign_conditional_transfer(req, private_key)
+}
+
+/// Same as [`sign_conditional_transfer`] without the field range checks.
+pub fn sign_conditional_transfer_unchecked(
+    json: &str,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let req: ConditionalTransfer = parse_json(json)?;
+    transfer::sign_conditional_transfer_unchecked(req, private_key)
+}
+
+/// Hash a conditional transfer
+/// json: the conditional transfer in json format
+/// return: the hash in hex format
+pub fn hash_conditional_transfer(json: &str) -> Result<String> {
+    let req: ConditionalTransfer = parse_json(json)?;
+    Ok(hash_type_to_string_with_0xprefix(req.hash()))
+}
+
+/// Verify a signature over a conditional transfer
+/// json: the conditional transfer in json format
+/// signature: the JubjubSignature
+/// pub_key: the public key of the sender in hex format
+/// return: true if the public key is the sender and the signature is valid
+pub fn verify_conditional_transfer(
+    json: &str,
+    signature: JubjubSignature,
+    pub_key: &str,
+) -> Result<bool> {
+    let req: ConditionalTransfer = parse_json(json)?;
+    let packed_pk = PackedPublicKey::try_from(pub_key)?;
+    if req.transfer.base.public_key.0 != packed_pk.0 {
+        return Ok(false);
+    }
+    let msg = hash_type_to_string_with_0xprefix(req.hash());
+    verify_jubjub_signature(signature, pub_key, &msg)
+}
+
+/// Poseidon hash of field elements, see [`poseidon_hash_field_elements`]
+/// json: array of field elements in hex format, each below the field modulus
+/// return: the hash in hex format
+pub fn poseidon_hash_field_elements_json(json: &str) -> Result<String> {
+    let elements: Vec<String> = parse_json(json)?;
+    let elements = elements
+        .iter()
+        .map(|element| {
+            U256::from_str_radix(trim_0x(element), 16)
+                .map_err(|e| ZkdexError::InvalidArgument(format!("{}: {}", element, e)))
+        })
+        .collect::<Result<Vec<U256>>>()?;
+    Ok(hash_type_to_string_with_0xprefix(
+        poseidon_hash_field_elements(&elements)?,
+    ))
+}
+
+/// Poseidon hash of u64 limbs, see [`poseidon_hash_u64_limbs`]
+/// json: array of u64 limbs as decimal strings
+/// return: the hash in hex format
+pub fn poseidon_hash_u64_limbs_json(json: &str) -> Result<String> {
+    let limbs: Vec<String> = parse_json(json)?;
+    let limbs = limbs
+        .iter()
+        .map(|limb| {
+            limb.parse::<u64>()
+                .map_err(|e| ZkdexError::InvalidArgument(format!("{}: {}", limb, e)))
+        })
+        .collect::<Result<Vec<u64>>>()?;
+    Ok(hash_type_to_string_with_0xprefix(poseidon_hash_u64_limbs(
+        &limbs,
+    )))
 }
 
 pub fn sign_withdraw(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let withdraw_req: WithdrawRequest = serde_json::from_str(json)?;
+    let withdraw_req: WithdrawRequest = parse_json(json)?;
     let withdraw = Withdraw {
         base: withdraw_req.base,
         position_id: withdraw_req.position_id,
         amount: withdraw_req.amount,
         owner_key: withdraw_req.owner_key,
     };
-    Ok(withdraw::sign_withdraw(
-        withdraw,
-        &withdraw_req.asset_id,
-        private_key,
-    )?)
+    withdraw::sign_withdraw(withdraw, &withdraw_req.asset_id, private_key)
+}
+
+/// Same as [`sign_withdraw`] without the field range checks.
+pub fn sign_withdraw_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let withdraw_req: WithdrawRequest = parse_json(json)?;
+    let withdraw = Withdraw {
+        base: withdraw_req.base,
+        position_id: withdraw_req.position_id,
+        amount: withdraw_req.amount,
+        owner_key: withdraw_req.owner_key,
+    };
+    withdraw::sign_withdraw_unchecked(withdraw, &withdraw_req.asset_id, private_key)
 }
 
 pub fn hash_withdraw(json: &str) -> Result<String> {
-    let withdraw_req: WithdrawRequest = serde_json::from_str(json)?;
+    let withdraw_req: WithdrawRequest = parse_json(json)?;
     let withdraw = Withdraw {
         base: withdraw_req.base,
         position_id: withdraw_req.position_id,
@@ -147,39 +402,58 @@ pub fn hash_withdraw(json: &str) -> Result<String> {
 }
 
 pub fn sign_limit_order(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: LimitOrderRequest = serde_json::from_str(json)?;
-    Ok(limit_order::sign_limit_order(req, private_key)?)
+    let req: LimitOrderRequest = parse_json(json)?;
+    limit_order::sign_limit_order(req, private_key)
+}
+
+/// Same as [`sign_limit_order`] without the field range checks.
+pub fn sign_limit_order_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: LimitOrderRequest = parse_json(json)?;
+    limit_order::sign_limit_order_unchecked(req, private_key)
 }
 
 pub fn hash_limit_order(json: &str) -> Result<String> {
-    let req: LimitOrderRequest = serde_json::from_str(json)?;
+    let req: LimitOrderRequest = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(
         crate::transaction::limit_order::hash_limit_order(req),
     ))
 }
 
 pub fn sign_liquidate(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: Liquidate = serde_json::from_str(json)?;
-    Ok(limit_order::sign_limit_order(
-        req.liquidator_order,
-        private_key,
-    )?)
+    let req: Liquidate = parse_json(json)?;
+    req.validate(None)?;
+    limit_order::sign_limit_order_unchecked(req.liquidator_order, private_key)
+}
+
+/// Same as [`sign_liquidate`] without the field range checks.
+pub fn sign_liquidate_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Liquidate = parse_json(json)?;
+    limit_order::sign_limit_order_unchecked(req.liquidator_order, private_key)
 }
 
 pub fn hash_liquidate(json: &str) -> Result<String> {
-    let req: Liquidate = serde_json::from_str(json)?;
+    let req: Liquidate = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(
         limit_order::hash_limit_order(req.liquidator_order),
     ))
 }
 
 pub fn sign_signed_oracle_price(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: SignedOraclePrice = serde_json::from_str(json)?;
-    Ok(oracle_price::sign_signed_oracle_price(req, private_key)?)
+    let req: SignedOraclePrice = parse_json(json)?;
+    oracle_price::sign_signed_oracle_price(req, private_key)
+}
+
+/// Same as [`sign_signed_oracle_price`] without the field range checks.
+pub fn sign_signed_oracle_price_unchecked(
+    json: &str,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let req: SignedOraclePrice = parse_json(json)?;
+    oracle_price::sign_signed_oracle_price_unchecked(req, private_key)
 }
 
 pub fn hash_signed_oracle_price(json: &str) -> Result<String> {
-    let req: SignedOraclePrice = serde_json::from_str(json)?;
+    let req: SignedOraclePrice = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(signed_oracle_price_hash(
         &req,
     )))
@@ -188,14 +462,14 @@ pub fn hash_signed_oracle_price(json: &str) -> Result<String> {
 pub fn private_key_to_pubkey_xy(private_key: &str) -> Result<(String, String)> {
     let pri_key = private_key_from_string(private_key)?;
     let packed_pk: PackedPublicKey = public_key_from_private(&pri_key);
-    Ok(pub_key_to_xy(&packed_pk.to_string())?)
+    pub_key_to_xy(&packed_pk.to_string())
 }
 
 pub fn pub_key_to_xy(pub_key: &str) -> Result<(String, String)> {
     let pub_key = pub_key.trim_start_matches("0x").trim_start_matches("0X");
     let packed_pk = PackedPublicKey::try_from(pub_key)?;
 
-    let jubjub_pk: BabyJubjubPoint = packed_pk.into();
+    let jubjub_pk = packed_pk.try_into_point()?;
     let mut x_point = [0; 32];
     jubjub_pk.x.to_big_endian(&mut x_point);
 
@@ -205,8 +479,12 @@ pub fn pub_key_to_xy(pub_key: &str) -> Result<(String, String)> {
     ))
 }
 
+pub(crate) fn parse_hash(msg: &str) -> Result<HashType> {
+    HashType::from_str(msg).map_err(|e| ZkdexError::InvalidHash(format!("{}: {}", msg, e)))
+}
+
 pub fn sign(private_key: &str, msg: &str) -> Result<JubjubSignature> {
-    let hash = HashType::from_str(msg)?;
+    let hash = parse_hash(msg)?;
     let private_key = private_key_from_string(private_key)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
@@ -219,31 +497,24 @@ pub fn verify_signature(
     _pub_key_y: &str,
     msg: &str,
 ) -> Result<bool> {
-    let sig = JubjubSignature::from_str(sig_r, sig_s);
-    let sig = PackedSignature::from(sig);
-    let msg = HashType::from_str(msg)?;
-    let packed_pk = PackedPublicKey::try_from(pub_key_x)?;
-    let jubjub_pk: BabyJubjubPoint = packed_pk.into();
-    let pk = convert_to_pubkey(&jubjub_pk.x, &jubjub_pk.y)?;
-    Ok(sig.verify(&pk, msg.as_le_bytes()))
+    let sig = JubjubSignature::try_from_str(sig_r, sig_s)?;
+    verify_jubjub_signature(sig, pub_key_x, msg)
 }
 
 pub fn verify_jubjub_signature(sig: JubjubSignature, pub_key: &str, msg: &str) -> Result<bool> {
-    let sig = PackedSignature::from(sig);
-    let msg = HashType::from_str(msg)?;
-    let packed_pk = PackedPublicKey::try_from(pub_key)?;
-    let jubjub_pk: BabyJubjubPoint = packed_pk.into();
-    let pk = convert_to_pubkey(&jubjub_pk.x, &jubjub_pk.y)?;
-    Ok(sig.verify(&pk, msg.as_le_bytes()))
+    VERIFIER.verify_jubjub_signature(&sig, pub_key, msg)
 }
 
+/// Check the packed public key `x` is a point of the prime order subgroup with x coordinate `y`,
+/// a key off the curve, of small order or outside the subgroup is an error.
 pub fn is_on_curve(x: &str, y: &str) -> Result<bool> {
     let x = x.trim_start_matches("0x").trim_start_matches("0X");
     let y = y.trim_start_matches("0x").trim_start_matches("0X");
-    let (x1, y1) = pub_key_to_xy(x)?;
-    let x1 = x1.trim_start_matches("0x").trim_start_matches("0X");
-    let y1 = y1.trim_start_matches("0x").trim_start_matches("0X");
-    Ok(x1 == x && y1 == y)
+    let public_key = PackedPublicKey::try_from(x)?.try_into_public_key()?;
+    let y = U256::from_str_radix(y, 16)
+        .map_err(|e| ZkdexError::InvalidPublicKey(format!("{}: {}", y, e)))?;
+    let (x1, _) = public_key.0.into_xy();
+    Ok(fr_to_u256(&x1)? == y)
 }
 
 #[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
@@ -258,10 +529,10 @@ pub struct L1Signature {
 pub fn l2_sign(msg: &str, private_key: &str) -> Result<L1Signature> {
     let msg = msg.trim_start_matches("0x").trim_start_matches("0X");
     let private_key = private_key_from_string(private_key)?;
-    let msg = HashType::from_str(msg)?;
+    let msg = parse_hash(msg)?;
     let (sig, packed_pk) = TxSignature::sign_msg(&private_key, msg.as_le_bytes());
     let p_g = FixedGenerators::SpendingKeyGenerator;
-    let pk = PublicKey::from_private(&private_key, p_g, &AltJubjubBn256::new());
+    let pk = PublicKey::from_private(&private_key, p_g, &JUBJUB_PARAMS);
     let (pk_x, _) = pk.0.into_xy();
     let (x, y) = sig.signature.0.r.into_xy();
     Ok(L1Signature {
@@ -276,24 +547,36 @@ pub fn l2_sign(msg: &str, private_key: &str) -> Result<L1Signature> {
 pub fn l2_verify(x: &str, y: &str, s: &str, pk_x: &str, pk_y: &str, msg: &str) -> Result<bool> {
     let x = x.trim_start_matches("0x").trim_start_matches("0X");
     let y = y.trim_start_matches("0x").trim_start_matches("0X");
-    let r = get_r_from_xy(
-        &U256::from_str_radix(x, 16).unwrap(),
-        &U256::from_str_radix(y, 16).unwrap(),
-    );
+    let x =
+        U256::from_str_radix(x, 16).map_err(|e| ZkdexError::InvalidSignature(e.to_string()))?;
+    let y =
+        U256::from_str_radix(y, 16).map_err(|e| ZkdexError::InvalidSignature(e.to_string()))?;
+    let r = try_get_r_from_xy(&x, &y)
+        .ok_or_else(|| ZkdexError::InvalidSignature("r is not on the curve".to_string()))?;
     let s = s.trim_start_matches("0x").trim_start_matches("0X");
     verify_signature(&format!("0x{:064x}", r), s, pk_x, pk_y, msg)
 }
 
 pub fn sign_eth_address(chain_id:&str,contract_address: &str,address: &str, pub_key: &str, private_key: &str) -> Result<String> {
     let t1 = Token::String("UserRegistration:".to_string());
-    let t2 = Token::FixedBytes(U256::from_str_radix(chain_id, 10).unwrap().encode());
-    let t3 = Token::Address(contract_address.parse().unwrap());
-    let t4 = Token::Address(address.parse().unwrap());
+    let chain_id = U256::from_str_radix(chain_id, 10)
+        .map_err(|e| ZkdexError::InvalidArgument(format!("chain_id: {}", e)))?;
+    let t2 = Token::FixedBytes(chain_id.encode());
+    let contract_address = contract_address
+        .parse::<Address>()
+        .map_err(|e| ZkdexError::InvalidArgument(format!("contract_address: {}", e)))?;
+    let t3 = Token::Address(contract_address);
+    let address = address
+        .parse::<Address>()
+        .map_err(|e| ZkdexError::InvalidArgument(format!("address: {}", e)))?;
+    let t4 = Token::Address(address);
 
     //let t3 = Token::Uint(U256::from_str_radix(pub_key, 16).unwrap());
 
-    let t5 = Token::FixedBytes(U256::from_str_radix(pub_key, 16).unwrap().encode());
-    let data = encode_packed(&[t1, t2, t3,t4,t5]).unwrap();
+    let pub_key = PackedPublicKey::try_from(pub_key)?;
+    let t5 = Token::FixedBytes(pub_key.0.encode());
+    let data = encode_packed(&[t1, t2, t3, t4, t5])
+        .map_err(|e| ZkdexError::InvalidArgument(e.to_string()))?;
     let result = Keccak256::digest(data.as_slice());
     let max = BigInt::from_str(
         "21888242871839275222246405745257275088548364400416034343698204186575808495617",
@@ -311,8 +594,8 @@ pub fn sign_eth_address(chain_id:&str,contract_address: &str,address: &str, pub_
     Ok(sig)
 }
 
-pub fn reverse_hex(str: &str) -> anyhow::Result<String> {
-    let mut ret = hex::decode(str)?;
+pub fn reverse_hex(str: &str) -> Result<String> {
+    let mut ret = hex::decode(str).map_err(|e| ZkdexError::InvalidArgument(e.to_string()))?;
     ret.as_mut_slice().reverse();
     Ok(hex::encode(ret))
 }
@@ -324,54 +607,84 @@ struct Signature<'a> {
 }
 
 pub fn sign_spot_transfer(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: spot::Transfer = serde_json::from_str(json).unwrap();
-    Ok(spot::sign_transfer(req, private_key)?)
+    let req: spot::Transfer = parse_json(json)?;
+    spot::sign_transfer(req, private_key)
+}
+
+/// Same as [`sign_spot_transfer`] without the field range checks.
+pub fn sign_spot_transfer_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: spot::Transfer = parse_json(json)?;
+    spot::sign_transfer_unchecked(req, private_key)
 }
 
 pub fn hash_spot_transfer(json: &str) -> Result<String> {
-    let req: spot::Transfer = serde_json::from_str(json).unwrap();
+    let req: spot::Transfer = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(spot::transfer_hash(&req)))
 }
 
 pub fn sign_spot_limit_order(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: spot::limit_order::LimitOrder = serde_json::from_str(json).unwrap();
-    Ok(spot::limit_order::sign_limit_order(&req, private_key)?)
+    let req: spot::limit_order::LimitOrder = parse_json(json)?;
+    spot::limit_order::sign_limit_order(&req, private_key)
+}
+
+/// Same as [`sign_spot_limit_order`] without the field range checks.
+pub fn sign_spot_limit_order_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: spot::limit_order::LimitOrder = parse_json(json)?;
+    spot::limit_order::sign_limit_order_unchecked(&req, private_key)
 }
 
 pub fn hash_spot_limit_order(json: &str) -> Result<String> {
-    let req: spot::limit_order::LimitOrder = serde_json::from_str(json).unwrap();
+    let req: spot::limit_order::LimitOrder = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash()))
 }
 
 pub fn sign_spot_withdrawal(json: &str, private_key: &str) -> Result<JubjubSignature> {
-    let req: spot::Withdrawal = serde_json::from_str(json).unwrap();
-    Ok(spot::sign_withdrawal(&req, private_key)?)
+    let req: spot::Withdrawal = parse_json(json)?;
+    spot::sign_withdrawal(&req, private_key)
+}
+
+/// Same as [`sign_spot_withdrawal`] without the field range checks.
+pub fn sign_spot_withdrawal_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: spot::Withdrawal = parse_json(json)?;
+    spot::sign_withdrawal_unchecked(&req, private_key)
 }
 
 pub fn hash_spot_withdrawal(json: &str) -> Result<String> {
-    let req: spot::Withdrawal = serde_json::from_str(json).unwrap();
+    let req: spot::Withdrawal = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash()))
 }
 
 #[cfg(test)]
 mod test {
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::utils::hash_message;
     use other_test::Bencher;
 
+    use franklin_crypto::alt_babyjubjub::AltJubjubBn256;
+    use franklin_crypto::bellman::pairing::ff::Field;
+    use franklin_crypto::jubjub::edwards::Point;
+    use franklin_crypto::jubjub::Unknown;
     use pairing_ce::bn256::Fr;
+    use primitive_types::U256;
 
-    use crate::transaction::limit_order::LimitOrderRequest;
     use crate::tx::convert::FeConvert;
-    use crate::tx::packed_public_key::{private_key_from_string, public_key_from_private};
-    use crate::tx::public_key_type::PublicKeyType;
+    use crate::tx::packed_public_key::{
+        fr_to_u256, private_key_from_string, public_key_from_private,
+    };
+    use crate::tx::packed_signature::try_point_from_xy;
+    use crate::tx::JUBJUB_PARAMS;
     use crate::zkw::JubjubSignature;
     use crate::{
-        hash_limit_order, hash_liquidate, hash_signed_oracle_price, hash_spot_limit_order,
-        hash_spot_transfer, hash_spot_withdrawal, hash_transfer, hash_withdraw, is_on_curve,
-        l2_sign, l2_verify, private_key_from_seed, private_key_to_pubkey_xy, pub_key_to_xy,
-        reverse_hex, sign, sign_eth_address, sign_limit_order, sign_liquidate,
-        sign_signed_oracle_price, sign_spot_limit_order, sign_spot_transfer, sign_spot_withdrawal,
-        sign_transfer, sign_withdraw, verify_jubjub_signature, verify_signature, L1Signature,
-        Signature,
+        hash_conditional_transfer, hash_limit_order, hash_liquidate, hash_signed_oracle_price,
+        hash_spot_limit_order, hash_spot_transfer, hash_spot_withdrawal, hash_transfer,
+        hash_withdraw, is_on_curve, l2_key_from_eth_signature, l2_sign, l2_verify,
+        onboarding_message, onboarding_message_hash, private_key_from_seed,
+        private_key_to_pubkey_xy, pub_key_to_xy, reverse_hex, sign, sign_conditional_transfer,
+        sign_conditional_transfer_unchecked, sign_eth_address, sign_limit_order, sign_liquidate,
+        sign_signed_oracle_price, sign_spot_limit_order, sign_spot_limit_order_unchecked,
+        sign_spot_transfer, sign_spot_withdrawal, sign_transfer, sign_transfer_unchecked,
+        sign_withdraw, verify_conditional_transfer, verify_jubjub_signature, verify_signature,
+        verify_transfer, Engine, L1Signature, Signature, ZkdexError,
     };
 
     const PRI_KEY: &str = "0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe";
@@ -384,6 +697,15 @@ mod test {
         assert!(sig.s.len() == 66);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_params_aliases() {
+        let jubjub = JUBJUB_PARAMS.with(|params| params as *const AltJubjubBn256);
+        assert!(std::ptr::eq(jubjub, &*JUBJUB_PARAMS));
+        let rescue = crate::RESCUE_PARAMS.with(|params| params as *const _);
+        assert!(std::ptr::eq(rescue, &**crate::RESCUE_PARAMS_CONST));
+    }
+
     #[test]
     pub fn test_verify_signature() {
         let sigr = "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749";
@@ -519,11 +841,101 @@ mod test {
         "asset_id":"0xa"
         }
         "#;
+        let err = sign_transfer(json, PRI_KEY).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        let json = json.replace(r#""amount":"0""#, r#""amount":"1""#);
+        let json = json.as_str();
         let sig = sign_transfer(json, PRI_KEY).unwrap();
         let hash = hash_transfer(json).unwrap();
         assert!(verify_jubjub_signature(sig, PUB_KEY, &hash).unwrap());
     }
 
+    #[test]
+    pub fn test_sign_conditional_transfer() {
+        let json = r#"
+        {
+        "nonce":"1",
+        "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+        "expiration_timestamp":"1684832800",
+        "sender_position_id":"2",
+        "receiver_public_key":"0x0000000000000000000000000000000000000000000000000000000000000000",
+        "receiver_position_id":"3",
+        "amount":"100",
+        "asset_id":"0xa",
+        "fact_registry_address":"0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+        "fact":"0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b"
+        }
+        "#;
+        let hash = hash_conditional_transfer(json).unwrap();
+        assert_eq!(
+            hash,
+            "0x0e82549aeb0699bb64a2f9273ebaeec466b4866db5200a491b9704856ee76bb3"
+        );
+        assert_ne!(hash, hash_transfer(json).unwrap());
+
+        let sig = sign_conditional_transfer(json, PRI_KEY).unwrap();
+        assert_eq!(
+            sig,
+            sign_conditional_transfer_unchecked(json, PRI_KEY).unwrap()
+        );
+        assert!(verify_jubjub_signature(sig.clone(), PUB_KEY, &hash).unwrap());
+        assert!(verify_conditional_transfer(json, sig.clone(), PUB_KEY).unwrap());
+        let other_key = "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa";
+        assert!(!verify_conditional_transfer(json, sig.clone(), other_key).unwrap());
+        let other_fact = json.replace("0x0a7d1a7e", "0x0b7d1a7e");
+        assert!(!verify_conditional_transfer(&other_fact, sig, PUB_KEY).unwrap());
+
+        let err = hash_conditional_transfer(&json.replace("0xB6aD5EfB", "0xB6aD")).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
+    #[test]
+    pub fn test_sign_transfer_with_fee() {
+        let json = r#"
+        {
+        "nonce":"1",
+        "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+        "expiration_timestamp":"1684832800",
+        "sender_position_id":"2",
+        "receiver_public_key":"0x0000000000000000000000000000000000000000000000000000000000000000",
+        "receiver_position_id":"3",
+        "amount":"100",
+        "asset_id":"0xa",
+        "fee":"5",
+        "fee_asset_id":"0xb",
+        "fee_position_id":"4"
+        }
+        "#;
+        let hash = hash_transfer(json).unwrap();
+        assert_eq!(
+            hash,
+            "0x18f326b750d7c82f4cb4a3b0fec1f21e704bd14c192566f45fbbfaeaefb1ef44"
+        );
+
+        let sig = sign_transfer(json, PRI_KEY).unwrap();
+        assert_eq!(sig, sign_transfer_unchecked(json, PRI_KEY).unwrap());
+        assert!(verify_transfer(json, sig.clone(), PUB_KEY).unwrap());
+        let other_key = "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa";
+        assert!(!verify_transfer(json, sig.clone(), other_key).unwrap());
+        let higher_fee = json.replace(r#""fee":"5""#, r#""fee":"6""#);
+        assert!(!verify_transfer(&higher_fee, sig.clone(), PUB_KEY).unwrap());
+        let mut without_fee: serde_json::Value = serde_json::from_str(json).unwrap();
+        for field in ["fee", "fee_asset_id", "fee_position_id"] {
+            assert!(without_fee.as_object_mut().unwrap().remove(field).is_some());
+        }
+        let without_fee = without_fee.to_string();
+        assert!(!verify_transfer(&without_fee, sig, PUB_KEY).unwrap());
+
+        let without_position = json.replace(r#""fee_asset_id":"0xb","#, "");
+        let err = sign_transfer(&without_position, PRI_KEY).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: fee_asset_id: must be set with a fee"
+        );
+        let err = hash_transfer(&json.replace(r#""fee":"5""#, r#""fee":{}"#)).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
     #[test]
     pub fn test_hash_transfer() {
         let json = r#"
@@ -661,7 +1073,6 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
     pub fn test_hash_limit_order_with_err_public_key() {
         let json = r#"{
         "nonce":"1",
@@ -675,10 +1086,8 @@ mod test {
         "position_id":"8",
         "is_buying_synthetic":false
         }"#;
-        let req: LimitOrderRequest = serde_json::from_str(json).unwrap();
-        let _pk: PublicKeyType = req.base.public_key.into();
-
-        assert!(hash_limit_order(json).unwrap().len() == 66)
+        let err = hash_limit_order(json).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PUBLIC_KEY");
     }
 
     #[test]
@@ -1039,6 +1448,98 @@ mod test {
         assert!(pri_key.len() == 66);
     }
 
+    #[test]
+    fn test_generate_private_key() {
+        let key = generate_private_key().unwrap();
+        assert_eq!(key.private_key.len(), 66);
+        let (x, _) = private_key_to_pubkey_xy(&key.private_key).unwrap();
+        assert_eq!(x, key.public_key);
+        assert_ne!(generate_private_key().unwrap(), key);
+    }
+
+    #[test]
+    fn test_derive_keys() {
+        let seed = "hello world good life 996 very nice".as_bytes();
+        assert_eq!(
+            derive_private_key(seed, "m").unwrap(),
+            private_key_from_seed(seed).unwrap()
+        );
+        assert_eq!(
+            derive_private_key(seed, "m/7'/3").unwrap(),
+            "0x006dacb284fb197c484075cb03f14ba8be86c07c96ab08a50ebef9813278b33f"
+        );
+
+        let master = derive_extended_public_key(seed, "m").unwrap();
+        assert_eq!(
+            master.chain_code,
+            "0x5aa33b47fa41321555e4fd679ededc4bc9886a414930ff242f7a7a60fb5eb499"
+        );
+        let child = derive_public_key(&master.public_key, &master.chain_code, "m/1/2").unwrap();
+        assert_eq!(
+            child,
+            "0x1e50f110dfe5d4da873afd8ecb2527e13f26c34aca560f11c56914e1011677f4"
+        );
+
+        let err = derive_public_key(&master.public_key, "0x1234", "m/1").unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        let err = derive_private_key(seed, "7/3").unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_l2_key_from_eth_signature() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let address = format!("{:?}", wallet.address());
+        let mut signature = wallet
+            .sign_hash(hash_message(onboarding_message()))
+            .unwrap();
+        let key = l2_key_from_eth_signature(&signature.to_string(), &address).unwrap();
+        assert_eq!(key.private_key.len(), 66);
+        let (x, _) = private_key_to_pubkey_xy(&key.private_key).unwrap();
+        assert_eq!(pub_key_to_xy(&key.public_key).unwrap().0, x);
+
+        // the same key whatever the encoding of v
+        signature.v -= 27;
+        let same = l2_key_from_eth_signature(&format!("0x{}", signature), &address).unwrap();
+        assert_eq!(same, key);
+
+        let other = "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb";
+        let err = l2_key_from_eth_signature(&signature.to_string(), other).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        let err = l2_key_from_eth_signature("0x1234", &address).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+
+        // the high s form of the signature derives the same key
+        let order = U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        for v in [0, 27] {
+            let mut high = signature;
+            high.v = if signature.v == 0 { v + 1 } else { v };
+            high.s = order - signature.s;
+            let high = l2_key_from_eth_signature(&format!("0x{}", high), &address).unwrap();
+            assert_eq!(high, key);
+        }
+
+        // every client must derive this key from this signature
+        assert_eq!(
+            onboarding_message_hash(),
+            "0x9861b0cb28d8d20c28107c0947c5f1e2c6843ec3e6d3aec0546d1e3169645412"
+        );
+        let eth_signature = "0xe95a2346431278a7ba90b34ed34cb6a3b54cecdcdb583f8ce1a130ba2bb08bf1127e20e256d7d3c5b98c460b1656f9b695e16d1163bbc50f4f94d4961fe537821b";
+        let address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+        let key = l2_key_from_eth_signature(eth_signature, address).unwrap();
+        assert_eq!(
+            key.private_key,
+            "0x017dcf6e6b75b27c1a0bd215f839e4572e59702202ce28576e65b520182719a7"
+        );
+    }
+
     #[test]
     fn test_is_on_curve() {
         let x = "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a";
@@ -1059,6 +1560,74 @@ mod test {
         assert!(ret == false);
     }
 
+    #[test]
+    fn test_poseidon_hash_json() {
+        assert_eq!(
+            crate::poseidon_hash_field_elements_json(r#"["0x1", "0x2"]"#).unwrap(),
+            "0x1ae3633579d8817e55590943ae6e61019d09b0728a1736c0e7bb873bdbcd5619"
+        );
+        assert_eq!(
+            crate::poseidon_hash_u64_limbs_json(r#"["1", "0", "0", "0", "2"]"#).unwrap(),
+            "0x1ae3633579d8817e55590943ae6e61019d09b0728a1736c0e7bb873bdbcd5619"
+        );
+
+        let modulus = r#"["0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"]"#;
+        for err in [
+            crate::poseidon_hash_field_elements_json(modulus).unwrap_err(),
+            crate::poseidon_hash_field_elements_json(r#"["0xzz"]"#).unwrap_err(),
+            crate::poseidon_hash_u64_limbs_json(r#"["18446744073709551616"]"#).unwrap_err(),
+        ] {
+            assert_eq!(err.code(), "INVALID_ARGUMENT");
+        }
+        let err = crate::poseidon_hash_u64_limbs_json("[1]").unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
+    #[test]
+    fn test_verify_rejects_malleable_signature() {
+        let msg = "0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9";
+        let sig = sign(PRI_KEY, msg).unwrap();
+        assert!(verify_jubjub_signature(sig.clone(), PUB_KEY, msg).unwrap());
+
+        // s + l is the same scalar, it must still be refused
+        let order = U256::from_dec_str(
+            "2736030358979909402780800718157159386076813972158567259200215660948447373041",
+        )
+        .unwrap();
+        let mut wide_s = sig.clone();
+        wide_s.sig_s = (U256(sig.sig_s) + order).0;
+        let err = verify_jubjub_signature(wide_s, PUB_KEY, msg).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+
+        // r plus the point of order 2 passes the cofactored equation but not the subgroup check
+        let mut minus_one = Fr::one();
+        minus_one.negate();
+        let order_two = Point::<Engine, Unknown>::from_xy(
+            Fr::zero(),
+            minus_one,
+            &JUBJUB_PARAMS as &AltJubjubBn256,
+        )
+        .unwrap();
+        let (x, y) = try_point_from_xy(&sig.sig_r.x, &sig.sig_r.y)
+            .unwrap()
+            .add(&order_two, &JUBJUB_PARAMS)
+            .into_xy();
+        let mut mixed_r = sig.clone();
+        mixed_r.sig_r.x = fr_to_u256(&x).unwrap();
+        mixed_r.sig_r.y = fr_to_u256(&y).unwrap();
+        let err = verify_jubjub_signature(mixed_r, PUB_KEY, msg).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid signature: r is not in the prime order subgroup"
+        );
+
+        let err = verify_jubjub_signature(sig.clone(), "0x1", msg).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PUBLIC_KEY");
+        let order_two = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        let err = is_on_curve(order_two, "0x0").unwrap_err();
+        assert!(err.to_string().ends_with("is a point of small order"));
+    }
+
     #[test]
     fn test_pub_key_to_xy() {
         let pk = "8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa";
@@ -1212,10 +1781,18 @@ mod test {
             "position_id":"1"
 
             }"#;
-        let sig = sign_spot_limit_order(json, PRI_KEY).unwrap();
+        let sig = sign_spot_limit_order_unchecked(json, PRI_KEY).unwrap();
         assert!(
             verify_jubjub_signature(sig, PUB_KEY, &hash_spot_limit_order(json).unwrap()).unwrap()
         );
+
+        match sign_spot_limit_order(json, PRI_KEY).unwrap_err() {
+            ZkdexError::InvalidFields(violations) => {
+                let fields: Vec<_> = violations.iter().map(|v| v.field.as_str()).collect();
+                assert_eq!(fields, vec!["amount_buy", "amount_sell"]);
+            }
+            e => panic!("unexpected error: {}", e),
+        }
     }
 
     #[test]
@@ -27,10 +27,10 @@ pub use integer::*;
 pub use offset_date_time_serde::OffsetDateTimeSerdeAsTimeStampStr;
 pub use other_serde::*;
 pub use std_duration_serde::{StdDurationSerdeAsSecondsStr, StdDurationSerdeAsSecondsU64};
-pub use u256_serde::U256SerdeAsRadix16Prefix0xString;
+pub use u256_serde::{OptionU256SerdeAsRadix16Prefix0xString, U256SerdeAsRadix16Prefix0xString};
 pub use u32_serde::{U128SerdeAsString, U32SerdeAsString};
 pub use u64_serde::U64SerdeAsRadix16Prefix0xString;
-pub use u64_serde::U64SerdeAsString;
+pub use u64_serde::{OptionU64SerdeAsString, U64SerdeAsString};
 pub use u8_array_serde::{U8Array32SerdeAsStringWith0x, U8Array64SignatureSerde};
 
 pub use transfer::TransferBaseSerde;
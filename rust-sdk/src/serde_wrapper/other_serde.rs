@@ -4,6 +4,7 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::str::FromStr;
 
 use crate::convert::*;
+use crate::error::ZkdexError;
 
 /// Trait for specifying prefix for bytes to hex serialization
 pub trait Prefix {
@@ -142,8 +143,10 @@ impl UnsignedRatioSerializeAsDecimal {
         big_decimal_to_ratio(&big_decimal_string).map_err(de::Error::custom)
     }
 
-    pub fn deserialize_from_str_with_dot(input: &str) -> Result<Ratio<BigUint>, anyhow::Error> {
-        big_decimal_to_ratio(&BigDecimal::from_str(input)?)
+    pub fn deserialize_from_str_with_dot(input: &str) -> crate::error::Result<Ratio<BigUint>> {
+        let num = BigDecimal::from_str(input)
+            .map_err(|e| ZkdexError::InvalidArgument(format!("{}: {}", input, e)))?;
+        big_decimal_to_ratio(&num)
     }
 
     pub fn serialize_to_str_with_dot(num: &Ratio<BigUint>, precision: usize) -> String {
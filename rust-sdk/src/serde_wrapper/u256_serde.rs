@@ -21,6 +21,33 @@ impl U256SerdeAsRadix16Prefix0xString {
     }
 }
 
+/// [`U256SerdeAsRadix16Prefix0xString`] for an optional value, a null or missing field is `None`.
+pub struct OptionU256SerdeAsRadix16Prefix0xString;
+
+impl OptionU256SerdeAsRadix16Prefix0xString {
+    pub fn serialize<S>(val: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match val {
+            Some(val) => U256SerdeAsRadix16Prefix0xString::serialize(val, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<U256>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex_str| {
+                U256::from_str_radix(&hex_str, 16)
+                    .map_err(|e| de::Error::custom(format!("u256 from string error: {}", e)))
+            })
+            .transpose()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,6 +20,33 @@ impl U64SerdeAsString {
     }
 }
 
+/// [`U64SerdeAsString`] for an optional value, a null or missing field is `None`.
+pub struct OptionU64SerdeAsString;
+
+impl OptionU64SerdeAsString {
+    pub fn serialize<S>(val: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match val {
+            Some(val) => U64SerdeAsString::serialize(val, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                u64::from_str(&s)
+                    .map_err(|e| de::Error::custom(format!("u64 from string error: {}", e)))
+            })
+            .transpose()
+    }
+}
+
 pub struct U64SerdeAsRadix16Prefix0xString;
 
 impl U64SerdeAsRadix16Prefix0xString {
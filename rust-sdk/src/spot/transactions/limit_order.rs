@@ -7,6 +7,7 @@ use crate::tx::sign::TxSignature;
 use crate::types::amount::AmountType;
 use crate::types::asset_id::AssetIdType;
 use crate::types::position_id::PositionIdType;
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use crate::{hash, HashType};
 use primitive_types::U256;
@@ -57,12 +58,30 @@ impl LimitOrder {
     }
 }
 
+impl Validate for LimitOrder {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount_buy, "amount_buy");
+        validator.positive(self.amount_sell, "amount_sell");
+    }
+}
+
+/// Sign a spot limit order, one that breaks any bound is refused.
 pub fn sign_limit_order(
     withdrawal: &LimitOrder,
     private_key: &str,
-) -> anyhow::Result<JubjubSignature> {
+) -> crate::error::Result<JubjubSignature> {
+    withdrawal.validate(None)?;
+    sign_limit_order_unchecked(withdrawal, private_key)
+}
+
+/// Same as [`sign_limit_order`] without the field range checks.
+pub fn sign_limit_order_unchecked(
+    withdrawal: &LimitOrder,
+    private_key: &str,
+) -> crate::error::Result<JubjubSignature> {
     let hash = withdrawal.hash();
-    let private_key = private_key_from_string(private_key).unwrap();
+    let private_key = private_key_from_string(private_key)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
 }
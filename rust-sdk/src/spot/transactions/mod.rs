@@ -3,6 +3,7 @@ mod transfer;
 mod withdrawal;
 
 pub use self::{
-    transfer::sign_transfer, transfer::transfer_hash, transfer::Transfer,
-    withdrawal::sign_withdrawal, withdrawal::Withdrawal,
+    transfer::sign_transfer, transfer::sign_transfer_unchecked, transfer::transfer_hash,
+    transfer::Transfer, withdrawal::sign_withdrawal, withdrawal::sign_withdrawal_unchecked,
+    withdrawal::Withdrawal,
 };
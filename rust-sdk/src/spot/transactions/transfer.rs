@@ -14,6 +14,7 @@ use crate::types::amount::AmountType;
 use crate::types::asset_id::AssetIdType;
 use crate::types::position_id::PositionIdType;
 use crate::types::HashType;
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use {
     crate::TransferBaseSerde,
@@ -72,9 +73,27 @@ pub fn transfer_hash(transfer: &Transfer) -> HashType {
     hasher.finalize()
 }
 
-pub fn sign_transfer(transfer: Transfer, private_key: &str) -> anyhow::Result<JubjubSignature> {
+impl Validate for Transfer {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "sender_public_key");
+        validator.public_key(&self.receiver_public_key, "receiver_public_key");
+        validator.positive(self.amount, "amount");
+    }
+}
+
+/// Sign a spot transfer, one that breaks any bound is refused.
+pub fn sign_transfer(transfer: Transfer, private_key: &str) -> crate::error::Result<JubjubSignature> {
+    transfer.validate(None)?;
+    sign_transfer_unchecked(transfer, private_key)
+}
+
+/// Same as [`sign_transfer`] without the field range checks.
+pub fn sign_transfer_unchecked(
+    transfer: Transfer,
+    private_key: &str,
+) -> crate::error::Result<JubjubSignature> {
     let hash = transfer_hash(&transfer);
-    let private_key = private_key_from_string(private_key).unwrap();
+    let private_key = private_key_from_string(private_key)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
 }
@@ -11,6 +11,7 @@ use crate::types::amount::AmountType;
 use crate::types::asset_id::AssetIdType;
 use crate::types::position_id::PositionIdType;
 use crate::types::HashType;
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
@@ -76,12 +77,29 @@ impl Withdrawal {
     }
 }
 
+impl Validate for Withdrawal {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount, "amount");
+    }
+}
+
+/// Sign a spot withdrawal, one that breaks any bound is refused.
 pub fn sign_withdrawal(
     withdrawal: &Withdrawal,
     private_key: &str,
-) -> anyhow::Result<JubjubSignature> {
+) -> crate::error::Result<JubjubSignature> {
+    withdrawal.validate(None)?;
+    sign_withdrawal_unchecked(withdrawal, private_key)
+}
+
+/// Same as [`sign_withdrawal`] without the field range checks.
+pub fn sign_withdrawal_unchecked(
+    withdrawal: &Withdrawal,
+    private_key: &str,
+) -> crate::error::Result<JubjubSignature> {
     let hash = withdrawal.hash();
-    let private_key = private_key_from_string(private_key).unwrap();
+    let private_key = private_key_from_string(private_key)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
 }
@@ -0,0 +1,132 @@
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::constant::ASSET_ID_UPPER_BOUND;
+use crate::error::{Result, ZkdexError};
+use crate::serde_wrapper::I128SerdeAsRadix16Prefix0xString;
+use crate::types::{AmountType, AssetIdType, BalanceType, PositionIdType};
+use crate::validate::{Validate, Validator};
+use crate::U64SerdeAsString;
+
+/// The deleverager takes over part of the synthetic position of the deleveraged position, which
+/// can no longer be liquidated, at a price set by the sequencer. Neither party signs it.
+///
+/// The json is the one the sequencer publishes: the fields in the order below, the amounts and
+/// positions as decimal strings and the asset as a hex string.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Deleverage {
+    #[serde(rename = "deleveraged_position_id", with = "U64SerdeAsString")]
+    pub deleveraged_position_id: PositionIdType,
+    #[serde(rename = "deleverager_position_id", with = "U64SerdeAsString")]
+    pub deleverager_position_id: PositionIdType,
+    #[serde(
+        rename = "synthetic_asset_id",
+        with = "I128SerdeAsRadix16Prefix0xString"
+    )]
+    pub synthetic_asset_id: AssetIdType,
+    #[serde(rename = "amount_collateral", with = "U64SerdeAsString")]
+    pub amount_collateral: AmountType,
+    #[serde(rename = "amount_synthetic", with = "U64SerdeAsString")]
+    pub amount_synthetic: AmountType,
+    #[serde(rename = "deleverager_is_buying_synthetic")]
+    pub deleverager_is_buying_synthetic: bool,
+}
+
+impl Validate for Deleverage {
+    fn check(&self, validator: &mut Validator) {
+        if self.deleverager_position_id == self.deleveraged_position_id {
+            validator.push(
+                "deleverager_position_id",
+                "must differ from deleveraged_position_id",
+            );
+        }
+        if self.synthetic_asset_id < 0 {
+            validator.push("synthetic_asset_id", "must not be negative");
+        } else {
+            validator.below(
+                self.synthetic_asset_id as u128,
+                U256::from(ASSET_ID_UPPER_BOUND as u128),
+                "synthetic_asset_id",
+            );
+        }
+        validator.positive(self.amount_collateral, "amount_collateral");
+        validator.positive(self.amount_synthetic, "amount_synthetic");
+    }
+}
+
+impl Deleverage {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| ZkdexError::InvalidTransaction(e.to_string()))
+    }
+
+    /// Check the fields and the synthetic balances of both positions before the deleverage, each
+    /// position must be reduced toward zero.
+    pub fn check_balances(
+        &self,
+        deleveraged_balance: BalanceType,
+        deleverager_balance: BalanceType,
+    ) -> Result<()> {
+        let mut validator = Validator::new(None);
+        self.check(&mut validator);
+        validator.reduces_position(
+            deleveraged_balance,
+            !self.deleverager_is_buying_synthetic,
+            self.amount_synthetic,
+            "deleveraged_position_id",
+        );
+        validator.reduces_position(
+            deleverager_balance,
+            self.deleverager_is_buying_synthetic,
+            self.amount_synthetic,
+            "deleverager_position_id",
+        );
+        validator.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DELEVERAGE: &str = r#"{"deleveraged_position_id":"7","deleverager_position_id":"8","synthetic_asset_id":"0x2","amount_collateral":"1000","amount_synthetic":"10","deleverager_is_buying_synthetic":true}"#;
+
+    #[test]
+    fn test_deleverage_serde() {
+        let tx: Deleverage = serde_json::from_str(DELEVERAGE).unwrap();
+        assert_eq!(tx.deleveraged_position_id, 7);
+        assert_eq!(tx.synthetic_asset_id, 2);
+        assert!(tx.validate(None).is_ok());
+        assert_eq!(tx.to_json().unwrap(), DELEVERAGE);
+    }
+
+    #[test]
+    fn test_deleverage_check_balances() {
+        let tx: Deleverage = serde_json::from_str(DELEVERAGE).unwrap();
+        // the deleverager buys from the long deleveraged position
+        assert!(tx.check_balances(10, -25).is_ok());
+        assert!(tx.check_balances(25, -10).is_ok());
+
+        let err = tx.check_balances(-10, -25).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert!(err
+            .to_string()
+            .contains("deleveraged_position_id: sells but the position is not long"));
+        let err = tx.check_balances(9, 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: deleveraged_position_id: trades more than the balance 9; \
+             deleverager_position_id: buys but the position is not short"
+        );
+
+        let mut sell = tx.clone();
+        sell.deleverager_is_buying_synthetic = false;
+        assert!(sell.check_balances(-10, 10).is_ok());
+
+        let mut same = tx;
+        same.deleverager_position_id = 7;
+        let err = same.check_balances(10, -10).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("deleverager_position_id: must differ from deleveraged_position_id"));
+    }
+}
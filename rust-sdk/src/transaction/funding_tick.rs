@@ -0,0 +1,208 @@
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::constant::{ASSET_ID_UPPER_BOUND, FUNDING_INDEX_LOWER_BOUND, FUNDING_INDEX_UPPER_BOUND};
+use crate::error::{Result, ZkdexError};
+use crate::fxp::{self, Fxp32};
+use crate::serde_utils::serde_str;
+use crate::serde_wrapper::I128SerdeAsRadix16Prefix0xString;
+use crate::types::{AssetIdType, BalanceType, IndexType, TimestampType};
+use crate::validate::{Validate, Validator};
+use crate::U32SerdeAsString;
+
+/// The funding index of a synthetic asset is the accumulated funding paid by a position holding
+/// one unit of it, in fixed point collateral (32.32). A position pays
+/// (index - cached_index) * balance when it is updated, cached_index being the index it last saw.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FundingIndex {
+    #[serde(rename = "asset_id", with = "I128SerdeAsRadix16Prefix0xString")]
+    pub asset_id: AssetIdType,
+    #[serde(rename = "funding_index", with = "serde_str")]
+    pub funding_index: IndexType,
+}
+
+/// The funding indices of every synthetic asset, sorted by asset_id, as of last_funding_tick.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FundingIndicesInfo {
+    #[serde(rename = "funding_indices")]
+    pub funding_indices: Vec<FundingIndex>,
+    #[serde(rename = "last_funding_tick", with = "U32SerdeAsString")]
+    pub last_funding_tick: TimestampType,
+}
+
+/// Moves the global funding indices forward, submitted by the sequencer and signed by nobody.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FundingTick {
+    #[serde(rename = "global_funding_indices")]
+    pub global_funding_indices: FundingIndicesInfo,
+}
+
+impl Validate for FundingIndex {
+    fn check(&self, validator: &mut Validator) {
+        if self.asset_id < 0 {
+            validator.push("asset_id", "must not be negative");
+        } else {
+            validator.below(
+                self.asset_id as u128,
+                U256::from(ASSET_ID_UPPER_BOUND as u128),
+                "asset_id",
+            );
+        }
+        if self.funding_index < FUNDING_INDEX_LOWER_BOUND
+            || self.funding_index >= FUNDING_INDEX_UPPER_BOUND as i128
+        {
+            validator.push(
+                "funding_index",
+                format!(
+                    "must be in [{}, {})",
+                    FUNDING_INDEX_LOWER_BOUND, FUNDING_INDEX_UPPER_BOUND
+                ),
+            );
+        }
+    }
+}
+
+impl Validate for FundingIndicesInfo {
+    fn check(&self, validator: &mut Validator) {
+        for (i, index) in self.funding_indices.iter().enumerate() {
+            let field = format!("funding_indices[{}]", i);
+            validator.nested(&field, index);
+            if i > 0 && self.funding_indices[i - 1].asset_id >= index.asset_id {
+                validator.push(&field, "asset_id must be above the previous one");
+            }
+        }
+    }
+}
+
+impl Validate for FundingTick {
+    fn check(&self, validator: &mut Validator) {
+        validator.nested("global_funding_indices", &self.global_funding_indices);
+    }
+}
+
+impl FundingIndicesInfo {
+    pub fn funding_index(&self, asset_id: AssetIdType) -> Option<IndexType> {
+        self.funding_indices
+            .binary_search_by_key(&asset_id, |index| index.asset_id)
+            .ok()
+            .map(|i| self.funding_indices[i].funding_index)
+    }
+
+    /// The funding paid by a position holding `balances` of synthetic assets from the `previous`
+    /// indices to these ones, in collateral. Positive when the position pays, rounded up so the
+    /// position never pays less nor receives more than the exact amount.
+    pub fn funding_payment(
+        &self,
+        previous: &FundingIndicesInfo,
+        balances: &[(AssetIdType, BalanceType)],
+    ) -> Result<i128> {
+        let mut total: Fxp32 = 0;
+        for &(asset_id, balance) in balances {
+            let index = |info: &FundingIndicesInfo| {
+                info.funding_index(asset_id).ok_or_else(|| {
+                    ZkdexError::InvalidArgument(format!(
+                        "no funding index for asset {:#x}",
+                        asset_id
+                    ))
+                })
+            };
+            total = asset_funding(balance, index(previous)?, index(self)?)
+                .and_then(|funding| total.checked_add(funding))
+                .ok_or_else(|| ZkdexError::InvalidArgument("funding overflows".to_string()))?;
+        }
+        Ok(fxp::ceil(total))
+    }
+}
+
+/// The funding of `balance` units of a synthetic as its index moves from `cached_index` to
+/// `index`, in fixed point collateral, positive when the position pays. `None` on overflow.
+pub fn asset_funding(
+    balance: BalanceType,
+    cached_index: IndexType,
+    index: IndexType,
+) -> Option<Fxp32> {
+    fxp::mul_int(index.checked_sub(cached_index)?, balance)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constant::FXP_32_ONE;
+
+    const FUNDING_TICK: &str = r#"{
+        "global_funding_indices": {
+            "funding_indices": [
+                {"asset_id": "0x2", "funding_index": "-4294967296"},
+                {"asset_id": "0x3", "funding_index": "6442450944"}
+            ],
+            "last_funding_tick": "1684832800"
+        }
+    }"#;
+
+    fn indices(indices: &[(AssetIdType, IndexType)]) -> FundingIndicesInfo {
+        FundingIndicesInfo {
+            funding_indices: indices
+                .iter()
+                .map(|&(asset_id, funding_index)| FundingIndex {
+                    asset_id,
+                    funding_index,
+                })
+                .collect(),
+            last_funding_tick: 0,
+        }
+    }
+
+    #[test]
+    fn test_funding_tick_serde() {
+        let tick: FundingTick = serde_json::from_str(FUNDING_TICK).unwrap();
+        let info = &tick.global_funding_indices;
+        assert_eq!(info.last_funding_tick, 1684832800);
+        assert_eq!(info.funding_index(2), Some(-(FXP_32_ONE as i128)));
+        assert_eq!(info.funding_index(4), None);
+        assert!(tick.validate(None).is_ok());
+
+        let json = serde_json::to_string(&tick).unwrap();
+        assert_eq!(serde_json::from_str::<FundingTick>(&json).unwrap(), tick);
+    }
+
+    #[test]
+    fn test_funding_tick_validate() {
+        let mut tick: FundingTick = serde_json::from_str(FUNDING_TICK).unwrap();
+        tick.global_funding_indices.funding_indices[1].asset_id = 2;
+        tick.global_funding_indices.funding_indices[0].funding_index =
+            FUNDING_INDEX_LOWER_BOUND - 1;
+        let err = tick.validate(None).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        let err = err.to_string();
+        assert!(err.contains("global_funding_indices.funding_indices[0].funding_index: must be in"));
+        assert!(err.contains(
+            "global_funding_indices.funding_indices[1]: asset_id must be above the previous one"
+        ));
+    }
+
+    #[test]
+    fn test_funding_payment() {
+        let one = FXP_32_ONE as i128;
+        let previous = indices(&[(2, 0), (3, 10 * one)]);
+        let current = indices(&[(2, one + one / 2), (3, 8 * one)]);
+
+        // a long of 2 pays 1.5 per unit, a short of 3 pays 2 per unit as the index falls
+        assert_eq!(current.funding_payment(&previous, &[(2, 2)]).unwrap(), 3);
+        assert_eq!(current.funding_payment(&previous, &[(3, -3)]).unwrap(), 6);
+        assert_eq!(current.funding_payment(&previous, &[(3, 3)]).unwrap(), -6);
+        assert_eq!(
+            current
+                .funding_payment(&previous, &[(2, 2), (3, 3)])
+                .unwrap(),
+            -3
+        );
+        // 1.5 is paid as 2, and -1.5 is received as 1
+        assert_eq!(current.funding_payment(&previous, &[(2, 1)]).unwrap(), 2);
+        assert_eq!(current.funding_payment(&previous, &[(2, -1)]).unwrap(), -1);
+        assert_eq!(current.funding_payment(&current, &[(2, 5)]).unwrap(), 0);
+
+        let err = current.funding_payment(&previous, &[(4, 1)]).unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        assert_eq!(asset_funding(i64::MAX, i128::MIN, i128::MAX), None);
+    }
+}
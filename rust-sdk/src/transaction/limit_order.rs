@@ -1,10 +1,11 @@
 use std::ops::ShlAssign;
 
-use anyhow::Result;
+use crate::error::Result;
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
 use crate::common::OrderBase;
+use crate::constant::{ASSET_ID_UPPER_BOUND, COLLATERAL_ASSET_ID_UPPER_BOUND};
 use crate::felt::LeBytesConvert;
 use crate::hash;
 use crate::hash::Hasher;
@@ -14,6 +15,7 @@ use crate::serde_wrapper::U64SerdeAsString;
 use crate::tx::packed_public_key::private_key_from_string;
 use crate::tx::sign::TxSignature;
 use crate::types::{AmountType, AssetIdType, CollateralAssetId, HashType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 
 const LIMIT_ORDER_WITH_FEES: u64 = 3;
@@ -44,7 +46,36 @@ pub struct LimitOrderRequest {
     pub is_buying_synthetic: bool,
 }
 
+impl Validate for LimitOrderRequest {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount_synthetic, "amount_synthetic");
+        validator.positive(self.amount_collateral, "amount_collateral");
+        if self.asset_id_synthetic < 0 {
+            validator.push("asset_id_synthetic", "must not be negative");
+        } else {
+            validator.below(
+                self.asset_id_synthetic as u128,
+                U256::from(ASSET_ID_UPPER_BOUND as u128),
+                "asset_id_synthetic",
+            );
+        }
+        validator.below(
+            self.asset_id_collateral,
+            COLLATERAL_ASSET_ID_UPPER_BOUND,
+            "asset_id_collateral",
+        );
+    }
+}
+
+/// Sign a limit order, one that breaks any bound is refused.
 pub fn sign_limit_order(req: LimitOrderRequest, prvk: &str) -> Result<JubjubSignature> {
+    req.validate(None)?;
+    sign_limit_order_unchecked(req, prvk)
+}
+
+/// Same as [`sign_limit_order`] without the field range checks.
+pub fn sign_limit_order_unchecked(req: LimitOrderRequest, prvk: &str) -> Result<JubjubSignature> {
     let hash = hash_limit_order(req);
     let private_key = private_key_from_string(prvk)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
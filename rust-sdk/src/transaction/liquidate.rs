@@ -1,5 +1,6 @@
 use crate::transaction::limit_order::LimitOrderRequest;
 use crate::types::{AmountType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use crate::U64SerdeAsString;
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +20,14 @@ pub struct Liquidate {
     pub actual_liquidator_fee: AmountType,
 }
 
+impl Validate for Liquidate {
+    fn check(&self, validator: &mut Validator) {
+        validator.nested("liquidator_order", &self.liquidator_order);
+        validator.positive(self.actual_collateral, "actual_collateral");
+        validator.positive(self.actual_synthetic, "actual_synthetic");
+    }
+}
+
 #[test]
 fn test_deserialize() {
     let json = r#"
@@ -1,3 +1,5 @@
+pub mod deleverage;
+pub mod funding_tick;
 pub mod limit_order;
 pub mod liquidate;
 pub mod oracle_price;
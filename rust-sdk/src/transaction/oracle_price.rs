@@ -4,21 +4,37 @@
 // divided by the ratio between a unit of synthetic asset and its resolution:
 //   (collateral_asset_unit / collateral_resolution) /
 //   (synthetic_asset_unit / synthetic_resolution).
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ops::ShlAssign;
+
 use crate::i128_serde::U128SerdeAsString;
 use crate::U32SerdeAsString;
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
-use std::ops::ShlAssign;
 
+use crate::constant::{
+    ASSET_ID_UPPER_BOUND, ASSET_RESOLUTION_LOWER_BOUND, EXTERNAL_PRICE_FIXED_POINT_UNIT,
+    EXTERNAL_PRICE_UPPER_BOUND, FXP_32_ONE, ORACLE_PRICE_QUORUM_LOWER_BOUND,
+    ORACLE_PRICE_QUORUM_UPPER_BOUND, PRICE_LOWER_BOUND, PRICE_UPPER_BOUND, SIGNED_MESSAGE_BOUND,
+};
+use crate::error::{Result, ZkdexError};
 use crate::felt::LeBytesConvert;
 use crate::hash::hash2;
-use crate::tx::packed_public_key::private_key_from_string;
+use crate::serde_wrapper::I128SerdeAsRadix16Prefix0xString;
+use crate::tx::batch_verify::{BatchVerifier, ParsedSignature};
+use crate::tx::packed_public_key::{private_key_from_string, PackedPublicKey};
+use crate::tx::packed_signature::{PackedSignature, SignatureSerde};
 use crate::tx::public_key_type::PublicKeyType;
 use crate::tx::sign::TxSignature;
-use crate::types::{AssetIdType, HashType, PriceType, SignedAssetId, TimestampType};
+use crate::tx::verifier::VERIFIER;
+use crate::types::{
+    AssetIdType, HashType, OraclePriceQuorumType, PriceType, ResolutionType, SignedAssetId,
+    TimestampType,
+};
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use crate::U256SerdeAsRadix16Prefix0xString;
-use anyhow::Result;
 
 // Represents a single signature on an external price with a timestamp.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -44,16 +60,51 @@ impl Default for SignedOraclePrice {
     }
 }
 
+// A SignedOraclePrice as carried by an oracle prices tick, together with the signature of its
+// signer on signed_oracle_price_hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OraclePriceSignature {
+    #[serde(flatten)]
+    pub price: SignedOraclePrice,
+    #[serde(rename = "signature", with = "SignatureSerde")]
+    pub signature: JubjubSignature,
+}
+
 // Represents a single Oracle Price of an asset in internal representation and
 // signatures on that price. The price is a median of all prices in the signatures.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct AssetOraclePrice {
+    #[serde(rename = "asset_id", with = "I128SerdeAsRadix16Prefix0xString")]
     pub asset_id: AssetIdType,
+    #[serde(rename = "price", with = "U128SerdeAsString")]
     pub price: PriceType,
     // Oracle signatures, sorted by signer_key.
-    pub signed_prices: Vec<SignedOraclePrice>,
+    #[serde(rename = "signed_prices")]
+    pub signed_prices: Vec<OraclePriceSignature>,
+}
+
+// Updates the oracle prices of some synthetic assets, sorted by asset_id. Submitted by the
+// oracle relayer, every price is signed by a quorum of the oracles of its asset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OraclePricesTick {
+    #[serde(rename = "oracle_prices")]
+    pub oracle_prices: Vec<AssetOraclePrice>,
+    #[serde(rename = "timestamp", with = "U32SerdeAsString")]
+    pub timestamp: TimestampType,
+}
+
+// The oracle settings of a synthetic asset in the exchange config: its prices are signed by
+// `signers` under one of `signed_asset_ids`, and a tick needs `quorum` of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetOracleConfig {
+    pub resolution: ResolutionType,
+    pub quorum: OraclePriceQuorumType,
+    pub signed_asset_ids: Vec<SignedAssetId>,
+    pub signers: Vec<PublicKeyType>,
 }
 
+// The signed prices of a tick must be signed within [min_time, max_time].
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TimeBounds {
     pub min_time: TimestampType,
     pub max_time: TimestampType,
@@ -62,6 +113,18 @@ pub struct TimeBounds {
 // const TIMESTAMP_BOUND = 2 ** 32;
 pub const TIMESTAMP_BOUND: i64 = 1 << 32;
 
+impl Validate for SignedOraclePrice {
+    fn check(&self, validator: &mut Validator) {
+        validator.public_key(&self.signer_key, "signer_key");
+        validator.below(
+            self.external_price,
+            U256::from(EXTERNAL_PRICE_UPPER_BOUND),
+            "external_price",
+        );
+        validator.below(self.signed_asset_id, SIGNED_MESSAGE_BOUND, "signed_asset_id");
+    }
+}
+
 pub fn signed_oracle_price_hash(price: &SignedOraclePrice) -> HashType {
     let mut y = U256::from(price.external_price);
     y.shl_assign(32);
@@ -69,13 +132,269 @@ pub fn signed_oracle_price_hash(price: &SignedOraclePrice) -> HashType {
     hash2(&price.signed_asset_id, &y)
 }
 
+/// Sign an oracle price, one that breaks any bound is refused.
 pub fn sign_signed_oracle_price(price: SignedOraclePrice, prvk: &str) -> Result<JubjubSignature> {
+    price.validate(None)?;
+    sign_signed_oracle_price_unchecked(price, prvk)
+}
+
+/// Same as [`sign_signed_oracle_price`] without the field range checks.
+pub fn sign_signed_oracle_price_unchecked(
+    price: SignedOraclePrice,
+    prvk: &str,
+) -> Result<JubjubSignature> {
     let hash = signed_oracle_price_hash(&price);
     let private_key = private_key_from_string(prvk)?;
     let (signature, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(signature.into())
 }
 
+impl OraclePriceSignature {
+    /// Whether `signature` is the signature of `signer_key` on the price.
+    pub fn verify(&self) -> Result<bool> {
+        let signature = PackedSignature::try_from(&self.signature)?;
+        let hash = signed_oracle_price_hash(&self.price);
+        VERIFIER.verify(
+            &PackedPublicKey(self.price.signer_key.0),
+            &signature,
+            hash.as_le_bytes(),
+        )
+    }
+
+    fn parse(&self) -> Result<ParsedSignature> {
+        let signature = PackedSignature::try_from(&self.signature)?;
+        let public_key = PackedPublicKey(self.price.signer_key.0).try_into_public_key()?;
+        Ok((public_key, signed_oracle_price_hash(&self.price), signature))
+    }
+}
+
+/// Whether each signed price verifies, the whole quorum is checked in one [`BatchVerifier`]
+/// batch, bisected down to the signatures that fail when it does not hold. A quorum of at most
+/// [`SINGLE_VERIFY_MAX`] is verified one by one. A signature or signer key that does not parse is
+/// invalid.
+///
+/// [`SINGLE_VERIFY_MAX`]: crate::tx::batch_verify::SINGLE_VERIFY_MAX
+fn verify_signed_prices(signed_prices: &[OraclePriceSignature]) -> Vec<bool> {
+    let parsed: Vec<_> = signed_prices
+        .iter()
+        .map(OraclePriceSignature::parse)
+        .collect();
+    BatchVerifier::verify_parsed(&parsed)
+}
+
+impl Validate for AssetOraclePrice {
+    fn check(&self, validator: &mut Validator) {
+        if self.asset_id < 0 {
+            validator.push("asset_id", "must not be negative");
+        } else {
+            validator.below(
+                self.asset_id as u128,
+                U256::from(ASSET_ID_UPPER_BOUND as u128),
+                "asset_id",
+            );
+        }
+        if self.price < PRICE_LOWER_BOUND || self.price >= PRICE_UPPER_BOUND {
+            validator.push(
+                "price",
+                format!("must be in [{}, {})", PRICE_LOWER_BOUND, PRICE_UPPER_BOUND),
+            );
+        }
+        if self.signed_prices.is_empty() {
+            validator.push("signed_prices", "must not be empty");
+        }
+        for (i, signed) in self.signed_prices.iter().enumerate() {
+            let field = format!("signed_prices[{}]", i);
+            validator.nested(&field, &signed.price);
+            if i > 0 && self.signed_prices[i - 1].price.signer_key >= signed.price.signer_key {
+                validator.push(&field, "signer_key must be above the previous one");
+            }
+        }
+    }
+}
+
+impl Validate for OraclePricesTick {
+    fn check(&self, validator: &mut Validator) {
+        for (i, price) in self.oracle_prices.iter().enumerate() {
+            let field = format!("oracle_prices[{}]", i);
+            validator.nested(&field, price);
+            if i > 0 && self.oracle_prices[i - 1].asset_id >= price.asset_id {
+                validator.push(&field, "asset_id must be above the previous one");
+            }
+        }
+    }
+}
+
+impl AssetOraclePrice {
+    /// The median of the signed external prices, the two middle ones averaged and rounded down
+    /// for an even count. `None` without signed prices.
+    pub fn median_external_price(&self) -> Option<PriceType> {
+        let mut prices: Vec<PriceType> = self
+            .signed_prices
+            .iter()
+            .map(|signed| signed.price.external_price)
+            .collect();
+        prices.sort_unstable();
+        let mid = prices.len() / 2;
+        match prices.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(prices[mid]),
+            _ => Some(prices[mid - 1] + (prices[mid] - prices[mid - 1]) / 2),
+        }
+    }
+}
+
+impl AssetOracleConfig {
+    fn ensure_bounds(&self) -> Result<()> {
+        if self.quorum < ORACLE_PRICE_QUORUM_LOWER_BOUND
+            || self.quorum >= ORACLE_PRICE_QUORUM_UPPER_BOUND
+        {
+            return Err(ZkdexError::InvalidArgument(format!(
+                "oracle price quorum {} is not in [{}, {})",
+                self.quorum, ORACLE_PRICE_QUORUM_LOWER_BOUND, ORACLE_PRICE_QUORUM_UPPER_BOUND
+            )));
+        }
+        if self.resolution < ASSET_RESOLUTION_LOWER_BOUND {
+            return Err(ZkdexError::InvalidArgument(
+                "synthetic resolution must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TimeBounds {
+    pub fn contains(&self, timestamp: TimestampType) -> bool {
+        self.min_time <= timestamp && timestamp <= self.max_time
+    }
+}
+
+impl OraclePricesTick {
+    /// Check the tick before it is submitted: every price is signed by distinct oracles of its
+    /// asset, sorted by signer_key, at least as many as the quorum, within `time_bounds`, and the
+    /// price is the internal price of the median of the signed ones.
+    ///
+    /// `configs` holds the oracle config of every synthetic asset. A config breaking its bounds
+    /// is an invalid argument, a tick breaking any check is reported with every invalid field.
+    pub fn verify(
+        &self,
+        configs: &HashMap<AssetIdType, AssetOracleConfig>,
+        collateral_resolution: ResolutionType,
+        time_bounds: &TimeBounds,
+    ) -> Result<()> {
+        if collateral_resolution < ASSET_RESOLUTION_LOWER_BOUND {
+            return Err(ZkdexError::InvalidArgument(
+                "collateral resolution must be positive".to_string(),
+            ));
+        }
+        let mut validator = Validator::new(None);
+        self.check(&mut validator);
+        for (i, price) in self.oracle_prices.iter().enumerate() {
+            let field = format!("oracle_prices[{}]", i);
+            match configs.get(&price.asset_id) {
+                Some(config) => {
+                    config.ensure_bounds()?;
+                    let check = AssetOracleCheck {
+                        price,
+                        config,
+                        collateral_resolution,
+                        time_bounds,
+                    };
+                    validator.nested(&field, &check);
+                }
+                None => validator.push(&format!("{}.asset_id", field), "has no oracle config"),
+            }
+        }
+        validator.finish()
+    }
+}
+
+// The checks of an asset price against the config of the asset, on top of its own bounds.
+struct AssetOracleCheck<'a> {
+    price: &'a AssetOraclePrice,
+    config: &'a AssetOracleConfig,
+    collateral_resolution: ResolutionType,
+    time_bounds: &'a TimeBounds,
+}
+
+impl Validate for AssetOracleCheck<'_> {
+    fn check(&self, validator: &mut Validator) {
+        let signed_prices = &self.price.signed_prices;
+        if (signed_prices.len() as u64) < self.config.quorum {
+            validator.push(
+                "signed_prices",
+                format!(
+                    "has {} prices, the quorum is {}",
+                    signed_prices.len(),
+                    self.config.quorum
+                ),
+            );
+        }
+        let signatures_valid = verify_signed_prices(signed_prices);
+        for (i, signed) in signed_prices.iter().enumerate() {
+            let field = |name: &str| format!("signed_prices[{}].{}", i, name);
+            if !self.config.signers.contains(&signed.price.signer_key) {
+                validator.push(&field("signer_key"), "is not an oracle of the asset");
+            }
+            if !self
+                .config
+                .signed_asset_ids
+                .contains(&signed.price.signed_asset_id)
+            {
+                validator.push(
+                    &field("signed_asset_id"),
+                    "is not an oracle id of the asset",
+                );
+            }
+            if !self.time_bounds.contains(signed.price.timestamp) {
+                validator.push(
+                    &field("timestamp"),
+                    format!(
+                        "must be in [{}, {}]",
+                        self.time_bounds.min_time, self.time_bounds.max_time
+                    ),
+                );
+            }
+            if !signatures_valid[i] {
+                validator.push(&field("signature"), "does not verify");
+            }
+        }
+        if let Some(median) = self.price.median_external_price() {
+            match internal_price(median, self.collateral_resolution, self.config.resolution) {
+                Some(price) if price == self.price.price => {}
+                Some(price) => validator.push(
+                    "price",
+                    format!("must be {}, the median of the signed prices", price),
+                ),
+                None => validator.push(
+                    "price",
+                    format!("the median {} is out of the price bounds", median),
+                ),
+            }
+        }
+    }
+}
+
+/// The internal price of `external_price`, see the price definitions on top of this file, rounded
+/// down. `None` when it is not in [PRICE_LOWER_BOUND, PRICE_UPPER_BOUND) or a resolution is zero.
+pub fn internal_price(
+    external_price: PriceType,
+    collateral_resolution: ResolutionType,
+    synthetic_resolution: ResolutionType,
+) -> Option<PriceType> {
+    let denominator =
+        U256::from(synthetic_resolution) * U256::from(EXTERNAL_PRICE_FIXED_POINT_UNIT);
+    if denominator.is_zero() {
+        return None;
+    }
+    let price =
+        U256::from(external_price) * U256::from(FXP_32_ONE) * U256::from(collateral_resolution)
+            / denominator;
+    if price < U256::from(PRICE_LOWER_BOUND) || price >= U256::from(PRICE_UPPER_BOUND) {
+        return None;
+    }
+    Some(price.as_u128())
+}
+
 #[test]
 fn test_deserialize() {
     let json = r#"
@@ -99,8 +418,218 @@ fn test_oracle() {
     data.external_price = 100000;
     data.timestamp = 18778987;
     data.signed_asset_id = SignedAssetId::from(100);
-    let sig = sign_signed_oracle_price(data, pri).unwrap();
+    let sig = sign_signed_oracle_price_unchecked(data, pri).unwrap();
     let json = serde_json::to_string(&sig).unwrap();
     println!("{:#?}", json);
     println!("{:#?}", sig);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tx::batch_verify::SINGLE_VERIFY_MAX;
+    use crate::tx::packed_public_key::public_key_from_private;
+
+    const BTC: AssetIdType = 2;
+    // "BTCUSDOKX" and the oracle name, as signed in lib::test::test_sign_oracle_price
+    const BTC_SIGNED_ASSET_ID: &str = "425443555344434f4b580000000000005374437277";
+    // Sorted by their public keys 0x0d4a.., 0x15d1.., 0x8af4...
+    const ORACLES: [&str; 3] = [
+        "01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe",
+        "060a45bcd72c9e3c82bc1c57f63ad15b25f56bb13ce01d15fd4ab3f8f2de35bb",
+        "0376204fa0b554ee3d8a03c6ccdb73f7b98d1965fbeaa3a9f88723669a23893f",
+    ];
+    const EXTERNAL_PRICES: [PriceType; 3] = [
+        28_420_500_000_000_000_000_000,
+        28_409_392_522_000_000_000_000,
+        28_400_000_000_000_000_000_000,
+    ];
+    const TIMESTAMP: TimestampType = 1693907824;
+    const COLLATERAL_RESOLUTION: ResolutionType = 1_000_000;
+    const BTC_RESOLUTION: ResolutionType = 10_000_000_000;
+
+    fn signer_key(oracle: &str) -> PublicKeyType {
+        public_key_from_private(&private_key_from_string(oracle).unwrap()).into()
+    }
+
+    fn signed_price(oracle: &str, external_price: PriceType) -> OraclePriceSignature {
+        let price = SignedOraclePrice {
+            signer_key: signer_key(oracle),
+            external_price,
+            timestamp: TIMESTAMP,
+            signed_asset_id: U256::from_str_radix(BTC_SIGNED_ASSET_ID, 16).unwrap(),
+        };
+        let signature = sign_signed_oracle_price(price.clone(), oracle).unwrap();
+        OraclePriceSignature { price, signature }
+    }
+
+    fn tick() -> OraclePricesTick {
+        OraclePricesTick {
+            oracle_prices: vec![AssetOraclePrice {
+                asset_id: BTC,
+                // 28409.392522 usd for 10^10 units of btc, in 10^6 units of usd, in 32.32
+                price: 12201741178,
+                signed_prices: ORACLES
+                    .iter()
+                    .zip(EXTERNAL_PRICES.iter())
+                    .map(|(oracle, &price)| signed_price(oracle, price))
+                    .collect(),
+            }],
+            timestamp: TIMESTAMP + 10,
+        }
+    }
+
+    fn configs(quorum: OraclePriceQuorumType) -> HashMap<AssetIdType, AssetOracleConfig> {
+        let config = AssetOracleConfig {
+            resolution: BTC_RESOLUTION,
+            quorum,
+            signed_asset_ids: vec![U256::from_str_radix(BTC_SIGNED_ASSET_ID, 16).unwrap()],
+            signers: ORACLES.iter().map(|oracle| signer_key(oracle)).collect(),
+        };
+        vec![(BTC, config)].into_iter().collect()
+    }
+
+    fn time_bounds() -> TimeBounds {
+        TimeBounds {
+            min_time: TIMESTAMP - 60,
+            max_time: TIMESTAMP + 10,
+        }
+    }
+
+    #[test]
+    fn test_oracle_prices_tick() {
+        let tick = tick();
+        assert!(tick.validate(None).is_ok());
+        assert!(tick
+            .verify(&configs(3), COLLATERAL_RESOLUTION, &time_bounds())
+            .is_ok());
+        assert_eq!(
+            tick.oracle_prices[0].median_external_price(),
+            Some(EXTERNAL_PRICES[1])
+        );
+
+        let json = serde_json::to_string(&tick).unwrap();
+        assert!(json.contains(r#""signature":{"r":"#));
+        let parsed: OraclePricesTick = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, tick);
+    }
+
+    #[test]
+    fn test_serialize_tick_with_r_off_curve() {
+        let mut tick = tick();
+        let sig_r = &mut tick.oracle_prices[0].signed_prices[1].signature.sig_r;
+        sig_r.y += U256::one();
+        let err = serde_json::to_string(&tick).unwrap_err();
+        assert_eq!(err.to_string(), "r is not on the curve");
+    }
+
+    #[test]
+    fn test_oracle_prices_tick_verify() {
+        let bounds = time_bounds();
+        let mut tick = tick();
+        let prices = &mut tick.oracle_prices[0];
+        prices.signed_prices.swap(0, 1);
+        prices.signed_prices[0].price.external_price += 1;
+        prices.signed_prices[2].price.timestamp = bounds.max_time + 1;
+        let err = tick
+            .verify(&configs(4), COLLATERAL_RESOLUTION, &bounds)
+            .unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: oracle_prices[0].signed_prices[1]: signer_key must be above the \
+             previous one; oracle_prices[0].signed_prices: has 3 prices, the quorum is 4; \
+             oracle_prices[0].signed_prices[0].signature: does not verify; \
+             oracle_prices[0].signed_prices[2].timestamp: must be in [1693907764, 1693907834]; \
+             oracle_prices[0].signed_prices[2].signature: does not verify"
+        );
+
+        let mut tick = self::tick();
+        tick.oracle_prices[0].price += 1;
+        let err = tick
+            .verify(&configs(3), COLLATERAL_RESOLUTION, &bounds)
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "oracle_prices[0].price: must be 12201741178, the median of the signed prices"
+        ));
+
+        let mut configs = configs(3);
+        configs.get_mut(&BTC).unwrap().signers.pop();
+        configs.get_mut(&BTC).unwrap().signed_asset_ids[0] = U256::one();
+        let err = self::tick()
+            .verify(&configs, COLLATERAL_RESOLUTION, &bounds)
+            .unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("signed_prices[2].signer_key: is not an oracle of the asset"));
+        assert!(err.contains("signed_prices[0].signed_asset_id: is not an oracle id of the asset"));
+
+        let err = self::tick()
+            .verify(&HashMap::new(), COLLATERAL_RESOLUTION, &bounds)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("oracle_prices[0].asset_id: has no oracle config"));
+        let err = self::tick()
+            .verify(&self::configs(0), COLLATERAL_RESOLUTION, &bounds)
+            .unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_large_quorum_names_forged_signer() {
+        let quorum = SINGLE_VERIFY_MAX + 2;
+        let mut oracles: Vec<_> = (0..quorum)
+            .map(|_| crate::generate_private_key().unwrap().private_key)
+            .collect();
+        oracles.sort_by_key(|oracle| signer_key(oracle).0);
+        let mut tick = tick();
+        tick.oracle_prices[0].signed_prices = oracles
+            .iter()
+            .map(|oracle| signed_price(oracle, EXTERNAL_PRICES[1]))
+            .collect();
+        let mut configs = configs(quorum as OraclePriceQuorumType);
+        configs.get_mut(&BTC).unwrap().signers =
+            oracles.iter().map(|oracle| signer_key(oracle)).collect();
+        assert!(tick
+            .verify(&configs, COLLATERAL_RESOLUTION, &time_bounds())
+            .is_ok());
+
+        // a valid signature of another oracle's price
+        let signed_prices = &mut tick.oracle_prices[0].signed_prices;
+        signed_prices[4].signature = signed_prices[3].signature.clone();
+        let err = tick
+            .verify(&configs, COLLATERAL_RESOLUTION, &time_bounds())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: oracle_prices[0].signed_prices[4].signature: does not verify"
+        );
+    }
+
+    #[test]
+    fn test_internal_price() {
+        let mut prices = tick().oracle_prices.remove(0);
+        prices.signed_prices.remove(0);
+        // the average of 28409.392522 and 28400
+        assert_eq!(
+            prices.median_external_price(),
+            Some(28_404_696_261_000_000_000_000)
+        );
+        assert_eq!(
+            internal_price(
+                28_404_696_261_000_000_000_000,
+                COLLATERAL_RESOLUTION,
+                BTC_RESOLUTION
+            ),
+            Some(12199724149)
+        );
+        prices.signed_prices.clear();
+        assert_eq!(prices.median_external_price(), None);
+
+        let one = EXTERNAL_PRICE_FIXED_POINT_UNIT as PriceType;
+        assert_eq!(internal_price(one, 1, 1), Some(FXP_32_ONE as PriceType));
+        assert_eq!(internal_price(one, 1, 0), None);
+        assert_eq!(internal_price(one, 1 << 32, 1), None);
+        assert_eq!(internal_price(1, 1, 1), None);
+    }
+}
@@ -1,17 +1,24 @@
 use crate::hash::Hasher;
-use anyhow::Result;
+use crate::error::Result;
+use ethers::types::Address;
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use std::ops::ShlAssign;
 
 use crate::common::OrderBase;
+use crate::constant::{AMOUNT_UPPER_BOUND_U256, COLLATERAL_ASSET_ID_UPPER_BOUND};
 use crate::common::{CONDITIONAL_TRANSFER_ORDER_TYPE, TRANSFER_ORDER_TYPE};
 use crate::felt::LeBytesConvert;
-use crate::serde_wrapper::U256SerdeAsRadix16Prefix0xString;
+use crate::serde_wrapper::{
+    OptionU256SerdeAsRadix16Prefix0xString, OptionU64SerdeAsString,
+    U256SerdeAsRadix16Prefix0xString,
+};
 use crate::tx::packed_public_key::private_key_from_string;
 use crate::tx::public_key_type::PublicKeyType;
 use crate::tx::sign::TxSignature;
-use crate::types::{AmountType, CollateralAssetId, HashType, PositionIdType};
+use crate::types::{AmountType, CollateralAssetId, ConditionType, HashType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use crate::{hash, U64SerdeAsString};
 
@@ -29,11 +36,127 @@ pub struct Transfer {
     pub amount: AmountType,
     #[serde(rename = "asset_id", with = "U256SerdeAsRadix16Prefix0xString")]
     pub asset_id: CollateralAssetId,
+    #[serde(flatten)]
+    pub fee: TransferFee,
+}
+
+/// The fee of a transfer, at most `fee` of `fee_asset_id` taken from `fee_position_id`, flat in
+/// the transfer like the unified one. The fields are optional, without them the transfer is hashed
+/// with a zero fee paid from the sender position.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TransferFee {
+    #[serde(rename = "fee", default, with = "U64SerdeAsString")]
+    pub fee: AmountType,
+    /// Set with a fee, along with `fee_position_id`.
+    #[serde(
+        rename = "fee_asset_id",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "OptionU256SerdeAsRadix16Prefix0xString"
+    )]
+    pub fee_asset_id: Option<CollateralAssetId>,
+    #[serde(
+        rename = "fee_position_id",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "OptionU64SerdeAsString"
+    )]
+    pub fee_position_id: Option<PositionIdType>,
+}
+
+impl Validate for Transfer {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount, "amount");
+        validator.below(self.amount, AMOUNT_UPPER_BOUND_U256, "amount");
+        validator.below(self.asset_id, COLLATERAL_ASSET_ID_UPPER_BOUND, "asset_id");
+        self.fee.check(validator);
+    }
+}
+
+impl Validate for TransferFee {
+    fn check(&self, validator: &mut Validator) {
+        validator.below(self.fee, AMOUNT_UPPER_BOUND_U256, "fee");
+        if let Some(asset_id) = self.fee_asset_id {
+            validator.below(asset_id, COLLATERAL_ASSET_ID_UPPER_BOUND, "fee_asset_id");
+        }
+        if self.fee != 0 {
+            if self.fee_asset_id.is_none() {
+                validator.push("fee_asset_id", "must be set with a fee");
+            }
+            if self.fee_position_id.is_none() {
+                validator.push("fee_position_id", "must be set with a fee");
+            }
+        }
+    }
 }
 
+/// Sign a transfer, one that breaks any bound is refused.
 pub fn sign_transfer(transfer: Transfer, private_key: &str) -> Result<JubjubSignature> {
-    let hash = transfer_hash(&transfer, 0);
-    let private_key = private_key_from_string(private_key).unwrap();
+    transfer.validate(None)?;
+    sign_transfer_unchecked(transfer, private_key)
+}
+
+/// Same as [`sign_transfer`] without the field range checks.
+pub fn sign_transfer_unchecked(transfer: Transfer, private_key: &str) -> Result<JubjubSignature> {
+    let hash = transfer_hash(&transfer, ConditionType::zero());
+    let private_key = private_key_from_string(private_key)?;
+    let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
+    Ok(sig.into())
+}
+
+/// A transfer that is only executed once `fact` is registered in the L1 fact registry at
+/// `fact_registry_address`, used by fast withdrawals.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConditionalTransfer {
+    #[serde(flatten)]
+    pub transfer: Transfer,
+    #[serde(rename = "fact_registry_address")]
+    pub fact_registry_address: Address,
+    #[serde(rename = "fact", with = "U256SerdeAsRadix16Prefix0xString")]
+    pub fact: U256,
+}
+
+impl ConditionalTransfer {
+    /// keccak256(fact_registry_address || fact) with the top 6 bits cleared, so it is a field
+    /// element.
+    pub fn condition(&self) -> ConditionType {
+        let mut fact = [0u8; 32];
+        self.fact.to_big_endian(&mut fact);
+        let mut hasher = Keccak256::new();
+        hasher.update(self.fact_registry_address.as_bytes());
+        hasher.update(fact);
+        let digest = U256::from_big_endian(hasher.finalize().as_slice());
+        digest & ((U256::one() << 250) - 1)
+    }
+
+    pub fn hash(&self) -> HashType {
+        transfer_hash(&self.transfer, self.condition())
+    }
+}
+
+impl Validate for ConditionalTransfer {
+    fn check(&self, validator: &mut Validator) {
+        self.transfer.check(validator);
+    }
+}
+
+/// Sign a conditional transfer, one that breaks any bound is refused.
+pub fn sign_conditional_transfer(
+    transfer: ConditionalTransfer,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    transfer.validate(None)?;
+    sign_conditional_transfer_unchecked(transfer, private_key)
+}
+
+/// Same as [`sign_conditional_transfer`] without the field range checks.
+pub fn sign_conditional_transfer_unchecked(
+    transfer: ConditionalTransfer,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let hash = transfer.hash();
+    let private_key = private_key_from_string(private_key)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
 }
@@ -48,11 +171,11 @@ pub struct ExchangeTransfer {
     pub amount: AmountType,
     pub asset_id: CollateralAssetId,
     pub src_fee_vault_id: PositionIdType,
-    pub asset_id_fee: AmountType,
+    pub asset_id_fee: CollateralAssetId,
     pub max_amount_fee: AmountType,
 }
 
-pub fn transfer_hash(transfer: &Transfer, condition: u64) -> HashType {
+pub fn transfer_hash(transfer: &Transfer, condition: ConditionType) -> HashType {
     let mut exchange_transfer = ExchangeTransfer::default();
     exchange_transfer.base = transfer.base.clone();
     exchange_transfer.sender_vault_id = transfer.sender_position_id;
@@ -60,14 +183,15 @@ pub fn transfer_hash(transfer: &Transfer, condition: u64) -> HashType {
     exchange_transfer.receiver_vault_id = transfer.receiver_position_id;
     exchange_transfer.amount = transfer.amount;
     exchange_transfer.asset_id = transfer.asset_id;
-    exchange_transfer.src_fee_vault_id = transfer.sender_position_id;
-    exchange_transfer.asset_id_fee = 0;
-    exchange_transfer.max_amount_fee = 0;
+    let fee = &transfer.fee;
+    exchange_transfer.src_fee_vault_id = fee.fee_position_id.unwrap_or(transfer.sender_position_id);
+    exchange_transfer.asset_id_fee = fee.fee_asset_id.unwrap_or_default();
+    exchange_transfer.max_amount_fee = fee.fee;
 
     return exchange_transfer.hash(condition);
 }
 
-pub fn transfer_hash_internal(transfer: &ExchangeTransfer, condition: u64) -> HashType {
+pub fn transfer_hash_internal(transfer: &ExchangeTransfer, condition: ConditionType) -> HashType {
     let mut hasher = hash::new_hasher();
     // let (msg) = hash2{hash_ptr=pedersen_ptr}(x=transfer.asset_id, y=transfer.asset_id_fee);
     // let (msg) = hash2{hash_ptr=pedersen_ptr}(x=msg, y=transfer.receiver_public_key);
@@ -76,7 +200,7 @@ pub fn transfer_hash_internal(transfer: &ExchangeTransfer, condition: u64) -> Ha
     hasher.update_single(&transfer.asset_id_fee);
 
     // Add condition to the signature hash if exists.
-    if condition != 0 {
+    if !condition.is_zero() {
         // let (msg) = hash2{hash_ptr=pedersen_ptr}(x=msg, y=condition);
         hasher.update_single(&condition);
     }
@@ -109,7 +233,7 @@ pub fn transfer_hash_internal(transfer: &ExchangeTransfer, condition: u64) -> Ha
     let mut packed_message1 = U256([
         transfer.max_amount_fee,
         transfer.amount,
-        if condition == 0 {
+        if condition.is_zero() {
             // Normal Transfer.
             TRANSFER_ORDER_TYPE
         } else {
@@ -134,17 +258,25 @@ pub fn transfer_hash_internal(transfer: &ExchangeTransfer, condition: u64) -> Ha
 }
 
 impl ExchangeTransfer {
-    pub fn hash(&self, condition: u64) -> HashType {
+    pub fn hash(&self, condition: ConditionType) -> HashType {
         transfer_hash_internal(self, condition)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use primitive_types::U256;
+
     use crate::common::OrderBase;
-    use crate::transaction::transfer::{sign_transfer, Transfer};
+    use crate::error::parse_json;
+    use crate::hash_type::hash_type_to_string_with_0xprefix;
+    use crate::transaction::transfer::{
+        sign_transfer, sign_transfer_unchecked, transfer_hash, ConditionalTransfer, Transfer,
+        TransferFee,
+    };
     use crate::tx::packed_public_key::{private_key_from_string, public_key_from_private};
     use crate::tx::public_key_type::PublicKeyType;
+    use crate::validate::Validate;
 
     #[test]
     pub fn test_sign_transfer() {
@@ -164,10 +296,134 @@ mod test {
             amount: 1,
             receiver_position_id: 0,
             asset_id: Default::default(),
+            fee: Default::default(),
         };
 
-        let w = sign_transfer(req, prv_key).unwrap();
+        let w = sign_transfer(req.clone(), prv_key).unwrap();
         println!("{:?}", w);
+
+        let zero = Transfer { amount: 0, ..req };
+        let err = sign_transfer(zero.clone(), prv_key).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert!(sign_transfer_unchecked(zero, prv_key).is_ok());
+    }
+
+    #[test]
+    fn test_conditional_transfer_hash() {
+        let json = r#"{
+        "nonce": "1",
+        "public_key": "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa",
+        "expiration_timestamp": "1684832800",
+        "sender_position_id": "2",
+        "receiver_public_key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "receiver_position_id": "3",
+        "amount": "100",
+        "asset_id": "0xa",
+        "fact_registry_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+        "fact": "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b"
+    }"#;
+        let tx: ConditionalTransfer = serde_json::from_str(json).unwrap();
+        // the keccak digest is 0x4110..., its top bits are cleared
+        assert_eq!(
+            tx.condition(),
+            U256::from_str_radix(
+                "0x11028162dc5903a312989b78c8e547e68fa848285fd435faf7ae349a9370929",
+                16
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(tx.hash()),
+            "0x0e82549aeb0699bb64a2f9273ebaeec466b4866db5200a491b9704856ee76bb3"
+        );
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(transfer_hash(&tx.transfer, U256::zero())),
+            "0x22af0e0c89e49b798c3ab59f87851ff095613e55f8812e2b173816ea11f6aec3"
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee_hash() {
+        let json = r#"{
+        "nonce": "1",
+        "public_key": "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa",
+        "expiration_timestamp": "1684832800",
+        "sender_position_id": "2",
+        "receiver_public_key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "receiver_position_id": "3",
+        "amount": "100",
+        "asset_id": "0xa",
+        "fee": "5",
+        "fee_asset_id": "0xb",
+        "fee_position_id": "4"
+    }"#;
+        let mut tx: Transfer = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(transfer_hash(&tx, U256::zero())),
+            "0x18f326b750d7c82f4cb4a3b0fec1f21e704bd14c192566f45fbbfaeaefb1ef44"
+        );
+        let value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["fee_position_id"], "4");
+
+        // a zero fee from the sender position is the transfer without fee
+        tx.fee = TransferFee {
+            fee: 0,
+            fee_asset_id: Some(U256::zero()),
+            fee_position_id: Some(tx.sender_position_id),
+        };
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(transfer_hash(&tx, U256::zero())),
+            "0x22af0e0c89e49b798c3ab59f87851ff095613e55f8812e2b173816ea11f6aec3"
+        );
+        tx.fee = TransferFee::default();
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(transfer_hash(&tx, U256::zero())),
+            "0x22af0e0c89e49b798c3ab59f87851ff095613e55f8812e2b173816ea11f6aec3"
+        );
+        let value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["fee"], "0");
+        assert!(value.get("fee_asset_id").is_none());
+        assert!(tx.validate(None).is_ok());
+
+        tx.fee = TransferFee {
+            fee: 5,
+            fee_asset_id: Some(U256::one() << 250),
+            fee_position_id: None,
+        };
+        let err = tx.validate(None).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        let err = err.to_string();
+        assert!(err.contains("fee_asset_id: must be below"));
+        assert!(err.contains("fee_position_id: must be set with a fee"));
+    }
+
+    #[test]
+    fn test_transfer_amount_bounds() {
+        let json = r#"{
+        "nonce": "1",
+        "public_key": "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa",
+        "expiration_timestamp": "1684832800",
+        "sender_position_id": "2",
+        "receiver_public_key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "receiver_position_id": "3",
+        "amount": "0",
+        "asset_id": "0xa"
+    }"#;
+        let mut tx: Transfer = parse_json(json).unwrap();
+        let err = tx.validate(None).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: amount: must be at least 1"
+        );
+        tx.amount = u64::MAX;
+        assert!(tx.validate(None).is_ok());
+
+        let err = parse_json::<Transfer>(
+            &json.replace(r#""amount": "0""#, r#""amount": "18446744073709551616""#),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "AMOUNT_OUT_OF_RANGE");
     }
 
     #[test]
@@ -1,11 +1,11 @@
-use anyhow::Result;
+use crate::error::Result;
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
 use crate::common::OrderBase;
 use crate::constant::{
-    AMOUNT_UPPER_BOUND_U256, EXPIRATION_TIMESTAMP_UPPER_BOUND_U256, NONCE_UPPER_BOUND_U256,
-    POSITION_ID_UPPER_BOUND_U256,
+    AMOUNT_UPPER_BOUND_U256, COLLATERAL_ASSET_ID_UPPER_BOUND,
+    EXPIRATION_TIMESTAMP_UPPER_BOUND_U256, NONCE_UPPER_BOUND_U256, POSITION_ID_UPPER_BOUND_U256,
 };
 use crate::felt::LeBytesConvert;
 use crate::hash::hash2;
@@ -13,6 +13,7 @@ use crate::tx::packed_public_key::private_key_from_string;
 use crate::tx::public_key_type::PublicKeyType;
 use crate::tx::sign::TxSignature;
 use crate::types::HashType;
+use crate::validate::{Validate, Validator};
 use crate::zkw::JubjubSignature;
 use crate::U256SerdeAsRadix16Prefix0xString;
 use crate::U64SerdeAsString;
@@ -46,13 +47,48 @@ pub struct Withdraw {
     pub owner_key: PublicKeyType,
 }
 
+impl Validate for WithdrawRequest {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount, "amount");
+        validator.below(self.amount, AMOUNT_UPPER_BOUND_U256, "amount");
+        validator.below(self.asset_id, COLLATERAL_ASSET_ID_UPPER_BOUND, "asset_id");
+    }
+}
+
+impl Validate for Withdraw {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount, "amount");
+        validator.below(self.amount, AMOUNT_UPPER_BOUND_U256, "amount");
+    }
+}
+
+/// Sign a withdrawal of `asset_id_collateral`, one that breaks any bound is refused.
 pub fn sign_withdraw(
     withdrawal: Withdraw,
     asset_id_collateral: &CollateralAssetId,
     prvk: &str,
+) -> Result<JubjubSignature> {
+    let mut validator = Validator::new(None);
+    withdrawal.check(&mut validator);
+    validator.below(
+        *asset_id_collateral,
+        COLLATERAL_ASSET_ID_UPPER_BOUND,
+        "asset_id",
+    );
+    validator.finish()?;
+    sign_withdraw_unchecked(withdrawal, asset_id_collateral, prvk)
+}
+
+/// Same as [`sign_withdraw`] without the field range checks.
+pub fn sign_withdraw_unchecked(
+    withdrawal: Withdraw,
+    asset_id_collateral: &CollateralAssetId,
+    prvk: &str,
 ) -> Result<JubjubSignature> {
     let hash = withdrawal_hash(&withdrawal, asset_id_collateral);
-    let private_key = private_key_from_string(prvk).unwrap();
+    let private_key = private_key_from_string(prvk)?;
     let (sig, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
     Ok(sig.into())
 }
@@ -94,11 +130,13 @@ pub fn withdrawal_hash(withdrawal: &Withdraw, asset_id_collateral: &CollateralAs
 #[cfg(test)]
 mod test {
     use crate::common::OrderBase;
+    use crate::error::parse_json;
     use crate::transaction::withdraw::{
         sign_withdraw, CollateralAssetId, Withdraw, WithdrawRequest,
     };
     use crate::tx::packed_public_key::{private_key_from_string, public_key_from_private};
     use crate::tx::public_key_type::PublicKeyType;
+    use crate::validate::Validate;
 
     #[test]
     pub fn test_withdraw() {
@@ -139,4 +177,44 @@ mod test {
         let withdraw = serde_json::from_str::<WithdrawRequest>(json);
         assert!(withdraw.is_ok());
     }
+
+    #[test]
+    pub fn test_withdraw_amount_bounds() {
+        let json = r#"{
+        "nonce":"1",
+        "public_key":"0x9bb04dba1329711e145d387f71926fb2b81496c72210d53588200a954dbb443f",
+        "expiration_timestamp":"1684832800",
+        "position_id":"2",
+        "amount":"0",
+        "eth_address":"0x9bb04dba1329711e145d387f71926fb2b81496c72210d53588200a954dbb443f",
+        "asset_id": "0x1a"
+    }"#;
+        let mut req: WithdrawRequest = parse_json(json).unwrap();
+        let err = req.validate(None).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: amount: must be at least 1"
+        );
+        req.amount = u64::MAX;
+        assert!(req.validate(None).is_ok());
+
+        let withdraw = Withdraw {
+            base: req.base,
+            position_id: req.position_id,
+            amount: 0,
+            owner_key: req.owner_key,
+        };
+        assert!(withdraw
+            .validate(None)
+            .unwrap_err()
+            .to_string()
+            .contains("amount: must be at least 1"));
+
+        let err = parse_json::<WithdrawRequest>(
+            &json.replace(r#""amount":"0""#, r#""amount":"18446744073709551616""#),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "AMOUNT_OUT_OF_RANGE");
+    }
 }
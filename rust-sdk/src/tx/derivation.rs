@@ -0,0 +1,279 @@
+//! Sub-account keys derived from one master seed.
+//!
+//! The scheme follows BIP-32 over the Jubjub group. The master private key is
+//! [`crate::private_key_from_seed`] of the seed, its chain code the right half of
+//! `HMAC-SHA512("zkdex seed", seed)`. Child `i` of `(k, c)` takes `I = HMAC-SHA512(c, data)` with
+//! `data = 0x00 || k || i` for a hardened index (`i >= 2^31`) and `data = K || i` otherwise, `k`
+//! and the packed public key `K` being 32 big endian bytes and `i` 4 big endian bytes. The left
+//! half of `I` read as a little endian scalar is the tweak `t`, the child private key is `k + t` and
+//! its chain code the right half. A normal child public key is `K + t * G`, so it can be derived
+//! from the parent public key alone.
+//!
+//! The same property works backwards: anyone holding the parent extended public key can compute
+//! `t`, so a leaked normal child private key gives the parent private key `k' - t`, and with it
+//! every sibling. Derive account level keys, the ones handed to a service or another device, at
+//! hardened indexes like `m/7'/3'` and keep normal indexes below a key whose whole subtree may be
+//! exposed together.
+
+use franklin_crypto::bellman::pairing::ff::Field;
+use franklin_crypto::eddsa::PrivateKey;
+use franklin_crypto::jubjub::{FixedGenerators, JubjubParams};
+use hmac::{Hmac, Mac};
+use pairing_ce::bn256::Bn256;
+use primitive_types::U256;
+use sha2::Sha512;
+
+use crate::error::{Result, ZkdexError};
+use crate::private_key_from_seed;
+use crate::tx::convert::FeConvert;
+use crate::tx::packed_public_key::{
+    private_key_from_string, public_key_from_private, PackedPublicKey, PrivateKeyType,
+};
+use crate::tx::{fs_from_le_bytes_reduced, JUBJUB_PARAMS};
+
+/// Indexes from `HARDENED` on can only be derived from a private key, written `i'` in a path.
+pub const HARDENED: u32 = 1 << 31;
+
+const MASTER_KEY: &[u8] = b"zkdex seed";
+
+pub struct ExtendedPrivateKey {
+    pub private_key: PrivateKeyType,
+    pub chain_code: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedPublicKey {
+    pub public_key: PackedPublicKey,
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let private_key = private_key_from_string(&private_key_from_seed(seed)?)?;
+        let (_, chain_code) = hmac_sha512(MASTER_KEY, seed);
+        Ok(Self {
+            private_key,
+            chain_code,
+        })
+    }
+
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: public_key_from_private(&self.private_key),
+            chain_code: self.chain_code,
+        }
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        let mut data = Vec::with_capacity(37);
+        if index >= HARDENED {
+            data.push(0);
+            data.extend(self.private_key.0.to_bytes());
+        } else {
+            data.extend(&packed_bytes(&public_key_from_private(&self.private_key)));
+        }
+        data.extend(&index.to_be_bytes());
+
+        let (tweak, chain_code) = hmac_sha512(&self.chain_code, &data);
+        let mut key = self.private_key.0;
+        key.add_assign(&fs_from_le_bytes_reduced(&tweak));
+        if key.is_zero() {
+            return Err(zero_child(index));
+        }
+        Ok(Self {
+            private_key: PrivateKey(key),
+            chain_code,
+        })
+    }
+
+    /// Derive along `path`, like `m/7'/3`, from this key.
+    pub fn derive_path(&self, path: &str) -> Result<Self> {
+        let mut key = Self {
+            private_key: PrivateKey(self.private_key.0),
+            chain_code: self.chain_code,
+        };
+        for index in parse_path(path)? {
+            key = key.derive_child(index)?;
+        }
+        Ok(key)
+    }
+}
+
+impl ExtendedPublicKey {
+    /// The public key of the normal child `index`, a hardened index fails.
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        if index >= HARDENED {
+            return Err(ZkdexError::InvalidArgument(format!(
+                "hardened index {}' needs the private key",
+                index - HARDENED
+            )));
+        }
+        let mut data = Vec::with_capacity(36);
+        data.extend(&packed_bytes(&self.public_key));
+        data.extend(&index.to_be_bytes());
+
+        let (tweak, chain_code) = hmac_sha512(&self.chain_code, &data);
+        let parent = self.public_key.try_into_public_key()?;
+        let tweak = JUBJUB_PARAMS
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .mul(fs_from_le_bytes_reduced(&tweak), &JUBJUB_PARAMS);
+        let child = parent.0.add(&tweak, &JUBJUB_PARAMS);
+
+        let mut packed = [0u8; 32];
+        child
+            .write(packed.as_mut())
+            .map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+        let public_key = U256::from_little_endian(&packed);
+        if public_key == U256::one() {
+            return Err(zero_child(index));
+        }
+        Ok(Self {
+            public_key: PackedPublicKey(public_key),
+            chain_code,
+        })
+    }
+
+    /// Derive along `path`, like `m/3/0`, from this key, every index must be normal.
+    pub fn derive_path(&self, path: &str) -> Result<Self> {
+        let mut key = self.clone();
+        for index in parse_path(path)? {
+            key = key.derive_child(index)?;
+        }
+        Ok(key)
+    }
+}
+
+/// Parse `m/7'/3h/0` into its indexes, `'` or `h` marks a hardened index.
+pub fn parse_path(path: &str) -> Result<Vec<u32>> {
+    let invalid =
+        |reason: String| ZkdexError::InvalidArgument(format!("path {}: {}", path, reason));
+    let mut parts = path.split('/');
+    if parts.next() != Some("m") {
+        return Err(invalid("should start with m".to_string()));
+    }
+    parts
+        .map(|part| {
+            let (number, hardened) = match part.strip_suffix(|c| c == '\'' || c == 'h') {
+                Some(number) => (number, true),
+                None => (part, false),
+            };
+            let index = number
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED)
+                .ok_or_else(|| invalid(format!("{} is not an index below 2^31", part)))?;
+            Ok(if hardened { index + HARDENED } else { index })
+        })
+        .collect()
+}
+
+fn packed_bytes(public_key: &PackedPublicKey) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    public_key.0.to_big_endian(&mut bytes);
+    bytes
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).expect("hmac takes any key size");
+    mac.input(data);
+    let out = mac.result().code();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&out[..32]);
+    right.copy_from_slice(&out[32..]);
+    (left, right)
+}
+
+fn zero_child(index: u32) -> ZkdexError {
+    ZkdexError::InvalidArgument(format!(
+        "index {} derives a zero key, use the next one",
+        index
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::packed_public_key::private_key_to_string;
+
+    const SEED: &[u8] = b"hello world good life 996 very nice";
+
+    fn private_key_hex(key: &ExtendedPrivateKey) -> String {
+        format!("0x{}", private_key_to_string(&key.private_key))
+    }
+
+    #[test]
+    fn test_vectors() {
+        let master = ExtendedPrivateKey::from_seed(SEED).unwrap();
+        assert_eq!(
+            private_key_hex(&master),
+            "0x02aca28609503a6474ec0a115b8662dbf760b6da6109e17c757dbbd3835c93f9"
+        );
+        assert_eq!(
+            hex::encode(master.chain_code),
+            "5aa33b47fa41321555e4fd679ededc4bc9886a414930ff242f7a7a60fb5eb499"
+        );
+        assert_eq!(
+            master.public_key().public_key.to_string(),
+            "0xb03ee7244d3dcaabbc9038c2b77e33a9116a530a61f7f9033138a06b80623403"
+        );
+
+        let vectors = [
+            (
+                "m/0'",
+                "0x039c312dd6ba85469fcbb8f5388e9c1f5ccb064f85daacbd00d608cf42eee843",
+                "0x23cc804761d7d786f69fd7db1d444174976ca90dbd7f6de5b17e334da77135f3",
+            ),
+            (
+                "m/1/2",
+                "0x017a4f39d23ece6122cea9aaacc086fb6c1a4bdfd8e8057f97057e545ef58dea",
+                "0x1e50f110dfe5d4da873afd8ecb2527e13f26c34aca560f11c56914e1011677f4",
+            ),
+            (
+                "m/7h/3",
+                "0x006dacb284fb197c484075cb03f14ba8be86c07c96ab08a50ebef9813278b33f",
+                "0x9826359c2ffc1bc0b602d89bcd1fc6448a8823792ea57e4c1dd8a8afec46df8f",
+            ),
+        ];
+        for (path, private_key, public_key) in vectors {
+            let child = master.derive_path(path).unwrap();
+            assert_eq!(private_key_hex(&child), private_key, "{}", path);
+            assert_eq!(
+                child.public_key().public_key.to_string(),
+                public_key,
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_public_derivation() {
+        let master = ExtendedPrivateKey::from_seed(SEED).unwrap();
+        let child = master.derive_path("m/1/2").unwrap().public_key();
+        assert_eq!(master.public_key().derive_path("m/1/2").unwrap(), child);
+        assert_eq!(
+            hex::encode(child.chain_code),
+            "dfe78839a1dcd6d7e20e84fafa7a208e461dce32e0c6ec08cb4689324271ab6c"
+        );
+
+        let hardened = master.derive_path("m/7'").unwrap().public_key();
+        let child = master.derive_path("m/7'/3").unwrap().public_key();
+        assert_eq!(hardened.derive_child(3).unwrap(), child);
+
+        let err = master.public_key().derive_path("m/7'").unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("m").unwrap(), Vec::<u32>::new());
+        assert_eq!(
+            parse_path("m/0'/5h/2147483647").unwrap(),
+            vec![HARDENED, HARDENED + 5, HARDENED - 1]
+        );
+        for path in ["", "0/1", "m/", "m/2147483648", "m/-1", "m/1''", "m/x"] {
+            assert!(parse_path(path).is_err(), "{}", path);
+        }
+    }
+}
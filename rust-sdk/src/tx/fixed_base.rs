@@ -0,0 +1,128 @@
+//! Scalar multiplication of a fixed point with a precomputed table.
+//!
+//! The table of a point `P` holds `j * 16^i * P` for every 4 bit window `i` of a scalar and every
+//! digit `j`, so a multiplication is one addition per non-zero window and no doubling, about four
+//! times fewer group operations than double-and-add. The generator has one table for the whole
+//! process, see [`crate::tx::GENERATOR_TABLE`], a public key that verifies many signatures can
+//! keep its own.
+
+use franklin_crypto::alt_babyjubjub::fs::Fs;
+use franklin_crypto::bellman::pairing::ff::PrimeField;
+use franklin_crypto::eddsa::PublicKey;
+use franklin_crypto::jubjub::edwards::Point;
+use franklin_crypto::jubjub::Unknown;
+use pairing_ce::bn256::Bn256;
+
+use crate::tx::JUBJUB_PARAMS;
+
+const WINDOW: usize = 4;
+const WINDOWS: usize = (Fs::NUM_BITS as usize + WINDOW - 1) / WINDOW;
+
+pub struct FixedBaseTable {
+    /// `windows[i][j - 1] = j * 16^i * P`.
+    windows: Vec<Vec<Point<Bn256, Unknown>>>,
+}
+
+impl FixedBaseTable {
+    pub fn new(base: &Point<Bn256, Unknown>) -> Self {
+        let params = &*JUBJUB_PARAMS;
+        let mut windows = Vec::with_capacity(WINDOWS);
+        let mut window_base = base.clone();
+        for _ in 0..WINDOWS {
+            let mut multiples = Vec::with_capacity((1 << WINDOW) - 1);
+            let mut multiple = window_base.clone();
+            for _ in 1..(1 << WINDOW) {
+                multiples.push(multiple.clone());
+                multiple = multiple.add(&window_base, params);
+            }
+            windows.push(multiples);
+            window_base = multiple;
+        }
+        Self { windows }
+    }
+
+    pub fn for_public_key(public_key: &PublicKey<Bn256>) -> Self {
+        Self::new(&public_key.0)
+    }
+
+    /// `scalar * P`.
+    pub fn mul(&self, scalar: &Fs) -> Point<Bn256, Unknown> {
+        let params = &*JUBJUB_PARAMS;
+        let repr = scalar.into_repr();
+        let mut sum = Point::zero();
+        for (i, multiples) in self.windows.iter().enumerate() {
+            let digit = window_digit(repr.as_ref(), i * WINDOW, WINDOW);
+            if digit != 0 {
+                sum = sum.add(&multiples[digit - 1], params);
+            }
+        }
+        sum
+    }
+}
+
+/// The `window` bits of `limbs` starting at bit `start`.
+pub(crate) fn window_digit(limbs: &[u64], start: usize, window: usize) -> usize {
+    let limb = start / 64;
+    let shift = start % 64;
+    let mut bits = limbs[limb] >> shift;
+    if shift + window > 64 && limb + 1 < limbs.len() {
+        bits |= limbs[limb + 1] << (64 - shift);
+    }
+    (bits & ((1 << window) - 1)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use franklin_crypto::alt_babyjubjub::FixedGenerators;
+    use franklin_crypto::bellman::pairing::ff::Field;
+    use franklin_crypto::jubjub::JubjubParams;
+    use rand::Rng;
+
+    use super::*;
+    use crate::other_test::Bencher;
+    use crate::tx::GENERATOR_TABLE;
+
+    fn generator() -> Point<Bn256, Unknown> {
+        JUBJUB_PARAMS
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .clone()
+            .into()
+    }
+
+    fn random_scalars(n: usize) -> Vec<Fs> {
+        let mut rng = rand::thread_rng();
+        let mut scalars: Vec<Fs> = (0..n).map(|_| rng.gen()).collect();
+        scalars.push(Fs::zero());
+        scalars.push(Fs::one());
+        let mut minus_one = Fs::one();
+        minus_one.negate();
+        scalars.push(minus_one);
+        scalars
+    }
+
+    #[test]
+    fn test_fixed_base_mul() {
+        let generator = generator();
+        let base = generator.mul(Fs::from_str("123456789").unwrap(), &JUBJUB_PARAMS);
+        let table = FixedBaseTable::new(&base);
+        for scalar in random_scalars(20) {
+            assert!(GENERATOR_TABLE
+                .mul(&scalar)
+                .eq(&generator.mul(scalar, &JUBJUB_PARAMS)));
+            assert!(table.mul(&scalar).eq(&base.mul(scalar, &JUBJUB_PARAMS)));
+        }
+    }
+
+    #[bench]
+    fn bench_fixed_base_mul(b: &mut Bencher) {
+        let scalar = random_scalars(1)[0];
+        b.iter(|| GENERATOR_TABLE.mul(&scalar))
+    }
+
+    #[bench]
+    fn bench_double_and_add_mul(b: &mut Bencher) {
+        let generator = generator();
+        let scalar = random_scalars(1)[0];
+        b.iter(|| generator.mul(scalar, &JUBJUB_PARAMS))
+    }
+}
@@ -1,16 +1,58 @@
+pub mod batch_verify;
 pub mod convert;
+pub mod derivation;
+pub mod fixed_base;
 pub mod packed_public_key;
 pub mod packed_signature;
 pub mod public_key_type;
+pub mod secret_key;
 pub mod sign;
+pub mod verifier;
 
+use std::convert::TryInto;
+
+use franklin_crypto::alt_babyjubjub::fs::{Fs, FsRepr};
+use franklin_crypto::alt_babyjubjub::FixedGenerators;
+use franklin_crypto::bellman::pairing::ff::{Field, PrimeField};
+use franklin_crypto::jubjub::JubjubParams;
 use franklin_crypto::{alt_babyjubjub::AltJubjubBn256, rescue::bn256::Bn256RescueParams};
 use lazy_static::lazy_static;
 use primitive_types::{H256, U256};
 
+use crate::tx::fixed_base::FixedBaseTable;
+
 lazy_static! {
     pub static ref JUBJUB_PARAMS: AltJubjubBn256 = AltJubjubBn256::new();
     pub static ref RESCUE_PARAMS: Bn256RescueParams = Bn256RescueParams::new_checked_2_into_1();
+    /// Multiples of the spending key generator, behind every signature and public key.
+    pub static ref GENERATOR_TABLE: FixedBaseTable = FixedBaseTable::new(
+        &JUBJUB_PARAMS
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .clone()
+            .into()
+    );
+}
+
+impl JUBJUB_PARAMS {
+    /// Runs `f` on the params, kept from when they were a `thread_local!`.
+    #[deprecated(note = "dereference JUBJUB_PARAMS, the params are shared between threads")]
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&AltJubjubBn256) -> R,
+    {
+        f(&**self)
+    }
+}
+
+impl RESCUE_PARAMS {
+    /// Runs `f` on the params, kept from when they were a `thread_local!`.
+    #[deprecated(note = "dereference RESCUE_PARAMS, the params are shared between threads")]
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&Bn256RescueParams) -> R,
+    {
+        f(&**self)
+    }
 }
 
 pub fn h256_to_u256(h: H256) -> U256 {
@@ -33,3 +75,29 @@ pub fn u256_to_le(u: &U256) -> [u8; 32] {
     u.to_little_endian(&mut h[..]);
     h
 }
+
+/// Read 32 little endian bytes as a Jubjub scalar, reduced by the group order as `lo + hi * 2^128`.
+pub(crate) fn fs_from_le_bytes_reduced(bytes: &[u8; 32]) -> Fs {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    let scalar = |limbs| Fs::from_repr(FsRepr(limbs)).expect("below the group order");
+
+    let mut fs = scalar([limbs[2], limbs[3], 0, 0]);
+    fs.mul_assign(&scalar([0, 0, 1, 0]));
+    fs.add_assign(&scalar([limbs[0], limbs[1], 0, 0]));
+    fs
+}
+
+/// Read 64 little endian bytes as a Jubjub scalar, wide enough that the reduction has no bias.
+pub(crate) fn fs_from_le_bytes_wide(bytes: &[u8; 64]) -> Fs {
+    let (lo, hi) = bytes.split_at(32);
+    let mut shift = Fs::from_repr(FsRepr([0, 0, 1, 0])).expect("below the group order");
+    shift.square();
+
+    let mut fs = fs_from_le_bytes_reduced(hi.try_into().unwrap());
+    fs.mul_assign(&shift);
+    fs.add_assign(&fs_from_le_bytes_reduced(lo.try_into().unwrap()));
+    fs
+}
@@ -1,21 +1,21 @@
 use std::convert::TryFrom;
 use std::fmt::{Debug, Formatter};
 
-use anyhow::anyhow;
 use franklin_crypto::alt_babyjubjub::AltJubjubBn256;
 use franklin_crypto::bellman::bn256::Fr;
 use franklin_crypto::eddsa::{PrivateKey, PublicKey, Signature};
 use franklin_crypto::jubjub::{edwards, FixedGenerators, JubjubEngine};
 use pairing_ce::bn256::{Bn256, FrRepr};
-use pairing_ce::ff::{PrimeField, PrimeFieldRepr};
+use pairing_ce::ff::{Field, PrimeField, PrimeFieldRepr};
 use primitive_types::U256;
-use rand::Rng;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error as ThisError;
+use zeroize::Zeroizing;
 
+use crate::error::ZkdexError;
 use crate::trim_0x;
-use crate::tx::packed_signature::{get_r_from_xy, get_xy_from_r};
-use crate::tx::{u256_to_le, JUBJUB_PARAMS};
+use crate::tx::packed_signature::{try_get_xy_from_r, try_prime_order_point};
+use crate::tx::{fs_from_le_bytes_wide, u256_to_le, GENERATOR_TABLE, JUBJUB_PARAMS};
 use crate::zkw::BabyJubjubPoint;
 
 use super::convert::FeConvert;
@@ -44,37 +44,22 @@ impl ToString for PackedPublicKey {
 }
 
 impl TryFrom<&str> for PackedPublicKey {
-    type Error = anyhow::Error;
+    type Error = ZkdexError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let key = U256::from_str_radix(value, 16)?;
+        let key = U256::from_str_radix(value, 16)
+            .map_err(|e| ZkdexError::InvalidPublicKey(format!("{}: {}", value, e)))?;
 
         Ok(Self(key))
     }
 }
 
-impl Into<BabyJubjubPoint> for PackedPublicKey {
-    fn into(self) -> BabyJubjubPoint {
-        if self.is_address() {
-            BabyJubjubPoint {
-                x: Default::default(),
-                y: self.0.clone(),
-            }
-        } else {
-            let r = &self.0;
-            let (x, y) = get_xy_from_r(r);
-            let x = fr_to_u256(&x).unwrap();
-            let y = fr_to_u256(&y).unwrap();
-            BabyJubjubPoint { x, y }
-        }
-    }
-}
-
-impl From<BabyJubjubPoint> for PackedPublicKey {
-    fn from(value: BabyJubjubPoint) -> Self {
-        let r = get_r_from_xy(&value.x, &value.y);
+/// The inverse of [`PackedPublicKey::try_into_point`].
+impl TryFrom<BabyJubjubPoint> for PackedPublicKey {
+    type Error = ZkdexError;
 
-        PackedPublicKey(r)
+    fn try_from(value: BabyJubjubPoint) -> Result<Self, Self::Error> {
+        PackedPublicKey::try_from((value.x, value.y))
     }
 }
 
@@ -93,6 +78,35 @@ impl PackedPublicKey {
         PackedPublicKey::try_from(address).unwrap()
     }
 
+    /// Decompress the key into its curve point, an address key keeps the address as y.
+    pub fn try_into_point(&self) -> crate::error::Result<BabyJubjubPoint> {
+        if self.is_address() {
+            return Ok(BabyJubjubPoint {
+                x: Default::default(),
+                y: self.0.clone(),
+            });
+        }
+        let (x, y) = try_get_xy_from_r(&self.0)
+            .map_err(|e| ZkdexError::InvalidPublicKey(format!("{:#x}: {}", self.0, e)))?;
+        let x = fr_to_u256(&x).map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+        let y = fr_to_u256(&y).map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+        Ok(BabyJubjubPoint { x, y })
+    }
+
+    /// The key as a point of the prime order subgroup, anything else, an address key included,
+    /// is rejected. Signatures are only checked against keys that pass.
+    pub fn try_into_public_key(&self) -> crate::error::Result<PublicKey<Bn256>> {
+        if self.is_address() {
+            return Err(ZkdexError::InvalidPublicKey(format!(
+                "{:#x} is an address",
+                self.0
+            )));
+        }
+        let point = try_prime_order_point(&self.0)
+            .map_err(|e| ZkdexError::InvalidPublicKey(format!("{:#x} is {}", self.0, e)))?;
+        Ok(PublicKey(point))
+    }
+
     pub fn format_hex(&self, x_prefix: bool) -> String {
         if x_prefix {
             format!("{:#066x}", self)
@@ -140,82 +154,109 @@ impl<'de> Deserialize<'de> for PackedPublicKey {
     }
 }
 
+/// The public key of `pk`, an error when `sig` of `msg` does not verify against it.
 pub fn public_key_from_private_with_verify(
     pk: &PrivateKey<Bn256>,
     msg: &[u8],
     sig: &Signature<Bn256>,
-) -> PackedPublicKey {
+) -> crate::error::Result<PackedPublicKey> {
     let pubkey = PublicKey::from_private(pk, FixedGenerators::SpendingKeyGenerator, &JUBJUB_PARAMS);
-    let aaa = pubkey.clone();
-    let point = pubkey.0;
-    let mut packed_point = [0u8; 32];
     let p_g = FixedGenerators::SpendingKeyGenerator;
-    let a = aaa.verify_for_raw_message(msg, sig, p_g, &JUBJUB_PARAMS, msg.len());
-    assert_eq!(a, true);
-    point.write(packed_point.as_mut()).unwrap();
-    PackedPublicKey(U256::from_little_endian(&packed_point))
+    if !pubkey.verify_for_raw_message(msg, sig, p_g, &JUBJUB_PARAMS, msg.len()) {
+        return Err(ZkdexError::InvalidSignature(
+            "the signature does not verify against its private key".to_string(),
+        ));
+    }
+    Ok(pack_public_key(&pubkey))
 }
 
 pub fn public_key_from_private(pk: &PrivateKey<Bn256>) -> PackedPublicKey {
-    let pubkey = PublicKey::from_private(pk, FixedGenerators::SpendingKeyGenerator, &JUBJUB_PARAMS);
-    let point = pubkey.0;
+    pack_public_key(&public_point_from_private(pk))
+}
+
+/// `k * G` from [`GENERATOR_TABLE`], the same point as `PublicKey::from_private`.
+pub(crate) fn public_point_from_private(pk: &PrivateKey<Bn256>) -> PublicKey<Bn256> {
+    PublicKey(GENERATOR_TABLE.mul(&pk.0))
+}
+
+pub(crate) fn pack_public_key(pubkey: &PublicKey<Bn256>) -> PackedPublicKey {
     let mut packed_point = [0u8; 32];
-    point.write(packed_point.as_mut()).unwrap();
+    pubkey.0.write(packed_point.as_mut()).unwrap();
     PackedPublicKey(U256::from_little_endian(&packed_point))
 }
 
-pub fn fr_to_u256(fr: &Fr) -> Result<U256, anyhow::Error> {
+pub fn fr_to_u256(fr: &Fr) -> crate::error::Result<U256> {
     let repr = fr.into_repr();
     let mut buf = [0u8; 32];
     repr.write_le(&mut buf[..])
-        .map_err(|e| anyhow!(e.to_string()))?;
+        .map_err(|e| ZkdexError::InvalidArgument(e.to_string()))?;
     Ok(U256::from_little_endian(&buf))
 }
 
-pub fn u256_to_fr(u: &U256) -> Result<Fr, anyhow::Error> {
+pub fn u256_to_fr(u: &U256) -> crate::error::Result<Fr> {
     let mut s_repr = FrRepr::default();
     s_repr
         .read_le(&u256_to_le(u)[..])
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let s = Fr::from_repr(s_repr)?;
+        .map_err(|e| ZkdexError::InvalidArgument(e.to_string()))?;
+    let s = Fr::from_repr(s_repr).map_err(|e| ZkdexError::InvalidArgument(e.to_string()))?;
     Ok(s)
 }
 
-pub fn convert_to_pubkey(x: &U256, y: &U256) -> Result<PublicKey<Bn256>, anyhow::Error> {
-    let x = u256_to_fr(x)?;
-    let y = u256_to_fr(y)?;
-    let point = edwards::Point::from_xy(x, y, &JUBJUB_PARAMS as &AltJubjubBn256).ok_or(anyhow!(
-        String::from("could not decode public key by x and y")
-    ))?;
+pub fn convert_to_pubkey(x: &U256, y: &U256) -> crate::error::Result<PublicKey<Bn256>> {
+    let x = u256_to_fr(x).map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+    let y = u256_to_fr(y).map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+    let point = edwards::Point::from_xy(x, y, &JUBJUB_PARAMS as &AltJubjubBn256).ok_or(
+        ZkdexError::InvalidPublicKey(String::from("could not decode public key by x and y")),
+    )?;
     Ok(PublicKey(point))
 }
 
-impl From<(U256, U256)> for PackedPublicKey {
-    fn from(value: (U256, U256)) -> Self {
+/// Pack the `(x, y)` coordinates of a key, `(0, address)` is an address key.
+impl TryFrom<(U256, U256)> for PackedPublicKey {
+    type Error = ZkdexError;
+
+    fn try_from(value: (U256, U256)) -> Result<Self, Self::Error> {
         if value.0 == U256::zero() && is_address(&value.1) {
-            return PackedPublicKey(value.1);
+            return Ok(PackedPublicKey(value.1));
         }
-        let pubkey = convert_to_pubkey(&value.0, &value.1).unwrap();
-        let point = pubkey.0;
+        let point = convert_to_pubkey(&value.0, &value.1)?.0;
         let mut packed_point = [0u8; 32];
-        point.write(packed_point.as_mut()).unwrap();
-        PackedPublicKey(U256::from_little_endian(&packed_point))
+        point
+            .write(packed_point.as_mut())
+            .map_err(|e| ZkdexError::InvalidPublicKey(e.to_string()))?;
+        Ok(PackedPublicKey(U256::from_little_endian(&packed_point)))
     }
 }
 
 pub fn new_private_key() -> PrivateKey<Bn256> {
-    let mut rng = rand::thread_rng();
-    PrivateKey::<Bn256>(rng.gen())
+    random_private_key().expect("the os has no randomness")
+}
+
+/// A private key drawn from the os CSPRNG, which is `crypto.getRandomValues` on wasm. 64 random
+/// bytes are reduced by the group order so every key is equally likely.
+pub fn random_private_key() -> crate::error::Result<PrivateKeyType> {
+    loop {
+        let mut bytes = Zeroizing::new([0u8; 64]);
+        getrandom::getrandom(bytes.as_mut())
+            .map_err(|e| ZkdexError::InvalidPrivateKey(format!("no randomness: {}", e)))?;
+        let key = fs_from_le_bytes_wide(&bytes);
+        if !key.is_zero() {
+            return Ok(PrivateKey(key));
+        }
+    }
 }
 
 pub fn private_key_to_string(pk: &PrivateKeyType) -> String {
     pk.0.to_hex()
 }
 
-pub fn private_key_from_string(s: &str) -> Result<PrivateKeyType, anyhow::Error> {
-    Ok(PrivateKey::<Bn256>(
-        <Bn256 as JubjubEngine>::Fs::from_bytes(hex::decode(trim_0x(s))?.as_slice())?,
-    ))
+pub fn private_key_from_string(s: &str) -> crate::error::Result<PrivateKeyType> {
+    let bytes = hex::decode(trim_0x(s))
+        .map(Zeroizing::new)
+        .map_err(|e| ZkdexError::InvalidPrivateKey(e.to_string()))?;
+    let fs = <Bn256 as JubjubEngine>::Fs::from_bytes(bytes.as_slice())
+        .map_err(|e| ZkdexError::InvalidPrivateKey(e.to_string()))?;
+    Ok(PrivateKey::<Bn256>(fs))
 }
 
 #[cfg(test)]
@@ -268,6 +309,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_packed_pubkey_from_xy() {
+        let private_key = private_key_from_string(
+            "01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe",
+        )
+        .unwrap();
+        let key = public_key_from_private(&private_key);
+        let point = key.try_into_point().unwrap();
+        assert_eq!(PackedPublicKey::try_from(point).unwrap(), key);
+
+        let address = PackedPublicKey::try_from("0x1").unwrap();
+        let point = address.try_into_point().unwrap();
+        assert_eq!(
+            PackedPublicKey::try_from((point.x, point.y)).unwrap(),
+            address
+        );
+
+        let err = PackedPublicKey::try_from((U256::one(), U256::one())).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PUBLIC_KEY");
+    }
+
     #[test]
     fn test_packed_pubkey_serde() {
         for _ in 0..10 {
@@ -287,4 +349,26 @@ mod tests {
             rand::random::<u64>(),
         ]))
     }
+
+    #[test]
+    fn test_random_private_key_reduction() {
+        let fs = fs_from_le_bytes_wide(&[0xff; 64]);
+        assert_eq!(
+            fs.to_hex(),
+            "0445b524f1ba50a8e472df203faa158f74646cacf5f84ec435e44abee7ecb21d"
+        );
+        let mut bytes = [0u8; 64];
+        bytes.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        assert_eq!(
+            fs_from_le_bytes_wide(&bytes).to_hex(),
+            "016bcee0d4ca296ee6714b6a806004160dfc1721ddb96f1a980db5511adf282b"
+        );
+
+        let key = random_private_key().unwrap();
+        assert!(!key.0.is_zero());
+        assert_ne!(
+            private_key_to_string(&key),
+            private_key_to_string(&new_private_key())
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use std::fmt::{Debug, Formatter};
 
+use franklin_crypto::alt_babyjubjub::fs::Fs;
 use franklin_crypto::alt_babyjubjub::{AltJubjubBn256, FixedGenerators};
 use franklin_crypto::bellman::PrimeField;
 use franklin_crypto::eddsa::{PublicKey, Signature};
@@ -10,11 +11,15 @@ use primitive_types::U256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+use crate::error::ZkdexError;
+use crate::tx::fixed_base::FixedBaseTable;
 use crate::tx::packed_public_key::u256_to_fr;
-use crate::tx::{le_to_u256, u256_to_le, JUBJUB_PARAMS};
+use crate::tx::{fs_from_le_bytes_reduced, le_to_u256, u256_to_le, GENERATOR_TABLE, JUBJUB_PARAMS};
 use crate::zkw::{BabyJubjubPoint, JubjubSignature};
 use crate::U256SerdeAsRadix16Prefix0xString;
 
+const MAX_MESSAGE_SIZE: usize = 32;
+
 pub struct SignatureSerde;
 
 #[derive(Debug, Error)]
@@ -30,6 +35,19 @@ pub enum DeserializeError {
     Unknown(String),
 }
 
+/// Why a point is refused as a public key or as the `R` of a signature.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PointError {
+    #[error("not a point of the curve")]
+    NotOnCurve,
+    #[error("the identity")]
+    Identity,
+    #[error("a point of small order")]
+    SmallOrder,
+    #[error("not in the prime order subgroup")]
+    NotInSubgroup,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SignatureOriginal {
     #[serde(rename = "r", with = "U256SerdeAsRadix16Prefix0xString")]
@@ -55,18 +73,52 @@ impl PackedSignature {
         let p_g = FixedGenerators::SpendingKeyGenerator;
         pk.verify_for_raw_message(msg, &self.0, p_g, &JUBJUB_PARAMS, msg.len())
     }
+
+    /// Same as [`PackedSignature::verify`] with `s * G` taken from [`GENERATOR_TABLE`] and `c * A`
+    /// from `pk_table` when the key has one, the table of `pk` made by
+    /// [`FixedBaseTable::for_public_key`].
+    pub fn verify_prepared(
+        &self,
+        pk: &PublicKey<Bn256>,
+        pk_table: Option<&FixedBaseTable>,
+        msg: &[u8],
+    ) -> bool {
+        let params = &*JUBJUB_PARAMS;
+        let c = match challenge(msg) {
+            Some(c) => c,
+            None => return false,
+        };
+        let c_a = match pk_table {
+            Some(table) => table.mul(&c),
+            None => pk.0.mul(c, params),
+        };
+        // 8 * (c * A + R - s * G) = 0
+        c_a.add(&self.0.r, params)
+            .add(&GENERATOR_TABLE.mul(&self.0.s).negate(), params)
+            .mul_by_cofactor(params)
+            .eq(&Point::zero())
+    }
 }
 
+/// The message as a scalar, it may exceed the group order. `None` when it is longer than a scalar,
+/// such a message has no valid signature.
+pub(crate) fn challenge(msg: &[u8]) -> Option<Fs> {
+    if msg.len() > MAX_MESSAGE_SIZE {
+        return None;
+    }
+    let mut bytes = [0u8; MAX_MESSAGE_SIZE];
+    bytes[..msg.len()].copy_from_slice(msg);
+    Some(fs_from_le_bytes_reduced(&bytes))
+}
+
+/// An `R` off the curve is a serialization error.
 impl Serialize for JubjubSignature {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut r = [0u8; 32];
-        let r_point = point_from_xy(&self.sig_r.x, &self.sig_r.y);
-        r_point.write(r.as_mut()).unwrap();
-
-        let r = le_to_u256(&r);
+        let r = try_get_r_from_xy(&self.sig_r.x, &self.sig_r.y)
+            .ok_or_else(|| serde::ser::Error::custom("r is not on the curve"))?;
         let s = U256(self.sig_s);
         let sign = SignatureOriginal { r, s };
 
@@ -79,15 +131,7 @@ impl SignatureSerde {
     where
         S: Serializer,
     {
-        let mut r = [0u8; 32];
-        let r_point = point_from_xy(&val.sig_r.x, &val.sig_r.y);
-        r_point.write(r.as_mut()).unwrap();
-
-        let r = le_to_u256(&r);
-        let s = U256(val.sig_s);
-        let sign = SignatureOriginal { r, s };
-
-        SignatureOriginal::serialize(&sign, serializer)
+        val.serialize(serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<JubjubSignature, D::Error>
@@ -96,55 +140,103 @@ impl SignatureSerde {
     {
         let sign = SignatureOriginal::deserialize(deserializer)?;
 
-        Ok(signature_from_rs(&sign.r, &sign.s))
+        try_signature_from_rs(&sign.r, &sign.s).map_err(serde::de::Error::custom)
     }
 }
 
+#[deprecated(note = "panics on an r off the curve, use try_signature_from_rs")]
 pub fn signature_from_rs(r: &U256, s: &U256) -> JubjubSignature {
-    let (x, y) = get_xy_from_r(r);
+    try_signature_from_rs(r, s).unwrap()
+}
+
+pub fn try_signature_from_rs(r: &U256, s: &U256) -> crate::error::Result<JubjubSignature> {
+    let (x, y) = try_get_xy_from_r(r).map_err(|e| ZkdexError::InvalidSignature(e.to_string()))?;
 
     let x_repr = x.into_repr();
     let y_repr = y.into_repr();
 
-    JubjubSignature {
+    Ok(JubjubSignature {
         sig_r: BabyJubjubPoint {
             x: U256(x_repr.0),
             y: U256(y_repr.0),
         },
         sig_s: s.0.clone(),
-    }
+    })
 }
 
+#[deprecated(note = "panics on an r off the curve, use try_get_xy_from_r")]
 pub fn get_xy_from_r(r_bar: &U256) -> (Fr, Fr) {
+    try_get_xy_from_r(r_bar).unwrap()
+}
+
+pub fn try_get_xy_from_r(r_bar: &U256) -> Result<(Fr, Fr), DeserializeError> {
     let r_bar = u256_to_le(&r_bar);
     let r: Point<Bn256, Unknown> =
         edwards::Point::read(r_bar.as_slice(), &JUBJUB_PARAMS as &AltJubjubBn256)
-            .map_err(DeserializeError::RestoreRPoint)
-            .unwrap();
-    r.into_xy()
+            .map_err(DeserializeError::RestoreRPoint)?;
+    Ok(r.into_xy())
 }
+
+#[deprecated(note = "panics on a point off the curve, use try_get_r_from_xy")]
 pub fn get_r_from_xy(x: &U256, y: &U256) -> U256 {
-    let point = point_from_xy(x, y);
+    try_get_r_from_xy(x, y).unwrap()
+}
+
+pub fn try_get_r_from_xy(x: &U256, y: &U256) -> Option<U256> {
+    let point = try_point_from_xy(x, y)?;
     let mut packed_point = [0u8; 32];
-    point.write(packed_point.as_mut()).unwrap();
-    le_to_u256(&packed_point)
+    point.write(packed_point.as_mut()).ok()?;
+    Some(le_to_u256(&packed_point))
+}
+
+/// Decompress `packed` into a point of the prime order subgroup other than the identity.
+pub fn try_prime_order_point(packed: &U256) -> Result<Point<Bn256, Unknown>, PointError> {
+    let packed = u256_to_le(packed);
+    let point = edwards::Point::read(packed.as_slice(), &JUBJUB_PARAMS as &AltJubjubBn256)
+        .map_err(|_| PointError::NotOnCurve)?;
+    check_prime_order(&point)?;
+    Ok(point)
+}
+
+/// A signature or public key point must have order `l`, the order of the generator. The identity
+/// and the points of order 2, 4 or 8 would let one signature pass for several keys or messages,
+/// and a small order component added to a valid point would make signatures malleable.
+pub fn check_prime_order(point: &Point<Bn256, Unknown>) -> Result<(), PointError> {
+    let params = &*JUBJUB_PARAMS;
+    if point.eq(&Point::zero()) {
+        return Err(PointError::Identity);
+    }
+    if point.mul_by_cofactor(params).eq(&Point::zero()) {
+        return Err(PointError::SmallOrder);
+    }
+    if !point.mul(Fs::char(), params).eq(&Point::zero()) {
+        return Err(PointError::NotInSubgroup);
+    }
+    Ok(())
 }
 
-pub(crate) fn point_from_xy(x: &U256, y: &U256) -> Point<Bn256, Unknown> {
-    let x = u256_to_fr(x).unwrap();
-    let y = u256_to_fr(y).unwrap();
+pub(crate) fn try_point_from_xy(x: &U256, y: &U256) -> Option<Point<Bn256, Unknown>> {
+    let x = u256_to_fr(x).ok()?;
+    let y = u256_to_fr(y).ok()?;
 
-    Point::from_xy(x, y, &JUBJUB_PARAMS as &AltJubjubBn256).unwrap()
+    Point::from_xy(x, y, &JUBJUB_PARAMS as &AltJubjubBn256)
 }
 
 impl JubjubSignature {
+    #[deprecated(note = "panics on an invalid signature, use try_from_str")]
     pub fn from_str(r: &str, s: &str) -> Self {
+        Self::try_from_str(r, s).unwrap()
+    }
+
+    pub fn try_from_str(r: &str, s: &str) -> crate::error::Result<Self> {
         let r_str = r.trim_start_matches("0x").trim_start_matches("0X");
-        let r = U256::from_str_radix(r_str, 16).unwrap();
+        let r = U256::from_str_radix(r_str, 16)
+            .map_err(|e| ZkdexError::InvalidSignature(format!("r: {}", e)))?;
 
         let s_str = s.trim_start_matches("0x").trim_start_matches("0X");
-        let s = U256::from_str_radix(s_str, 16).unwrap();
-        signature_from_rs(&r, &s)
+        let s = U256::from_str_radix(s_str, 16)
+            .map_err(|e| ZkdexError::InvalidSignature(format!("s: {}", e)))?;
+        try_signature_from_rs(&r, &s)
     }
 }
 
@@ -153,7 +245,11 @@ mod tests {
     use serde::{Deserialize, Serialize};
     use std::convert::TryInto;
 
-    use crate::tx::packed_public_key::fr_to_u256;
+    use franklin_crypto::bellman::pairing::ff::Field;
+
+    use crate::other_test::Bencher;
+    use crate::tx::packed_public_key::{fr_to_u256, public_key_from_private};
+    use crate::tx::sign::{gen_test_pk, TxSignature};
 
     use super::*;
 
@@ -174,7 +270,7 @@ mod tests {
             .try_into()
             .unwrap();
         let s: [u8; 32] = hex::decode(SIGNATURE_1_0_S).unwrap().try_into().unwrap();
-        let (x, y) = get_xy_from_r(&le_to_u256(&r));
+        let (x, y) = try_get_xy_from_r(&le_to_u256(&r)).unwrap();
         let x = fr_to_u256(&x).unwrap();
         let y = fr_to_u256(&y).unwrap();
 
@@ -192,4 +288,99 @@ mod tests {
         assert_eq!(base.signature.sig_r.x, base2.signature.sig_r.x);
         assert_eq!(base.signature.sig_r.y, base2.signature.sig_r.y);
     }
+
+    #[test]
+    fn test_serialize_r_off_curve() {
+        let signature = JubjubSignature {
+            sig_r: BabyJubjubPoint {
+                x: U256::one(),
+                y: U256::one(),
+            },
+            sig_s: [1, 0, 0, 0],
+        };
+        let err = serde_json::to_string(&signature).unwrap_err();
+        assert_eq!(err.to_string(), "r is not on the curve");
+    }
+
+    fn packed(point: &Point<Bn256, Unknown>) -> U256 {
+        let mut bytes = [0u8; 32];
+        point.write(bytes.as_mut()).unwrap();
+        le_to_u256(&bytes)
+    }
+
+    #[test]
+    fn test_prime_order_point() {
+        let public_key = public_key_from_private(&gen_test_pk());
+        let point = try_prime_order_point(&public_key.0).unwrap();
+        assert_eq!(packed(&point), public_key.0);
+
+        let mut minus_one = Fr::one();
+        minus_one.negate();
+        let order_two = Point::<Bn256, Unknown>::from_xy(
+            Fr::zero(),
+            minus_one,
+            &JUBJUB_PARAMS as &AltJubjubBn256,
+        )
+        .unwrap();
+        let mixed = point.add(&order_two, &JUBJUB_PARAMS);
+
+        let cases = [
+            (U256::MAX, PointError::NotOnCurve),
+            (U256::one(), PointError::Identity),
+            (packed(&order_two), PointError::SmallOrder),
+            (packed(&mixed), PointError::NotInSubgroup),
+        ];
+        for (packed, err) in cases {
+            assert_eq!(try_prime_order_point(&packed).unwrap_err(), err);
+        }
+    }
+
+    #[test]
+    fn test_verify_prepared() {
+        let (public_key, msg, signature) = signed_hash();
+        let table = FixedBaseTable::for_public_key(&public_key);
+
+        let mut other = msg;
+        other[0] ^= 1;
+        for (msg, valid) in [
+            (&msg[..], true),
+            (&other[..], false),
+            (&[9u8; 33][..], false),
+        ] {
+            assert_eq!(signature.verify_prepared(&public_key, None, msg), valid);
+            assert_eq!(
+                signature.verify_prepared(&public_key, Some(&table), msg),
+                valid
+            );
+        }
+        assert!(signature.verify(&public_key, &msg));
+    }
+
+    fn signed_hash() -> (PublicKey<Bn256>, [u8; 32], PackedSignature) {
+        let key = gen_test_pk();
+        let public_key =
+            PublicKey::from_private(&key, FixedGenerators::SpendingKeyGenerator, &JUBJUB_PARAMS);
+        let msg = [9u8; 32];
+        let (signature, _) = TxSignature::sign_msg(&key, &msg);
+        (public_key, msg, signature.signature)
+    }
+
+    #[bench]
+    fn bench_verify(b: &mut Bencher) {
+        let (public_key, msg, signature) = signed_hash();
+        b.iter(|| assert!(signature.verify(&public_key, &msg)))
+    }
+
+    #[bench]
+    fn bench_verify_prepared(b: &mut Bencher) {
+        let (public_key, msg, signature) = signed_hash();
+        b.iter(|| assert!(signature.verify_prepared(&public_key, None, &msg)))
+    }
+
+    #[bench]
+    fn bench_verify_prepared_with_key_table(b: &mut Bencher) {
+        let (public_key, msg, signature) = signed_hash();
+        let table = FixedBaseTable::for_public_key(&public_key);
+        b.iter(|| assert!(signature.verify_prepared(&public_key, Some(&table), &msg)))
+    }
 }
@@ -0,0 +1,296 @@
+//! A private key that is parsed once, reused for many signatures and wiped when dropped.
+
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use franklin_crypto::bellman::pairing::ff::Field;
+use franklin_crypto::eddsa::{PrivateKey, PublicKey};
+use once_cell::sync::OnceCell;
+use pairing_ce::bn256::Bn256;
+
+use crate::error::{Result, ZkdexError};
+use crate::felt::LeBytesConvert;
+use crate::parse_hash;
+use crate::tx::fixed_base::FixedBaseTable;
+use crate::tx::packed_public_key::{
+    pack_public_key, private_key_from_string, public_point_from_private, random_private_key,
+    PackedPublicKey, PrivateKeyType,
+};
+use crate::tx::sign::{NonceMode, TxSignature};
+use crate::unified::transactions::UnifiedTransaction;
+use crate::unified::{sign_transaction, AnySignature};
+use crate::validate::Validate;
+use crate::zkw::JubjubSignature;
+
+/// Neither `Debug` nor any other trait of this type shows the key, only its public key.
+///
+/// The public key is computed once, a signature then costs a single scalar multiplication.
+pub struct SecretKey {
+    private_key: PrivateKeyType,
+    public_key: PackedPublicKey,
+    public_point: PublicKey<Bn256>,
+    /// Built by the first signature checked with `verify_signatures`.
+    public_table: OnceCell<FixedBaseTable>,
+    nonce_mode: NonceMode,
+    verify_signatures: bool,
+}
+
+impl SecretKey {
+    /// Parse a private key in hex format, the intermediate bytes are wiped.
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        let private_key = private_key_from_string(private_key)?;
+        Ok(Self::from_private_key(private_key))
+    }
+
+    /// A new key from the os CSPRNG, see [`random_private_key`].
+    pub fn generate() -> Result<Self> {
+        Ok(Self::from_private_key(random_private_key()?))
+    }
+
+    pub(crate) fn from_private_key(private_key: PrivateKeyType) -> Self {
+        let public_point = public_point_from_private(&private_key);
+        Self {
+            private_key,
+            public_key: pack_public_key(&public_point),
+            public_point,
+            public_table: OnceCell::new(),
+            nonce_mode: NonceMode::default(),
+            verify_signatures: false,
+        }
+    }
+
+    /// Sign with `mode` from now on, the key signs deterministically unless told otherwise.
+    pub fn with_nonce_mode(mut self, mode: NonceMode) -> Self {
+        self.nonce_mode = mode;
+        self
+    }
+
+    pub fn set_nonce_mode(&mut self, mode: NonceMode) {
+        self.nonce_mode = mode;
+    }
+
+    pub fn nonce_mode(&self) -> NonceMode {
+        self.nonce_mode
+    }
+
+    /// Check every signature against the public key before handing it out, a signature that does
+    /// not verify, say after a hardware fault, is an `INVALID_SIGNATURE` error. Off by default, it
+    /// about doubles the cost of signing.
+    pub fn with_verify_signatures(mut self, verify: bool) -> Self {
+        self.verify_signatures = verify;
+        self
+    }
+
+    pub fn set_verify_signatures(&mut self, verify: bool) {
+        self.verify_signatures = verify;
+    }
+
+    pub fn verify_signatures(&self) -> bool {
+        self.verify_signatures
+    }
+
+    /// Another key with the same private key and settings, for a key that is shared.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            private_key: PrivateKey(self.private_key.0),
+            public_key: self.public_key.clone(),
+            public_point: PublicKey(self.public_point.0.clone()),
+            public_table: OnceCell::new(),
+            nonce_mode: self.nonce_mode,
+            verify_signatures: self.verify_signatures,
+        }
+    }
+
+    pub fn public_key(&self) -> &PackedPublicKey {
+        &self.public_key
+    }
+
+    pub(crate) fn private_key(&self) -> &PrivateKeyType {
+        &self.private_key
+    }
+
+    /// Sign the little endian `hash` with the settings of this key.
+    pub(crate) fn sign_hash(&self, hash: &[u8]) -> Result<TxSignature> {
+        let signature = TxSignature::sign_with_public_key(
+            &self.private_key,
+            &self.public_key,
+            hash,
+            self.nonce_mode,
+        );
+        if self.verify_signatures && !self.verify_hash(&signature, hash) {
+            return Err(ZkdexError::InvalidSignature(
+                "the signature does not verify against the key".to_string(),
+            ));
+        }
+        Ok(signature)
+    }
+
+    fn verify_hash(&self, signature: &TxSignature, hash: &[u8]) -> bool {
+        let table = self
+            .public_table
+            .get_or_init(|| FixedBaseTable::for_public_key(&self.public_point));
+        signature
+            .signature
+            .verify_prepared(&self.public_point, Some(table), hash)
+    }
+
+    /// Same as [`crate::sign`] with this key.
+    pub fn sign(&self, msg: &str) -> Result<JubjubSignature> {
+        let hash = parse_hash(msg)?;
+        Ok(self.sign_hash(hash.as_le_bytes())?.into())
+    }
+
+    /// Same as [`crate::sign_any`] with this key.
+    pub fn sign_any(&self, json: &str) -> Result<AnySignature> {
+        let tx = UnifiedTransaction::from_json(json)?;
+        tx.validate(None)?;
+        sign_transaction(&tx, self)
+    }
+
+    /// Same as [`crate::sign_any_unchecked`] with this key.
+    pub fn sign_any_unchecked(&self, json: &str) -> Result<AnySignature> {
+        let tx = UnifiedTransaction::from_json(json)?;
+        sign_transaction(&tx, self)
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = ZkdexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_hex(s)
+    }
+}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretKey({})", self.public_key.to_string())
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        // a volatile write cannot be optimized away as a dead store
+        unsafe { std::ptr::write_volatile(&mut self.private_key, PrivateKey(Field::zero())) };
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::other_test::Bencher;
+    use crate::tx::packed_public_key::public_key_from_private;
+    use crate::unified::transactions::test::PRI_KEY;
+    use crate::{sign, sign_any};
+
+    const PUB_KEY: &str = "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a";
+
+    #[test]
+    fn test_secret_key_signs_like_hex_key() {
+        let key: SecretKey = PRI_KEY.parse().unwrap();
+        assert_eq!(
+            key.public_key().to_string(),
+            "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+        );
+
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        for _ in 0..2 {
+            assert_eq!(key.sign(msg).unwrap(), sign(PRI_KEY, msg).unwrap());
+        }
+
+        let withdrawal = r#"{
+            "type": "UNIFIED_WITHDRAWAL",
+            "amount": "1682637359498011204",
+            "eth_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+            "expiration_timestamp": "2101470722",
+            "asset_id": "0x11111",
+            "nonce": "4265854110",
+            "position_id": "775817640",
+            "fee": "0",
+            "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+            "chain_id": "123"
+        }"#;
+        assert_eq!(
+            key.sign_any(withdrawal).unwrap(),
+            sign_any(withdrawal, PRI_KEY).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_secret_key_hides_private_key() {
+        let key = SecretKey::from_hex(PRI_KEY).unwrap();
+        let debug = format!("{:?}", key);
+        assert_eq!(
+            debug,
+            "SecretKey(0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a)"
+        );
+        assert!(!debug.contains("01e1b55a"));
+
+        let err = SecretKey::from_hex("0xzz").unwrap_err();
+        assert_eq!(err.code(), "INVALID_PRIVATE_KEY");
+    }
+
+    #[test]
+    fn test_generate() {
+        let key = SecretKey::generate().unwrap();
+        let other = SecretKey::generate().unwrap();
+        assert_ne!(key.public_key(), other.public_key());
+        assert_eq!(
+            &public_key_from_private(key.private_key()),
+            key.public_key()
+        );
+    }
+
+    #[test]
+    fn test_nonce_mode() {
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        let mut key = SecretKey::from_hex(PRI_KEY).unwrap();
+        assert_eq!(key.nonce_mode(), NonceMode::Deterministic);
+        let deterministic = key.sign(msg).unwrap();
+
+        key.set_nonce_mode(NonceMode::Hedged);
+        let hedged = key.sign(msg).unwrap();
+        assert_ne!(hedged, deterministic);
+        assert_ne!(hedged, key.sign(msg).unwrap());
+        assert!(crate::verify_jubjub_signature(hedged, PUB_KEY, msg).unwrap());
+
+        let mut copy = key.duplicate();
+        assert_eq!(copy.public_key(), key.public_key());
+        assert_eq!(copy.nonce_mode(), NonceMode::Hedged);
+        copy.set_nonce_mode(NonceMode::Deterministic);
+        assert_eq!(copy.sign(msg).unwrap(), deterministic);
+    }
+
+    #[test]
+    fn test_verify_signatures() {
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        let key = SecretKey::from_hex(PRI_KEY).unwrap();
+        assert!(!key.verify_signatures());
+        let checked = key.duplicate().with_verify_signatures(true);
+        assert_eq!(checked.sign(msg).unwrap(), key.sign(msg).unwrap());
+
+        // a key whose cached public key is wrong stands in for a fault while signing
+        let mut faulty = checked.duplicate();
+        faulty.public_point = SecretKey::generate().unwrap().public_point;
+        assert!(faulty.public_table.get().is_none());
+        let err = faulty.sign(msg).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        faulty.set_verify_signatures(false);
+        assert!(faulty.sign(msg).is_ok());
+    }
+
+    #[bench]
+    fn bench_secret_key_sign(b: &mut Bencher) {
+        let key = SecretKey::from_hex(PRI_KEY).unwrap();
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        b.iter(|| key.sign(msg).unwrap())
+    }
+
+    #[bench]
+    fn bench_sign_with_hex_key(b: &mut Bencher) {
+        let msg = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+        b.iter(|| sign(PRI_KEY, msg).unwrap())
+    }
+}
@@ -1,25 +1,56 @@
+use std::convert::TryFrom;
 use std::fmt::{Debug, Formatter};
-use std::thread::sleep;
-use std::time::Duration;
+use std::str::FromStr;
 
-use ef::ff::{PrimeField, PrimeFieldRepr};
+use ef::ff::{Field, PrimeField, PrimeFieldRepr};
 use franklin_crypto::alt_babyjubjub::fs::{Fs, FsRepr};
 use franklin_crypto::eddsa::{PrivateKey, PublicKey, Seed, Signature};
 use franklin_crypto::jubjub::FixedGenerators;
 use pairing_ce as ef;
 use pairing_ce::bn256::Bn256;
 use primitive_types::U256;
-use rand::{Rng, SeedableRng, XorShiftRng};
-use time::OffsetDateTime;
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
 
+use crate::error::ZkdexError;
 use crate::tx::convert::FeConvert;
 use crate::tx::packed_public_key::{
-    fr_to_u256, public_key_from_private, public_key_from_private_with_verify, PackedPublicKey,
+    fr_to_u256, new_private_key, public_key_from_private, PackedPublicKey,
 };
-use crate::tx::packed_signature::{point_from_xy, PackedSignature};
-use crate::tx::{le_to_u256, u256_to_h256, JUBJUB_PARAMS};
+use crate::tx::packed_signature::{
+    challenge, check_prime_order, try_point_from_xy, PackedSignature,
+};
+use crate::tx::{fs_from_le_bytes_wide, le_to_u256, u256_to_h256, GENERATOR_TABLE, JUBJUB_PARAMS};
 use crate::zkw::{BabyJubjubPoint, JubjubSignature};
 
+const HEDGED_NONCE: &[u8] = b"zkdex hedged nonce";
+
+/// How the nonce of a signature is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonceMode {
+    /// From the key and the message only, a message always gets the same signature.
+    #[default]
+    Deterministic,
+    /// From the key, the message and fresh randomness, so a fault or side channel observed while
+    /// signing cannot be repeated by asking for the same signature again.
+    Hedged,
+}
+
+impl FromStr for NonceMode {
+    type Err = ZkdexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deterministic" => Ok(NonceMode::Deterministic),
+            "hedged" => Ok(NonceMode::Hedged),
+            _ => Err(ZkdexError::InvalidArgument(format!(
+                "nonce mode {}: should be deterministic or hedged",
+                s
+            ))),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TxSignature {
     pub pub_key: PackedPublicKey,
@@ -53,36 +84,81 @@ impl Into<JubjubSignature> for TxSignature {
     }
 }
 
-impl From<JubjubSignature> for PackedSignature {
-    fn from(value: JubjubSignature) -> Self {
-        let r = point_from_xy(&value.sig_r.x, &value.sig_r.y);
+impl TryFrom<&JubjubSignature> for PackedSignature {
+    type Error = ZkdexError;
+
+    fn try_from(value: &JubjubSignature) -> Result<Self, Self::Error> {
+        let r = try_point_from_xy(&value.sig_r.x, &value.sig_r.y)
+            .ok_or_else(|| ZkdexError::InvalidSignature("r is not on the curve".to_string()))?;
+        check_prime_order(&r).map_err(|e| ZkdexError::InvalidSignature(format!("r is {}", e)))?;
         let s = u256_to_h256(U256(value.sig_s)).0;
         let mut fspr = FsRepr::default();
-        fspr.read_le(&s[..]).unwrap();
-        let s = Fs::from_repr(fspr).unwrap();
-        PackedSignature {
+        fspr.read_le(&s[..])
+            .map_err(|e| ZkdexError::InvalidSignature(e.to_string()))?;
+        let s = Fs::from_repr(fspr).map_err(|_| {
+            ZkdexError::InvalidSignature("s is not below the group order".to_string())
+        })?;
+        Ok(PackedSignature {
             0: Signature { r: r, s: s },
-        }
+        })
     }
 }
 
 impl TxSignature {
     pub fn sign_msg(pk: &PrivateKey<Bn256>, msg: &[u8]) -> (TxSignature, PackedPublicKey) {
-        let ret = Self::sign_raw(pk, msg);
+        Self::sign_msg_with(pk, msg, NonceMode::Deterministic)
+    }
+
+    pub fn sign_msg_with(
+        pk: &PrivateKey<Bn256>,
+        msg: &[u8],
+        mode: NonceMode,
+    ) -> (TxSignature, PackedPublicKey) {
+        let ret = Self::sign_raw_with(pk, msg, mode);
         let pubkey = ret.pub_key.clone();
         (ret, pubkey)
     }
+
     pub fn sign_raw(pk: &PrivateKey<Bn256>, hash_msg: &[u8]) -> Self {
-        let seed = Seed::deterministic_seed(pk, &hash_msg);
-        let signature = pk.sign_raw_message(
-            &hash_msg,
-            &seed,
-            FixedGenerators::SpendingKeyGenerator,
-            &JUBJUB_PARAMS,
-            hash_msg.len(), /* usize */
-        );
+        Self::sign_raw_with(pk, hash_msg, NonceMode::Deterministic)
+    }
+
+    pub fn sign_raw_with(pk: &PrivateKey<Bn256>, hash_msg: &[u8], mode: NonceMode) -> Self {
+        Self::sign_with_public_key(pk, &public_key_from_private(pk), hash_msg, mode)
+    }
+
+    /// Sign with the public key of `pk` at hand, so signing costs a single scalar multiplication.
+    /// The signature is not checked, [`crate::SecretKey`] can do that on request.
+    pub fn sign_with_public_key(
+        pk: &PrivateKey<Bn256>,
+        pub_key: &PackedPublicKey,
+        hash_msg: &[u8],
+        mode: NonceMode,
+    ) -> Self {
+        let seed = match mode {
+            NonceMode::Deterministic => Seed::deterministic_seed(pk, &hash_msg),
+            NonceMode::Hedged => hedged_seed(pk, hash_msg),
+        };
+        // R = r * G and s = r + c * k as franklin computes them, R from the generator table
+        let signature = match challenge(hash_msg) {
+            Some(mut s) => {
+                s.mul_assign(&pk.0);
+                s.add_assign(&seed.0);
+                Signature {
+                    r: GENERATOR_TABLE.mul(&seed.0),
+                    s,
+                }
+            }
+            None => pk.sign_raw_message(
+                &hash_msg,
+                &seed,
+                FixedGenerators::SpendingKeyGenerator,
+                &JUBJUB_PARAMS,
+                hash_msg.len(), /* usize */
+            ),
+        };
         Self {
-            pub_key: public_key_from_private_with_verify(pk, hash_msg, &signature),
+            pub_key: pub_key.clone(),
             signature: PackedSignature(signature),
         }
     }
@@ -92,21 +168,31 @@ impl TxSignature {
     }
 }
 
+/// The nonce seed `SHA-512("zkdex hedged nonce" || k || z || msg)` read as a scalar, `z` being 32
+/// fresh random bytes. When the os has no randomness `z` stays zero and the nonce is still bound
+/// to the key and the message as a deterministic one is.
+fn hedged_seed(pk: &PrivateKey<Bn256>, msg: &[u8]) -> Seed<Bn256> {
+    let mut entropy = Zeroizing::new([0u8; 32]);
+    if getrandom::getrandom(entropy.as_mut()).is_err() {
+        *entropy = [0u8; 32];
+    }
+    let mut hasher = Sha512::new();
+    hasher.input(HEDGED_NONCE);
+    hasher.input(Zeroizing::new(pk.0.to_bytes()).as_slice());
+    hasher.input(entropy.as_ref());
+    hasher.input(msg);
+    let mut wide = Zeroizing::new([0u8; 64]);
+    wide.copy_from_slice(&hasher.result());
+    Seed(fs_from_le_bytes_wide(&wide))
+}
+
 pub fn gen_test_pk() -> PrivateKey<Bn256> {
     let ss = "0x057afe7e950189b17eedfd749f5537a88eb3ed4981467636a115e5c3efcce0f4";
     PrivateKey::<Bn256>(Fs::from_bytes(&*hex::decode(&ss[2..]).unwrap()).unwrap())
 }
 
 pub fn gen_random_key() -> PrivateKey<Bn256> {
-    sleep(Duration::from_millis(100));
-    let ts_nanos = OffsetDateTime::now_utc().unix_timestamp_nanos();
-    let mut rng = XorShiftRng::from_seed([
-        (ts_nanos as u32 & u32::MAX),
-        ((ts_nanos >> 32) as u32) & u32::MAX,
-        ((ts_nanos >> 64) as u32) & u32::MAX,
-        ((ts_nanos >> 96) as u32) & u32::MAX,
-    ]);
-    PrivateKey(rng.gen())
+    new_private_key()
 }
 
 pub fn gen_couple() -> (PrivateKey<Bn256>, PackedPublicKey) {
@@ -117,7 +203,13 @@ pub fn gen_couple() -> (PrivateKey<Bn256>, PackedPublicKey) {
 
 #[cfg(test)]
 mod tests {
+    use rand::Rng;
+
+    use crate::felt::LeBytesConvert;
+    use crate::hash::poseidon_hash_u64_limbs;
+    use crate::other_test::Bencher;
     use crate::tx;
+    use crate::tx::packed_public_key::pack_public_key;
 
     use super::*;
 
@@ -134,10 +226,117 @@ mod tests {
 
         assert!(sig.0.verify(&pub_key, &msg));
         let a1 = sig.0.signature.clone();
-        let a2 = PackedSignature::from(<tx::sign::TxSignature as Into<JubjubSignature>>::into(
-            sig.0,
-        ));
+        let a2 = PackedSignature::try_from(
+            &<tx::sign::TxSignature as Into<JubjubSignature>>::into(sig.0),
+        )
+        .unwrap();
         println!("{:#?}", a1);
         println!("{:#?}", a2);
     }
+
+    fn assert_table_sign_matches_franklin(key: &PrivateKey<Bn256>, msg: &[u8]) {
+        let seed = Seed::deterministic_seed(key, msg);
+        let expected = key.sign_raw_message(
+            msg,
+            &seed,
+            FixedGenerators::SpendingKeyGenerator,
+            &JUBJUB_PARAMS,
+            msg.len(),
+        );
+        let (signature, pub_key) = TxSignature::sign_msg(key, msg);
+        assert!(signature.signature.0.r.eq(&expected.r));
+        assert_eq!(signature.signature.0.s, expected.s);
+        assert_eq!(
+            pub_key,
+            pack_public_key(&PublicKey::from_private(
+                key,
+                FixedGenerators::SpendingKeyGenerator,
+                &JUBJUB_PARAMS
+            ))
+        );
+    }
+
+    #[test]
+    fn test_table_sign_matches_franklin() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..8 {
+            let key = PrivateKey::<Bn256>(rng.gen());
+            let mut msg = [0u8; 32];
+            rng.fill(&mut msg[..]);
+            assert_table_sign_matches_franklin(&key, &msg);
+        }
+    }
+
+    #[test]
+    fn test_table_sign_matches_franklin_on_hashes() {
+        let key = gen_test_pk();
+        let mut rng = rand::thread_rng();
+        let mut top_bits = 0;
+        for _ in 0..32 {
+            let limbs: [u64; 8] = rng.gen();
+            let hash = poseidon_hash_u64_limbs(&limbs);
+            top_bits |= hash.0[3] >> 61;
+            assert_table_sign_matches_franklin(&key, hash.as_le_bytes());
+        }
+        // hashes run up to the 254 bit field modulus, past the 251 bit group order
+        assert_ne!(top_bits, 0);
+    }
+
+    #[bench]
+    fn bench_sign_franklin(b: &mut Bencher) {
+        let key = gen_test_pk();
+        let hash = poseidon_hash_u64_limbs(&[1, 2, 3, 4]);
+        let msg = hash.as_le_bytes();
+        b.iter(|| {
+            let seed = Seed::deterministic_seed(&key, msg);
+            key.sign_raw_message(
+                msg,
+                &seed,
+                FixedGenerators::SpendingKeyGenerator,
+                &JUBJUB_PARAMS,
+                msg.len(),
+            )
+        })
+    }
+
+    #[bench]
+    fn bench_sign_table(b: &mut Bencher) {
+        let key = gen_test_pk();
+        let pub_key = public_key_from_private(&key);
+        let hash = poseidon_hash_u64_limbs(&[1, 2, 3, 4]);
+        b.iter(|| {
+            TxSignature::sign_with_public_key(
+                &key,
+                &pub_key,
+                hash.as_le_bytes(),
+                NonceMode::Deterministic,
+            )
+        })
+    }
+
+    #[test]
+    fn test_hedged_sign() {
+        let key = gen_test_pk();
+        let pub_key =
+            PublicKey::from_private(&key, FixedGenerators::SpendingKeyGenerator, &JUBJUB_PARAMS);
+        let msg = [7u8; 32];
+
+        let signature = |mode| -> JubjubSignature {
+            let (signature, _) = TxSignature::sign_msg_with(&key, &msg, mode);
+            assert!(signature.verify(&pub_key, &msg));
+            signature.into()
+        };
+        let (deterministic, _) = TxSignature::sign_msg(&key, &msg);
+        let deterministic: JubjubSignature = deterministic.into();
+        assert_eq!(signature(NonceMode::Deterministic), deterministic);
+
+        let hedged = signature(NonceMode::Hedged);
+        assert_ne!(hedged, signature(NonceMode::Hedged));
+        assert_ne!(hedged, deterministic);
+
+        assert_eq!("hedged".parse::<NonceMode>().unwrap(), NonceMode::Hedged);
+        assert_eq!(NonceMode::default(), NonceMode::Deterministic);
+        let err = "random".parse::<NonceMode>().unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+    }
 }
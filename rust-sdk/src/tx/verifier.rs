@@ -0,0 +1,406 @@
+//! Signature verification with a cache of public keys.
+//!
+//! Decompressing a packed public key and checking that it lies in the prime order subgroup costs
+//! about as much as the signature check itself. A [`Verifier`] keeps the keys it has seen, up to
+//! its capacity, and drops the least recently used one when full. Keys that fail validation are
+//! never cached.
+//!
+//! A key looked up [`KEY_TABLE_AFTER_USES`] times also gets a [`FixedBaseTable`] of its multiples,
+//! so its next verifications skip the variable base multiplication. Building the table costs about
+//! three such multiplications, a key seen once or twice is not worth it. A table takes about
+//! 120 KB, a verifier keeps at most [`KEY_TABLES_MAX`] of them and drops a table with its key.
+//!
+//! [`VERIFIER`] is shared by [`crate::verify_jubjub_signature`] and the other free functions that
+//! verify a signature.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use franklin_crypto::eddsa::PublicKey;
+use lazy_static::lazy_static;
+use once_cell::sync::OnceCell;
+use pairing_ce::bn256::Bn256;
+use primitive_types::U256;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::felt::LeBytesConvert;
+use crate::parse_hash;
+use crate::tx::fixed_base::FixedBaseTable;
+use crate::tx::packed_public_key::PackedPublicKey;
+use crate::tx::packed_signature::PackedSignature;
+use crate::zkw::JubjubSignature;
+
+/// The lookups of a cached key, the first one included, after which it gets a table.
+pub const KEY_TABLE_AFTER_USES: u32 = 4;
+/// The most key tables a [`Verifier`] keeps.
+pub const KEY_TABLES_MAX: usize = 64;
+
+lazy_static! {
+    /// The verifier behind the free verify functions.
+    pub static ref VERIFIER: Verifier = Verifier::new(1024);
+}
+
+pub struct Verifier {
+    cache: Mutex<PublicKeyCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct VerifierStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that decompressed the key, including the keys that turned out invalid.
+    pub misses: u64,
+    /// Keys in the cache.
+    pub len: usize,
+    /// Keys in the cache with a table.
+    pub tables: usize,
+    pub capacity: usize,
+}
+
+/// A decompressed and validated public key.
+pub struct VerifyingKey {
+    public_key: PublicKey<Bn256>,
+    table: OnceCell<FixedBaseTable>,
+}
+
+impl VerifyingKey {
+    fn new(public_key: PublicKey<Bn256>) -> Self {
+        Self {
+            public_key,
+            table: OnceCell::new(),
+        }
+    }
+
+    pub fn public_key(&self) -> &PublicKey<Bn256> {
+        &self.public_key
+    }
+
+    pub fn has_table(&self) -> bool {
+        self.table.get().is_some()
+    }
+
+    /// Whether `signature` is the signature of the key on `msg`.
+    pub fn verify(&self, signature: &PackedSignature, msg: &[u8]) -> bool {
+        signature.verify_prepared(&self.public_key, self.table.get(), msg)
+    }
+
+    fn build_table(&self) {
+        self.table
+            .get_or_init(|| FixedBaseTable::for_public_key(&self.public_key));
+    }
+}
+
+impl Verifier {
+    /// A verifier caching up to `capacity` keys, with 0 every key is decompressed on each call.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(PublicKeyCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Same as [`crate::verify_signature`].
+    pub fn verify_signature(
+        &self,
+        sig_r: &str,
+        sig_s: &str,
+        pub_key: &str,
+        msg: &str,
+    ) -> Result<bool> {
+        let sig = JubjubSignature::try_from_str(sig_r, sig_s)?;
+        self.verify_jubjub_signature(&sig, pub_key, msg)
+    }
+
+    /// Same as [`crate::verify_jubjub_signature`].
+    pub fn verify_jubjub_signature(
+        &self,
+        sig: &JubjubSignature,
+        pub_key: &str,
+        msg: &str,
+    ) -> Result<bool> {
+        let sig = PackedSignature::try_from(sig)?;
+        let msg = parse_hash(msg)?;
+        let pub_key = PackedPublicKey::try_from(pub_key)?;
+        self.verify(&pub_key, &sig, msg.as_le_bytes())
+    }
+
+    /// Whether `signature` is the signature of `packed` on `msg`.
+    pub fn verify(
+        &self,
+        packed: &PackedPublicKey,
+        signature: &PackedSignature,
+        msg: &[u8],
+    ) -> Result<bool> {
+        Ok(self.verifying_key(packed)?.verify(signature, msg))
+    }
+
+    /// The decompressed and validated `packed` key, from the cache when it is there.
+    pub fn verifying_key(&self, packed: &PackedPublicKey) -> Result<Arc<VerifyingKey>> {
+        let cached = self.cache.lock().unwrap().get(&packed.0);
+        if let Some((key, build_table)) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            if build_table {
+                key.build_table();
+            }
+            return Ok(key);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        // outside the lock, so other threads keep hitting the cache meanwhile
+        let key = Arc::new(VerifyingKey::new(packed.try_into_public_key()?));
+        self.cache.lock().unwrap().insert(packed.0, key.clone());
+        Ok(key)
+    }
+
+    pub fn stats(&self) -> VerifierStats {
+        let cache = self.cache.lock().unwrap();
+        VerifierStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: cache.keys.len(),
+            tables: cache.tables,
+            capacity: cache.capacity,
+        }
+    }
+
+    /// Drop the cached keys and reset the counters.
+    pub fn clear(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.keys.clear();
+        cache.recency.clear();
+        cache.tables = 0;
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+/// A least recently used cache, each key carries the tick of its last use.
+struct PublicKeyCache {
+    capacity: usize,
+    tick: u64,
+    keys: HashMap<U256, CachedKey>,
+    /// The keys by the tick of their last use, the first one is evicted next.
+    recency: BTreeMap<u64, U256>,
+    /// Keys given a table, at most [`KEY_TABLES_MAX`].
+    tables: usize,
+}
+
+struct CachedKey {
+    key: Arc<VerifyingKey>,
+    used: u64,
+    uses: u32,
+    with_table: bool,
+}
+
+impl PublicKeyCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            keys: HashMap::new(),
+            recency: BTreeMap::new(),
+            tables: 0,
+        }
+    }
+
+    /// The cached key and whether the caller should build its table.
+    fn get(&mut self, packed: &U256) -> Option<(Arc<VerifyingKey>, bool)> {
+        self.tick += 1;
+        let cached = self.keys.get_mut(packed)?;
+        self.recency.remove(&cached.used);
+        cached.used = self.tick;
+        cached.uses = cached.uses.saturating_add(1);
+        self.recency.insert(self.tick, *packed);
+        let build_table = !cached.with_table
+            && cached.uses >= KEY_TABLE_AFTER_USES
+            && self.tables < KEY_TABLES_MAX;
+        if build_table {
+            cached.with_table = true;
+            self.tables += 1;
+        }
+        Some((cached.key.clone(), build_table))
+    }
+
+    fn insert(&mut self, packed: U256, key: Arc<VerifyingKey>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        let cached = CachedKey {
+            key,
+            used: self.tick,
+            uses: 1,
+            with_table: false,
+        };
+        if let Some(replaced) = self.keys.insert(packed, cached) {
+            // another thread inserted the key since our lookup
+            self.recency.remove(&replaced.used);
+            self.drop_table(&replaced);
+        } else if self.keys.len() > self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                if let Some(evicted) = self.keys.remove(&oldest) {
+                    self.drop_table(&evicted);
+                }
+            }
+        }
+        self.recency.insert(self.tick, packed);
+    }
+
+    fn drop_table(&mut self, cached: &CachedKey) {
+        if cached.with_table {
+            self.tables -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::other_test::Bencher;
+    use crate::unified::transactions::test::PRI_KEY;
+    use crate::{generate_private_key, sign, verify_jubjub_signature, SecretKey};
+
+    const MSG: &str = "0x0a7d1a7e5c2d6b5d7a0d7f9e1c3f2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b";
+
+    fn signed_by(private_key: &str) -> (String, JubjubSignature) {
+        let public_key = SecretKey::from_hex(private_key)
+            .unwrap()
+            .public_key()
+            .to_string();
+        (public_key, sign(private_key, MSG).unwrap())
+    }
+
+    fn stats(verifier: &Verifier) -> (u64, u64, usize, usize) {
+        let stats = verifier.stats();
+        (stats.hits, stats.misses, stats.len, stats.tables)
+    }
+
+    #[test]
+    fn test_verifier_caches_keys() {
+        let verifier = Verifier::new(2);
+        let (public_key, sig) = signed_by(PRI_KEY);
+        for _ in 0..3 {
+            assert!(verifier
+                .verify_jubjub_signature(&sig, &public_key, MSG)
+                .unwrap());
+        }
+        assert_eq!(stats(&verifier), (2, 1, 1, 0));
+        // the fourth lookup builds the table
+        assert_eq!(
+            verifier.verify_jubjub_signature(&sig, &public_key, MSG),
+            verify_jubjub_signature(sig.clone(), &public_key, MSG)
+        );
+        let other = "0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520";
+        assert!(!verifier
+            .verify_jubjub_signature(&sig, &public_key, other)
+            .unwrap());
+
+        let order_two = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        for _ in 0..2 {
+            let err = verifier
+                .verify_jubjub_signature(&sig, order_two, MSG)
+                .unwrap_err();
+            assert_eq!(err.code(), "INVALID_PUBLIC_KEY");
+        }
+        assert_eq!(stats(&verifier), (4, 3, 1, 1));
+
+        verifier.clear();
+        assert_eq!(
+            verifier.stats(),
+            VerifierStats {
+                hits: 0,
+                misses: 0,
+                len: 0,
+                tables: 0,
+                capacity: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_verifier_evicts_least_recently_used() {
+        let verifier = Verifier::new(2);
+        let keys: Vec<_> = (0..3)
+            .map(|_| signed_by(&generate_private_key().unwrap().private_key))
+            .collect();
+        let verify = |i: usize| {
+            let (public_key, sig) = &keys[i];
+            assert!(verifier
+                .verify_jubjub_signature(sig, public_key, MSG)
+                .unwrap());
+        };
+
+        verify(0);
+        verify(1);
+        verify(0);
+        // the cache is full, 1 is the least recently used
+        verify(2);
+        assert_eq!(stats(&verifier), (1, 3, 2, 0));
+        verify(0);
+        verify(2);
+        assert_eq!(stats(&verifier), (3, 3, 2, 0));
+        verify(1);
+        assert_eq!(stats(&verifier), (3, 4, 2, 0));
+
+        let uncached = Verifier::new(0);
+        let (public_key, sig) = &keys[0];
+        for _ in 0..2 {
+            assert!(uncached
+                .verify_jubjub_signature(sig, public_key, MSG)
+                .unwrap());
+        }
+        assert_eq!(stats(&uncached), (0, 2, 0, 0));
+    }
+
+    #[test]
+    fn test_verifier_tables_frequent_keys() {
+        let verifier = Verifier::new(1);
+        let (public_key, sig) = signed_by(PRI_KEY);
+        let packed = PackedPublicKey::try_from(public_key.as_str()).unwrap();
+        for uses in 1..=KEY_TABLE_AFTER_USES {
+            let key = verifier.verifying_key(&packed).unwrap();
+            assert_eq!(key.has_table(), uses == KEY_TABLE_AFTER_USES);
+        }
+        assert_eq!(stats(&verifier), (3, 1, 1, 1));
+        for _ in 0..2 {
+            assert!(verifier
+                .verify_jubjub_signature(&sig, &public_key, MSG)
+                .unwrap());
+        }
+        assert_eq!(stats(&verifier), (5, 1, 1, 1));
+
+        // the table goes with its key
+        let (other_key, other_sig) = signed_by(&generate_private_key().unwrap().private_key);
+        assert!(verifier
+            .verify_jubjub_signature(&other_sig, &other_key, MSG)
+            .unwrap());
+        assert_eq!(stats(&verifier), (5, 2, 1, 0));
+    }
+
+    #[bench]
+    fn bench_verifier_cached_key(b: &mut Bencher) {
+        let verifier = Verifier::new(16);
+        let (public_key, sig) = signed_by(PRI_KEY);
+        b.iter(|| {
+            assert!(verifier
+                .verify_jubjub_signature(&sig, &public_key, MSG)
+                .unwrap())
+        })
+    }
+
+    #[bench]
+    fn bench_verify_without_cache(b: &mut Bencher) {
+        let verifier = Verifier::new(0);
+        let (public_key, sig) = signed_by(PRI_KEY);
+        b.iter(|| {
+            assert!(verifier
+                .verify_jubjub_signature(&sig, &public_key, MSG)
+                .unwrap())
+        })
+    }
+}
@@ -22,7 +22,8 @@ pub type FundingRateType = i128;
 pub type PriceType = u128;
 pub type AmountType = u64;
 pub type BalanceType = i64;
-pub type ConditionType = u64;
+/// A field element, zero for a transfer without condition.
+pub type ConditionType = U256;
 pub type NonceType = u32;
 pub type SpotAmountType = u128;
 pub type SpotAssetIdType = u32;
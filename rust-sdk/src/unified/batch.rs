@@ -0,0 +1,265 @@
+//! Sign, hash or verify many transactions in one call.
+//!
+//! The work is spread over the rayon thread pool, wasm has no threads and runs it in order on the
+//! calling thread. Results always come back in input order.
+
+use std::convert::TryFrom;
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{parse_json, parse_json_value, Result, ZkdexError};
+use crate::serde_wrapper::U256SerdeAsRadix16Prefix0xString;
+use crate::tx::batch_verify::{BatchVerifier, ParsedSignature};
+use crate::tx::packed_public_key::{PackedPublicKey, PrivateKeyType};
+use crate::tx::packed_signature::{try_signature_from_rs, PackedSignature, SignatureOriginal};
+use crate::tx::public_key_type::PublicKeyType;
+use crate::tx::secret_key::SecretKey;
+use crate::types::HashType;
+use crate::unified::transactions::hash_trait::HashTrait;
+use crate::unified::transactions::sign_trait::SignTrait;
+use crate::unified::transactions::UnifiedTransaction;
+use crate::unified::{hash_transaction, sign_transaction, AnyHash, AnySignature};
+use crate::validate::Validate;
+use crate::zkw::JubjubSignature;
+
+/// Sign every transaction with `private_key`, one result per transaction as [`SignTrait::sign`]
+/// refuses a transaction that breaks any bound.
+pub fn batch_sign<T: SignTrait + Sync>(
+    txs: &[T],
+    private_key: &PrivateKeyType,
+) -> Vec<Result<JubjubSignature>> {
+    par_map(txs, |tx| tx.sign(private_key).map(Into::into))
+}
+
+/// Hash every transaction.
+pub fn batch_hash<T: HashTrait + Sync>(txs: &[T]) -> Vec<U256> {
+    par_map(txs, |tx| tx.hash())
+}
+
+/// Sign a json array of unified transactions, each one is handled like [`crate::sign_any`]
+/// json: the transactions in json format
+/// private_key: the private key in hex format
+/// return: one result per transaction, a malformed array or private key fails the whole batch
+pub fn batch_sign_any(json: &str, private_key: &str) -> Result<Vec<Result<AnySignature>>> {
+    let secret_key = SecretKey::from_hex(private_key)?;
+    batch_sign_any_with(json, &secret_key)
+}
+
+/// Same as [`batch_sign_any`] with an already parsed key.
+pub fn batch_sign_any_with(
+    json: &str,
+    secret_key: &SecretKey,
+) -> Result<Vec<Result<AnySignature>>> {
+    let items: Vec<Value> = parse_json(json)?;
+    Ok(par_map(&items, |item| {
+        let tx = UnifiedTransaction::from_value(item.clone())?;
+        tx.validate(None)?;
+        sign_transaction(&tx, secret_key)
+    }))
+}
+
+/// Hash a json array of unified transactions, each one is handled like [`crate::hash_any`]
+/// json: the transactions in json format
+/// return: one result per transaction, a malformed array fails the whole batch
+pub fn batch_hash_any(json: &str) -> Result<Vec<Result<AnyHash>>> {
+    let items: Vec<Value> = parse_json(json)?;
+    Ok(par_map(&items, |item| {
+        let tx = UnifiedTransaction::from_value(item.clone())?;
+        Ok(hash_transaction(&tx))
+    }))
+}
+
+/// A signature of a hash, one item of [`batch_verify`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedHash {
+    pub signature: SignatureOriginal,
+    pub public_key: PublicKeyType,
+    #[serde(with = "U256SerdeAsRadix16Prefix0xString")]
+    pub hash: HashType,
+}
+
+/// Verify a json array of signed hashes `{"signature":{"r","s"},"public_key","hash"}` in one
+/// batch, see [`BatchVerifier`].
+/// json: the signed hashes in json format
+/// return: whether each signature is valid, a malformed item is an error for that item only and a
+/// malformed array fails the whole batch
+pub fn batch_verify(json: &str) -> Result<Vec<Result<bool>>> {
+    let items: Vec<Value> = parse_json(json)?;
+    let parsed: Vec<Result<ParsedSignature>> = par_map(&items, |item| {
+        let item: SignedHash = parse_json_value(item.clone())?;
+        let signature = try_signature_from_rs(&item.signature.r, &item.signature.s)?;
+        let signature = PackedSignature::try_from(&signature)?;
+        let public_key = PackedPublicKey(item.public_key.0).try_into_public_key()?;
+        Ok((public_key, item.hash, signature))
+    });
+    let valid = BatchVerifier::verify_parsed(&parsed);
+    Ok(parsed
+        .into_iter()
+        .zip(valid)
+        .map(|(item, valid)| item.map(|_| valid))
+        .collect())
+}
+
+/// One item of a batch as returned by the bindings, the value itself or
+/// `{"error": {"code": ..., "message": ...}}` when the item failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum BatchItem<T> {
+    Ok(T),
+    Err { error: BatchError },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl<T> From<Result<T>> for BatchItem<T> {
+    fn from(result: Result<T>) -> Self {
+        match result {
+            Ok(value) => BatchItem::Ok(value),
+            Err(e) => BatchItem::Err {
+                error: BatchError::from(e),
+            },
+        }
+    }
+}
+
+impl From<ZkdexError> for BatchError {
+    fn from(e: ZkdexError) -> Self {
+        Self {
+            code: e.code(),
+            message: e.to_string(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    use rayon::prelude::*;
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    F: Fn(&T) -> R,
+{
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::packed_public_key::private_key_from_string;
+    use crate::unified::transactions::order::perpetual::LimitOrder;
+    use crate::unified::transactions::test::PRI_KEY;
+    use crate::unified::{hash_any, sign_any};
+
+    const ORDER: &str = r##"
+    {
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484339",
+        "position_id":"4805234",
+        "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+    }
+    "##;
+
+    #[test]
+    fn test_batch_sign_typed() {
+        let order: LimitOrder = serde_json::from_str(ORDER).unwrap();
+        let orders: Vec<LimitOrder> = (0..16)
+            .map(|nonce| {
+                let mut order = order.clone();
+                order.base.nonce = nonce;
+                order
+            })
+            .collect();
+        let private_key = private_key_from_string(PRI_KEY).unwrap();
+        let signatures = batch_sign(&orders, &private_key);
+        let hashes = batch_hash(&orders);
+        for ((order, signature), hash) in orders.iter().zip(signatures).zip(hashes) {
+            let expected: JubjubSignature = order.sign(&private_key).unwrap().into();
+            assert_eq!(signature.unwrap(), expected);
+            assert_eq!(hash, order.hash());
+        }
+
+        let mut order = order;
+        order.amount_synthetic = 0;
+        let signatures = batch_sign(&[order], &private_key);
+        assert_eq!(signatures[0].as_ref().unwrap_err().code(), "INVALID_FIELDS");
+    }
+
+    #[test]
+    fn test_batch_any_keeps_order_and_item_errors() {
+        let json = format!(r#"[{}, {{"type":"DEPOSIT"}}, {}]"#, ORDER, ORDER);
+        let signatures = batch_sign_any(&json, PRI_KEY).unwrap();
+        assert_eq!(signatures.len(), 3);
+        assert_eq!(signatures[0], sign_any(ORDER, PRI_KEY));
+        assert_eq!(signatures[1].as_ref().unwrap_err().code(), "MALFORMED_JSON");
+        assert_eq!(signatures[2], signatures[0]);
+        let secret_key = SecretKey::from_hex(PRI_KEY).unwrap();
+        assert_eq!(batch_sign_any_with(&json, &secret_key).unwrap(), signatures);
+
+        let hashes = batch_hash_any(&json).unwrap();
+        assert_eq!(hashes[0], hash_any(ORDER));
+        assert!(hashes[1].is_err());
+
+        let items: Vec<BatchItem<AnyHash>> = hashes.into_iter().map(Into::into).collect();
+        let value = serde_json::to_value(&items).unwrap();
+        assert!(value[0].is_string());
+        assert_eq!(value[1]["error"]["code"], "MALFORMED_JSON");
+
+        let err = batch_sign_any("{}", PRI_KEY).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
+    #[test]
+    fn test_batch_verify_names_bad_items() {
+        let signed = |msg: &str| {
+            serde_json::json!({
+                "signature": {
+                    "r": "0x2e39e39381ac5e962650072a8936b99716fc0b3fda124f59ef62066301fd0749",
+                    "s": "0x37fd915bf958893ed35132a91b98fc4fcd7821c9fe784057bbc85d8fc5e7d4f"
+                },
+                "public_key": "0x8f792ad4f9b161ad77e37423d3709e0fc3d694259f4ec84c354f532e58643faa",
+                "hash": msg
+            })
+        };
+        let valid = signed("0x08a09b19adaa35815065dffcc4b5e0ee75f54660eb474c5932929b96c0ff15c9");
+        let wrong = signed("0x01817ed5bea1d0082c0fbe18edb06c15f52e2bb98c2b92f36d1a5ab082f1a520");
+        let mut malformed = valid.clone();
+        malformed["public_key"] = Value::from("0x1");
+        // more valid signatures than the cutoff, the batch equation runs and then bisects
+        let mut items = vec![valid; 7];
+        items[2] = wrong;
+        items[5] = malformed;
+
+        let results = batch_verify(&Value::from(items).to_string()).unwrap();
+        assert_eq!(results.len(), 7);
+        for (i, result) in results.iter().enumerate() {
+            match i {
+                2 => assert_eq!(result.as_ref().unwrap(), &false),
+                5 => assert_eq!(result.as_ref().unwrap_err().code(), "INVALID_PUBLIC_KEY"),
+                _ => assert_eq!(result.as_ref().unwrap(), &true),
+            }
+        }
+        assert!(batch_verify("[]").unwrap().is_empty());
+        assert_eq!(batch_verify("{}").unwrap_err().code(), "MALFORMED_JSON");
+    }
+}
@@ -0,0 +1,275 @@
+//! A signed transaction as sent to the gateway.
+//!
+//! The envelope is the canonical json of a [`UnifiedTransaction`] with the `"signature"` and
+//! `"hash"` of its message attached. A trade carries them in each of its orders instead.
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::error::{parse_json, Result, ZkdexError};
+use crate::felt::LeBytesConvert;
+use crate::hash_type::hash_type_to_string_with_0xprefix;
+use crate::tx::packed_public_key::{private_key_from_string, public_key_from_private};
+use crate::tx::sign::TxSignature;
+use crate::types::HashType;
+use crate::unified::transactions::UnifiedTransaction;
+use crate::unified::{embedded_signature, verify_signer};
+use crate::validate::Validate;
+use crate::zkw::JubjubSignature;
+
+const TRADE_ORDERS: [&str; 2] = ["party_a_order", "party_b_order"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    pub tx: UnifiedTransaction,
+    /// One signature per message of [`UnifiedTransaction::signers`], party A first for a trade.
+    pub signatures: Vec<JubjubSignature>,
+}
+
+impl Envelope {
+    /// Sign every message of `tx`, `private_keys` holds one key per entry of
+    /// [`UnifiedTransaction::signers`] and each one must own its message.
+    pub fn sign(tx: UnifiedTransaction, private_keys: &[&str]) -> Result<Self> {
+        let signers = tx.signers();
+        if signers.len() != private_keys.len() {
+            return Err(ZkdexError::InvalidArgument(format!(
+                "{} needs {} private keys, got {}",
+                tx.type_name(),
+                signers.len(),
+                private_keys.len()
+            )));
+        }
+        let mut signatures = Vec::with_capacity(signers.len());
+        let seals = signers.iter().zip(private_keys).zip(seal_paths(&tx));
+        for (((signer, hash), private_key), order) in seals {
+            let private_key = private_key_from_string(private_key)?;
+            if public_key_from_private(&private_key).0 != signer.0 {
+                return Err(ZkdexError::InvalidPrivateKey(match order {
+                    Some(order) => format!("private key does not own {}", order),
+                    None => "private key does not own the transaction".to_string(),
+                }));
+            }
+            let (signature, _) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
+            signatures.push(signature.into());
+        }
+        Ok(Self { tx, signatures })
+    }
+
+    /// The hash of every signed message in hex format, in the order of `signatures`.
+    pub fn hashes(&self) -> Vec<String> {
+        self.tx
+            .signers()
+            .into_iter()
+            .map(|(_, hash)| hash_type_to_string_with_0xprefix(hash as HashType))
+            .collect()
+    }
+
+    /// Parse an envelope, the attached hashes must match the transaction and every signature must
+    /// verify against its signer.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: Value = parse_json(json)?;
+        Self::from_value(value)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| ZkdexError::InvalidTransaction(e.to_string()))
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        let tx = UnifiedTransaction::from_value(value.clone())?;
+        let mut signatures = Vec::new();
+        for (order, (signer, hash)) in seal_paths(&tx).into_iter().zip(tx.signers()) {
+            let signature = embedded_signature(&value, order)?;
+
+            let object = order.map_or(&value, |order| &value[order]);
+            let path = order.map_or("hash".to_string(), |order| format!("{}.hash", order));
+            let expected = hash_type_to_string_with_0xprefix(hash as HashType);
+            match object.get("hash").and_then(Value::as_str) {
+                Some(found) if found.eq_ignore_ascii_case(&expected) => {}
+                Some(found) => {
+                    return Err(ZkdexError::InvalidHash(format!(
+                        "{} is {}, the transaction hashes to {}",
+                        path, found, expected
+                    )))
+                }
+                None => return Err(ZkdexError::InvalidHash(format!("{} is missing", path))),
+            }
+
+            if !verify_signer(signer, hash, &signature)? {
+                let path = order.map_or("signature".to_string(), |order| {
+                    format!("{}.signature", order)
+                });
+                return Err(ZkdexError::InvalidSignature(format!(
+                    "{} does not verify",
+                    path
+                )));
+            }
+            signatures.push(signature);
+        }
+        Ok(Self { tx, signatures })
+    }
+}
+
+/// Where the signature and hash of each message go, `None` is the top level.
+fn seal_paths(tx: &UnifiedTransaction) -> Vec<Option<&'static str>> {
+    if tx.is_trade() {
+        TRADE_ORDERS.iter().map(|order| Some(*order)).collect()
+    } else {
+        vec![None]
+    }
+}
+
+impl Serialize for Envelope {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut value = serde_json::to_value(&self.tx).map_err(ser::Error::custom)?;
+        let seals = seal_paths(&self.tx)
+            .into_iter()
+            .zip(&self.signatures)
+            .zip(self.hashes());
+        for ((order, signature), hash) in seals {
+            let object = match order {
+                Some(order) => &mut value[order],
+                None => &mut value,
+            };
+            if let Value::Object(map) = object {
+                let signature = serde_json::to_value(signature).map_err(ser::Error::custom)?;
+                map.insert("signature".to_string(), signature);
+                map.insert("hash".to_string(), Value::from(hash));
+            }
+        }
+        value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Envelope {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Self::from_value(value).map_err(de::Error::custom)
+    }
+}
+
+/// Validate and sign `tx`, returning its envelope in json format.
+pub(crate) fn sign_envelope(tx: UnifiedTransaction, private_keys: &[&str]) -> Result<String> {
+    tx.validate(None)?;
+    Envelope::sign(tx, private_keys)?.to_json()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign;
+    use crate::unified::transactions::test::PRI_KEY;
+    use crate::unified::{
+        unified_hash_withdrawal, unified_sign_perpetual_trade_envelope, unified_sign_withdrawal,
+        unified_sign_withdrawal_envelope, unified_verify_perpetual_trade,
+        unified_verify_withdrawal,
+    };
+
+    // does not own WITHDRAWAL
+    const OTHER_KEY: &str = "0x060a45bcd72c9e3c82bc1c57f63ad15b25f56bb13ce01d15fd4ab3f8f2de35bb";
+
+    const WITHDRAWAL: &str = r##"
+    {
+        "amount": "1682637359498011204",
+        "eth_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+        "expiration_timestamp": "2101470722",
+        "asset_id": "0x11111",
+        "nonce": "4265854110",
+        "position_id": "775817640",
+        "fee": "0",
+        "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+        "chain_id": "123"
+    }
+    "##;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let json = unified_sign_withdrawal_envelope(WITHDRAWAL, PRI_KEY).unwrap();
+        let mut value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["type"], "UNIFIED_WITHDRAWAL");
+        assert_eq!(value["hash"], unified_hash_withdrawal(WITHDRAWAL).unwrap());
+        let signature = unified_sign_withdrawal(WITHDRAWAL, PRI_KEY).unwrap();
+        assert_eq!(
+            value["signature"],
+            serde_json::to_value(&signature).unwrap()
+        );
+
+        let envelope = Envelope::from_json(&json).unwrap();
+        assert_eq!(envelope.signatures, vec![signature]);
+        assert_eq!(envelope.to_json().unwrap(), json);
+        assert!(unified_verify_withdrawal(&json).unwrap());
+
+        value["hash"] = Value::from("0x1");
+        let err = Envelope::from_json(&value.to_string()).unwrap_err();
+        assert_eq!(err.code(), "INVALID_HASH");
+
+        let hash = unified_hash_withdrawal(WITHDRAWAL).unwrap();
+        value["hash"] = Value::from(hash.as_str());
+        value["signature"] = serde_json::to_value(&sign(OTHER_KEY, &hash).unwrap()).unwrap();
+        let err = Envelope::from_json(&value.to_string()).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        assert!(err.to_string().contains("signature does not verify"));
+    }
+
+    #[test]
+    fn test_sign_needs_the_owning_key() {
+        let tx = UnifiedTransaction::from_json(WITHDRAWAL).unwrap();
+        let err = Envelope::sign(tx, &[OTHER_KEY]).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PRIVATE_KEY");
+    }
+
+    #[test]
+    fn test_trade_envelope() {
+        let trade = r##"
+        {
+            "party_a_order":{
+                "type":"PERP_CROSS",
+                "amount_collateral":"15334874",
+                "amount_fee":"1767749",
+                "amount_synthetic":"15460142",
+                "asset_id_collateral":"0x57d05d",
+                "asset_id_synthetic":"0x2",
+                "expiration_timestamp":"3608164305",
+                "is_buying_synthetic":true,
+                "nonce":"1210484339",
+                "order_type":"LIMIT_ORDER_WITH_FEES",
+                "position_id":"4805234",
+                "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+            },
+            "party_b_order":{
+                "type":"PERP_CROSS",
+                "amount_collateral":"15334874",
+                "amount_fee":"1767749",
+                "amount_synthetic":"15460142",
+                "asset_id_collateral":"0x57d05d",
+                "asset_id_synthetic":"0x2",
+                "expiration_timestamp":"3608164305",
+                "is_buying_synthetic":false,
+                "nonce":"12104",
+                "order_type":"LIMIT_ORDER_WITH_FEES",
+                "position_id":"48052349",
+                "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+            },
+            "actual_a_fee":"87916620",
+            "actual_b_fee":"-9309",
+            "actual_collateral":"775817",
+            "actual_synthetic":"1530808"
+        }
+        "##;
+        let json = unified_sign_perpetual_trade_envelope(trade, PRI_KEY, PRI_KEY).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert!(value.get("signature").is_none());
+        assert!(value["party_a_order"]["hash"].is_string());
+        assert!(value["party_b_order"]["signature"].is_object());
+
+        let envelope: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(envelope.signatures.len(), 2);
+        assert!(unified_verify_perpetual_trade(&json).unwrap().is_valid());
+    }
+}
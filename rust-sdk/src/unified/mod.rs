@@ -1,32 +1,68 @@
+use std::convert::TryFrom;
+
+use franklin_crypto::eddsa::PublicKey;
+use pairing_ce::bn256::Bn256;
+use primitive_types::U256;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{parse_json, parse_json_value, Result, ZkdexError};
+use crate::felt::LeBytesConvert;
 use crate::hash_type::hash_type_to_string_with_0xprefix;
-use crate::tx::packed_public_key::private_key_from_string;
+use crate::tx::batch_verify::{BatchVerifier, ParsedSignature};
+use crate::tx::packed_public_key::{private_key_from_string, PackedPublicKey};
+use crate::tx::packed_signature::{try_signature_from_rs, PackedSignature, SignatureOriginal};
+use crate::tx::public_key_type::PublicKeyType;
+use crate::tx::secret_key::SecretKey;
+use crate::tx::verifier::VERIFIER;
 use crate::types::HashType;
+use crate::unified::envelope::sign_envelope;
 use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::transactions::{
-    Liquidate, PerpetualTrade, SignedOraclePrice, SpotTrade, Transfer, Withdrawal,
+    Liquidate, PerpetualTrade, SignedOraclePrice, SpotTrade, Transfer, UnifiedTransaction,
+    Withdrawal,
 };
+use crate::validate::Validate;
+use crate::verify_jubjub_signature;
 use crate::zkw::JubjubSignature;
 
-mod transactions;
-mod types;
+pub mod batch;
+pub mod envelope;
+pub mod transactions;
+pub mod types;
+
+pub use batch::{
+    batch_hash, batch_hash_any, batch_sign, batch_sign_any, batch_sign_any_with, batch_verify,
+    BatchItem, SignedHash,
+};
+pub use envelope::Envelope;
 
 /// Sign a withdrawal transaction
 /// json: the withdrawal transaction in json format
 /// private_key: the private key in hex format
 /// return: the JubjubSignature
-pub fn unified_sign_withdrawal(json: &str, private_key: &str) -> anyhow::Result<JubjubSignature> {
-    let req: Withdrawal = serde_json::from_str(json)?;
+pub fn unified_sign_withdrawal(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Withdrawal = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_withdrawal`] without the field range checks.
+pub fn unified_sign_withdrawal_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Withdrawal = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash a withdrawal transaction
 /// json: the withdrawal transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_withdrawal(json: &str) -> anyhow::Result<String> {
-    let req: Withdrawal = serde_json::from_str(json)?;
+pub fn unified_hash_withdrawal(json: &str) -> Result<String> {
+    let req: Withdrawal = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
 
@@ -39,19 +75,34 @@ pub fn unified_sign_spot_trade(
     json: &str,
     private_key_a: &str,
     private_key_b: &str,
-) -> anyhow::Result<(JubjubSignature, JubjubSignature)> {
-    let req: SpotTrade = serde_json::from_str(json)?;
+) -> Result<(JubjubSignature, JubjubSignature)> {
+    let req: SpotTrade = parse_json(json)?;
+    req.validate(None)?;
+    let private_key_a = private_key_from_string(private_key_a)?;
+    let private_key_b = private_key_from_string(private_key_b)?;
+    let signature_a = req.party_a_order.sign_unchecked(&private_key_a);
+    let signature_b = req.party_b_order.sign_unchecked(&private_key_b);
+    Ok((signature_a.into(), signature_b.into()))
+}
+
+/// Same as [`unified_sign_spot_trade`] without the field range checks.
+pub fn unified_sign_spot_trade_unchecked(
+    json: &str,
+    private_key_a: &str,
+    private_key_b: &str,
+) -> Result<(JubjubSignature, JubjubSignature)> {
+    let req: SpotTrade = parse_json(json)?;
     let private_key_a = private_key_from_string(private_key_a)?;
     let private_key_b = private_key_from_string(private_key_b)?;
-    let signature_a = req.party_a_order.sign(&private_key_a);
-    let signature_b = req.party_b_order.sign(&private_key_b);
+    let signature_a = req.party_a_order.sign_unchecked(&private_key_a);
+    let signature_b = req.party_b_order.sign_unchecked(&private_key_b);
     Ok((signature_a.into(), signature_b.into()))
 }
 
 /// Hash a spot trade transaction
 /// json: the spot trade transaction in json format
-pub fn unified_hash_spot_trade(json: &str) -> anyhow::Result<(String, String)> {
-    let req: SpotTrade = serde_json::from_str(json)?;
+pub fn unified_hash_spot_trade(json: &str) -> Result<(String, String)> {
+    let req: SpotTrade = parse_json(json)?;
     Ok((
         hash_type_to_string_with_0xprefix(req.party_a_order.hash() as HashType),
         hash_type_to_string_with_0xprefix(req.party_b_order.hash() as HashType),
@@ -67,20 +118,35 @@ pub fn unified_sign_perpetual_trade(
     json: &str,
     private_key_a: &str,
     private_key_b: &str,
-) -> anyhow::Result<(JubjubSignature, JubjubSignature)> {
-    let req: PerpetualTrade = serde_json::from_str(json)?;
+) -> Result<(JubjubSignature, JubjubSignature)> {
+    let req: PerpetualTrade = parse_json(json)?;
+    req.validate(None)?;
     let private_key_a = private_key_from_string(private_key_a)?;
     let private_key_b = private_key_from_string(private_key_b)?;
-    let signature_a = req.party_a_order.sign(&private_key_a);
-    let signature_b = req.party_b_order.sign(&private_key_b);
+    let signature_a = req.party_a_order.sign_unchecked(&private_key_a);
+    let signature_b = req.party_b_order.sign_unchecked(&private_key_b);
+    Ok((signature_a.into(), signature_b.into()))
+}
+
+/// Same as [`unified_sign_perpetual_trade`] without the field range checks.
+pub fn unified_sign_perpetual_trade_unchecked(
+    json: &str,
+    private_key_a: &str,
+    private_key_b: &str,
+) -> Result<(JubjubSignature, JubjubSignature)> {
+    let req: PerpetualTrade = parse_json(json)?;
+    let private_key_a = private_key_from_string(private_key_a)?;
+    let private_key_b = private_key_from_string(private_key_b)?;
+    let signature_a = req.party_a_order.sign_unchecked(&private_key_a);
+    let signature_b = req.party_b_order.sign_unchecked(&private_key_b);
     Ok((signature_a.into(), signature_b.into()))
 }
 
 /// Hash a perpetual trade transaction
 /// json: the perpetual trade transaction in json format
 /// return: the hash of both A and B in hex format
-pub fn unified_hash_perpetual_trade(json: &str) -> anyhow::Result<(String, String)> {
-    let req: PerpetualTrade = serde_json::from_str(json)?;
+pub fn unified_hash_perpetual_trade(json: &str) -> Result<(String, String)> {
+    let req: PerpetualTrade = parse_json(json)?;
     Ok((
         hash_type_to_string_with_0xprefix(req.party_a_order.hash() as HashType),
         hash_type_to_string_with_0xprefix(req.party_b_order.hash() as HashType),
@@ -91,18 +157,26 @@ pub fn unified_hash_perpetual_trade(json: &str) -> anyhow::Result<(String, Strin
 /// json: the transfer transaction in json format
 /// private_key: the private key in hex format
 /// return: the JubjubSignature
-pub fn unified_sign_transfer(json: &str, private_key: &str) -> anyhow::Result<JubjubSignature> {
-    let req: Transfer = serde_json::from_str(json)?;
+pub fn unified_sign_transfer(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Transfer = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_transfer`] without the field range checks.
+pub fn unified_sign_transfer_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Transfer = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash a transfer transaction
 /// json: the transfer transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_transfer(json: &str) -> anyhow::Result<String> {
-    let req: Transfer = serde_json::from_str(json)?;
+pub fn unified_hash_transfer(json: &str) -> Result<String> {
+    let req: Transfer = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
 
@@ -110,18 +184,29 @@ pub fn unified_hash_transfer(json: &str) -> anyhow::Result<String> {
 /// json: the oracle price transaction in json format
 /// private_key: the private key in hex format
 /// return: the JubjubSignature
-pub fn unified_sign_oracle_price(json: &str, private_key: &str) -> anyhow::Result<JubjubSignature> {
-    let req: SignedOraclePrice = serde_json::from_str(json)?;
+pub fn unified_sign_oracle_price(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: SignedOraclePrice = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_oracle_price`] without the field range checks.
+pub fn unified_sign_oracle_price_unchecked(
+    json: &str,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let req: SignedOraclePrice = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash an oracle price transaction
 /// json: the oracle price transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_oracle_price(json: &str) -> anyhow::Result<String> {
-    let req: SignedOraclePrice = serde_json::from_str(json)?;
+pub fn unified_hash_oracle_price(json: &str) -> Result<String> {
+    let req: SignedOraclePrice = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
 
@@ -129,18 +214,26 @@ pub fn unified_hash_oracle_price(json: &str) -> anyhow::Result<String> {
 /// json: the liquidate transaction in json format
 /// private_key: the private key in hex format
 /// return: the JubjubSignature
-pub fn unified_sign_liquidate(json: &str, private_key: &str) -> anyhow::Result<JubjubSignature> {
-    let req: Liquidate = serde_json::from_str(json)?;
+pub fn unified_sign_liquidate(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Liquidate = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_liquidate`] without the field range checks.
+pub fn unified_sign_liquidate_unchecked(json: &str, private_key: &str) -> Result<JubjubSignature> {
+    let req: Liquidate = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash a liquidate transaction
 /// json: the liquidate transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_liquidate(json: &str) -> anyhow::Result<String> {
-    let req: Liquidate = serde_json::from_str(json)?;
+pub fn unified_hash_liquidate(json: &str) -> Result<String> {
+    let req: Liquidate = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
 
@@ -151,18 +244,29 @@ pub fn unified_hash_liquidate(json: &str) -> anyhow::Result<String> {
 pub fn unified_sign_spot_limit_order(
     json: &str,
     private_key: &str,
-) -> anyhow::Result<JubjubSignature> {
-    let req: transactions::order::spot::LimitOrder = serde_json::from_str(json)?;
+) -> Result<JubjubSignature> {
+    let req: transactions::order::spot::LimitOrder = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_spot_limit_order`] without the field range checks.
+pub fn unified_sign_spot_limit_order_unchecked(
+    json: &str,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let req: transactions::order::spot::LimitOrder = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash a spot limit order transaction
 /// json: the spot limit order transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_spot_limit_order(json: &str) -> anyhow::Result<String> {
-    let req: transactions::order::spot::LimitOrder = serde_json::from_str(json)?;
+pub fn unified_hash_spot_limit_order(json: &str) -> Result<String> {
+    let req: transactions::order::spot::LimitOrder = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
 
@@ -173,17 +277,534 @@ pub fn unified_hash_spot_limit_order(json: &str) -> anyhow::Result<String> {
 pub fn unified_sign_perpetual_limit_order(
     json: &str,
     private_key: &str,
-) -> anyhow::Result<JubjubSignature> {
-    let req: transactions::order::perpetual::LimitOrder = serde_json::from_str(json)?;
+) -> Result<JubjubSignature> {
+    let req: transactions::order::perpetual::LimitOrder = parse_json(json)?;
+    let private_key = private_key_from_string(private_key)?;
+    let signature = req.sign(&private_key)?;
+    Ok(signature.into())
+}
+
+/// Same as [`unified_sign_perpetual_limit_order`] without the field range checks.
+pub fn unified_sign_perpetual_limit_order_unchecked(
+    json: &str,
+    private_key: &str,
+) -> Result<JubjubSignature> {
+    let req: transactions::order::perpetual::LimitOrder = parse_json(json)?;
     let private_key = private_key_from_string(private_key)?;
-    let signature = req.sign(&private_key);
+    let signature = req.sign_unchecked(&private_key);
     Ok(signature.into())
 }
 
 /// Hash a perpetual limit order transaction
 /// json: the perpetual limit order transaction in json format
 /// return: the hash in hex format
-pub fn unified_hash_perpetual_limit_order(json: &str) -> anyhow::Result<String> {
-    let req: transactions::order::perpetual::LimitOrder = serde_json::from_str(json)?;
+pub fn unified_hash_perpetual_limit_order(json: &str) -> Result<String> {
+    let req: transactions::order::perpetual::LimitOrder = parse_json(json)?;
     Ok(hash_type_to_string_with_0xprefix(req.hash() as HashType))
 }
+
+/// Signature of any unified transaction, a trade carries one signature per order and leaves out
+/// the orders that were not signed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnySignature {
+    Single(JubjubSignature),
+    Compose {
+        signature_a: Option<JubjubSignature>,
+        signature_b: Option<JubjubSignature>,
+    },
+}
+
+/// Hash of any unified transaction, a trade carries one hash per order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnyHash {
+    Single(String),
+    Compose { hash_a: String, hash_b: String },
+}
+
+/// Sign any unified transaction, the kind of transaction is taken from its "type" field
+/// json: the transaction in json format
+/// private_key: the private key in hex format
+/// return: the JubjubSignature, for a trade only the orders owned by the private key are signed,
+/// any other transaction must be owned by the private key
+pub fn sign_any(json: &str, private_key: &str) -> Result<AnySignature> {
+    let tx = UnifiedTransaction::from_json(json)?;
+    tx.validate(None)?;
+    sign_transaction(&tx, &SecretKey::from_hex(private_key)?)
+}
+
+/// Same as [`sign_any`] without the field range checks.
+pub fn sign_any_unchecked(json: &str, private_key: &str) -> Result<AnySignature> {
+    let tx = UnifiedTransaction::from_json(json)?;
+    sign_transaction(&tx, &SecretKey::from_hex(private_key)?)
+}
+
+pub(crate) fn sign_transaction(
+    tx: &UnifiedTransaction,
+    secret_key: &SecretKey,
+) -> Result<AnySignature> {
+    let signers = tx.signers();
+    let public_key = secret_key.public_key();
+    if !tx.is_trade() {
+        let (signer, hash) = &signers[0];
+        if signer.0 != public_key.0 {
+            return Err(ZkdexError::InvalidPrivateKey(
+                "private key does not own the transaction".to_string(),
+            ));
+        }
+        let signature = secret_key.sign_hash(hash.as_le_bytes())?;
+        return Ok(AnySignature::Single(signature.into()));
+    }
+
+    let signatures = signers
+        .iter()
+        .map(|(signer, hash)| {
+            if signer.0 == public_key.0 {
+                let signature: JubjubSignature = secret_key.sign_hash(hash.as_le_bytes())?.into();
+                Ok(Some(signature))
+            } else {
+                Ok(None)
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut signatures = signatures.into_iter();
+    let signature_a = signatures.next().flatten();
+    let signature_b = signatures.next().flatten();
+    if signature_a.is_none() && signature_b.is_none() {
+        return Err(ZkdexError::InvalidPrivateKey(
+            "private key does not own any order of the trade".to_string(),
+        ));
+    }
+    Ok(AnySignature::Compose {
+        signature_a,
+        signature_b,
+    })
+}
+
+/// Hash any unified transaction, the kind of transaction is taken from its "type" field
+/// json: the transaction in json format
+/// return: the hash in hex format, a trade returns the hash of both A and B
+pub fn hash_any(json: &str) -> Result<AnyHash> {
+    let tx = UnifiedTransaction::from_json(json)?;
+    Ok(hash_transaction(&tx))
+}
+
+fn hash_transaction(tx: &UnifiedTransaction) -> AnyHash {
+    let hashes: Vec<String> = tx
+        .signers()
+        .into_iter()
+        .map(|(_, hash)| hash_type_to_string_with_0xprefix(hash as HashType))
+        .collect();
+    if tx.is_trade() {
+        AnyHash::Compose {
+            hash_a: hashes[0].clone(),
+            hash_b: hashes[1].clone(),
+        }
+    } else {
+        AnyHash::Single(hashes[0].clone())
+    }
+}
+
+/// Verify a signature over any unified transaction, the kind of transaction is taken from its "type" field
+/// json: the transaction in json format
+/// signature: the JubjubSignature
+/// pub_key: the public key of the signer in hex format
+/// return: true if the public key signs the transaction and the signature is valid, for a trade
+/// either order owned by the public key may match
+pub fn verify_any(json: &str, signature: JubjubSignature, pub_key: &str) -> Result<bool> {
+    let tx = UnifiedTransaction::from_json(json)?;
+    let packed_pk = PackedPublicKey::try_from(pub_key)?;
+    for (signer, hash) in tx.signers() {
+        if signer.0 != packed_pk.0 {
+            continue;
+        }
+        let msg = hash_type_to_string_with_0xprefix(hash as HashType);
+        if verify_jubjub_signature(signature.clone(), pub_key, &msg)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Sign a withdrawal transaction and attach the signature and hash to it
+/// json: the withdrawal transaction in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_withdrawal_envelope(json: &str, private_key: &str) -> Result<String> {
+    let req: Withdrawal = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::Withdrawal(req), &[private_key])
+}
+
+/// Sign a transfer transaction and attach the signature and hash to it
+/// json: the transfer transaction in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_transfer_envelope(json: &str, private_key: &str) -> Result<String> {
+    let req: Transfer = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::Transfer(req), &[private_key])
+}
+
+/// Sign an oracle price and attach the signature and hash to it
+/// json: the oracle price in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_oracle_price_envelope(json: &str, private_key: &str) -> Result<String> {
+    let req: SignedOraclePrice = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::OraclePrice(req), &[private_key])
+}
+
+/// Sign a liquidate transaction and attach the signature and hash to it
+/// json: the liquidate transaction in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_liquidate_envelope(json: &str, private_key: &str) -> Result<String> {
+    let req: Liquidate = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::Liquidate(req), &[private_key])
+}
+
+/// Sign a spot limit order and attach the signature and hash to it
+/// json: the spot limit order in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_spot_limit_order_envelope(json: &str, private_key: &str) -> Result<String> {
+    let req: transactions::order::spot::LimitOrder = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::SpotLimitOrder(req), &[private_key])
+}
+
+/// Sign a perpetual limit order and attach the signature and hash to it
+/// json: the perpetual limit order in json format
+/// private_key: the private key in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_perpetual_limit_order_envelope(
+    json: &str,
+    private_key: &str,
+) -> Result<String> {
+    let req: transactions::order::perpetual::LimitOrder = parse_json(json)?;
+    sign_envelope(UnifiedTransaction::PerpetualLimitOrder(req), &[private_key])
+}
+
+/// Sign both orders of a spot trade and attach the signature and hash to each of them
+/// json: the spot trade transaction in json format
+/// private_key_a: the private key of party A in hex format
+/// private_key_b: the private key of party B in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_spot_trade_envelope(
+    json: &str,
+    private_key_a: &str,
+    private_key_b: &str,
+) -> Result<String> {
+    let req: SpotTrade = parse_json(json)?;
+    sign_envelope(
+        UnifiedTransaction::SpotTrade(req),
+        &[private_key_a, private_key_b],
+    )
+}
+
+/// Sign both orders of a perpetual trade and attach the signature and hash to each of them
+/// json: the perpetual trade transaction in json format
+/// private_key_a: the private key of party A in hex format
+/// private_key_b: the private key of party B in hex format
+/// return: the signed transaction in json format, see [`Envelope`]
+pub fn unified_sign_perpetual_trade_envelope(
+    json: &str,
+    private_key_a: &str,
+    private_key_b: &str,
+) -> Result<String> {
+    let req: PerpetualTrade = parse_json(json)?;
+    sign_envelope(
+        UnifiedTransaction::PerpetualTrade(req),
+        &[private_key_a, private_key_b],
+    )
+}
+
+/// Result of verifying the signatures embedded in a trade, one flag per order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TradeVerification {
+    pub party_a: bool,
+    pub party_b: bool,
+}
+
+impl TradeVerification {
+    pub fn is_valid(&self) -> bool {
+        self.party_a && self.party_b
+    }
+}
+
+/// Verify the signature embedded in a withdrawal against its public key
+/// json: the withdrawal transaction in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_withdrawal(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &Withdrawal| &tx.base.public_key)
+}
+
+/// Verify the signature embedded in a transfer against its sender public key
+/// json: the transfer transaction in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_transfer(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &Transfer| &tx.base.public_key)
+}
+
+/// Verify the signature embedded in an oracle price against its signer key
+/// json: the oracle price in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_oracle_price(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &SignedOraclePrice| &tx.signer_key)
+}
+
+/// Verify the signature embedded in a liquidate against the public key of the liquidator order
+/// json: the liquidate transaction in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_liquidate(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &Liquidate| &tx.liquidator_order.base.public_key)
+}
+
+/// Verify the signature embedded in a spot limit order against its public key
+/// json: the spot limit order in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_spot_limit_order(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &transactions::order::spot::LimitOrder| {
+        &tx.base.public_key
+    })
+}
+
+/// Verify the signature embedded in a perpetual limit order against its public key
+/// json: the perpetual limit order in json format, with its "signature": {r, s}
+/// return: true if the signature is valid
+pub fn unified_verify_perpetual_limit_order(json: &str) -> Result<bool> {
+    verify_embedded(json, |tx: &transactions::order::perpetual::LimitOrder| {
+        &tx.base.public_key
+    })
+}
+
+/// Verify the signatures embedded in both orders of a spot trade against their public keys
+/// json: the spot trade in json format, each order with its "signature": {r, s}
+/// return: the TradeVerification telling which order has a valid signature
+pub fn unified_verify_spot_trade(json: &str) -> Result<TradeVerification> {
+    let value: Value = parse_json(json)?;
+    let signature_a = embedded_rs(&value, Some("party_a_order"))?;
+    let signature_b = embedded_rs(&value, Some("party_b_order"))?;
+    let tx: SpotTrade = parse_json_value(value)?;
+    Ok(verify_trade_signers(
+        (
+            &tx.party_a_order.base.public_key,
+            tx.party_a_order.hash(),
+            &signature_a,
+        ),
+        (
+            &tx.party_b_order.base.public_key,
+            tx.party_b_order.hash(),
+            &signature_b,
+        ),
+    ))
+}
+
+/// Verify the signatures embedded in both orders of a perpetual trade against their public keys
+/// json: the perpetual trade in json format, each order with its "signature": {r, s}
+/// return: the TradeVerification telling which order has a valid signature
+pub fn unified_verify_perpetual_trade(json: &str) -> Result<TradeVerification> {
+    let value: Value = parse_json(json)?;
+    let signature_a = embedded_rs(&value, Some("party_a_order"))?;
+    let signature_b = embedded_rs(&value, Some("party_b_order"))?;
+    let tx: PerpetualTrade = parse_json_value(value)?;
+    Ok(verify_trade_signers(
+        (
+            &tx.party_a_order.base.public_key,
+            tx.party_a_order.hash(),
+            &signature_a,
+        ),
+        (
+            &tx.party_b_order.base.public_key,
+            tx.party_b_order.hash(),
+            &signature_b,
+        ),
+    ))
+}
+
+fn verify_embedded<T, F>(json: &str, signer: F) -> Result<bool>
+where
+    T: DeserializeOwned + HashTrait,
+    F: Fn(&T) -> &PublicKeyType,
+{
+    let value: Value = parse_json(json)?;
+    let signature = embedded_signature(&value, None)?;
+    let tx: T = parse_json_value(value)?;
+    verify_signer(signer(&tx), tx.hash(), &signature)
+}
+
+/// Read the "signature" field of `value`, or of its `order` field for an order of a trade.
+fn embedded_signature(value: &Value, order: Option<&str>) -> Result<JubjubSignature> {
+    let signature = embedded_rs(value, order)?;
+    try_signature_from_rs(&signature.r, &signature.s)
+}
+
+/// Same as [`embedded_signature`] without checking `r` is a point of the curve.
+fn embedded_rs(value: &Value, order: Option<&str>) -> Result<SignatureOriginal> {
+    let (object, path) = match order {
+        Some(order) => (&value[order], format!("{}.signature", order)),
+        None => (value, "signature".to_string()),
+    };
+    let signature = object
+        .get("signature")
+        .ok_or_else(|| ZkdexError::InvalidSignature(format!("{} is missing", path)))?;
+    SignatureOriginal::deserialize(signature)
+        .map_err(|e| ZkdexError::InvalidSignature(format!("{}: {}", path, e)))
+}
+
+fn verify_signer(signer: &PublicKeyType, hash: U256, signature: &JubjubSignature) -> Result<bool> {
+    let signature = PackedSignature::try_from(signature)?;
+    VERIFIER.verify(&PackedPublicKey(signer.0), &signature, hash.as_le_bytes())
+}
+
+/// Both orders of a trade are queued in one [`BatchVerifier`], two signatures are below its
+/// cutoff so each one is verified on its own. A signature or public key of one order that does not
+/// parse only makes that order invalid.
+fn verify_trade_signers(
+    party_a: (&PublicKeyType, U256, &SignatureOriginal),
+    party_b: (&PublicKeyType, U256, &SignatureOriginal),
+) -> TradeVerification {
+    let parsed: Vec<Result<ParsedSignature>> = vec![party_a, party_b]
+        .into_iter()
+        .map(|(signer, hash, signature)| {
+            let signature = try_signature_from_rs(&signature.r, &signature.s)?;
+            let signature = PackedSignature::try_from(&signature)?;
+            Ok((signer_public_key(signer)?, hash, signature))
+        })
+        .collect();
+    let valid = BatchVerifier::verify_parsed(&parsed);
+    TradeVerification {
+        party_a: valid[0],
+        party_b: valid[1],
+    }
+}
+
+fn signer_public_key(signer: &PublicKeyType) -> Result<PublicKey<Bn256>> {
+    PackedPublicKey(signer.0).try_into_public_key()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::unified::transactions::test::PRI_KEY;
+
+    const WITHDRAWAL: &str = r##"
+    {
+        "amount": "1682637359498011204",
+        "eth_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+        "expiration_timestamp": "2101470722",
+        "asset_id": "0x11111",
+        "nonce": "4265854110",
+        "position_id": "775817640",
+        "fee": "0",
+        "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+        "chain_id": "123"
+    }
+    "##;
+
+    const PERP_TRADE: &str = r##"
+    {
+        "party_a_order":{
+            "type":"PERP_CROSS",
+            "amount_collateral":"15334874",
+            "amount_fee":"1767749",
+            "amount_synthetic":"15460142",
+            "asset_id_collateral":"0x57d05d",
+            "asset_id_synthetic":"0x2",
+            "expiration_timestamp":"3608164305",
+            "is_buying_synthetic":true,
+            "nonce":"1210484339",
+            "order_type":"LIMIT_ORDER_WITH_FEES",
+            "position_id":"4805234",
+            "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+        },
+        "party_b_order":{
+            "type":"PERP_CROSS",
+            "amount_collateral":"15334874",
+            "amount_fee":"1767749",
+            "amount_synthetic":"15460142",
+            "asset_id_collateral":"0x57d05d",
+            "asset_id_synthetic":"0x2",
+            "expiration_timestamp":"3608164305",
+            "is_buying_synthetic":false,
+            "nonce":"12104",
+            "order_type":"LIMIT_ORDER_WITH_FEES",
+            "position_id":"48052349",
+            "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+        },
+        "actual_a_fee":"87916620",
+        "actual_b_fee":"-9309",
+        "actual_collateral":"775817",
+        "actual_synthetic":"1530808"
+    }
+    "##;
+
+    fn embed(value: &mut Value, signature: JubjubSignature) {
+        value["signature"] = json!(signature);
+    }
+
+    #[test]
+    fn test_verify_embedded_signature() {
+        let signature = unified_sign_withdrawal(WITHDRAWAL, PRI_KEY).unwrap();
+        let mut value: Value = serde_json::from_str(WITHDRAWAL).unwrap();
+        embed(&mut value, signature);
+        assert!(unified_verify_withdrawal(&value.to_string()).unwrap());
+
+        value["amount"] = json!("1682637359498011205");
+        assert!(!unified_verify_withdrawal(&value.to_string()).unwrap());
+
+        let err = unified_verify_withdrawal(WITHDRAWAL).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        assert!(err.to_string().contains("signature is missing"));
+    }
+
+    #[test]
+    fn test_verify_trade_reports_failed_party() {
+        let (signature_a, signature_b) =
+            unified_sign_perpetual_trade(PERP_TRADE, PRI_KEY, PRI_KEY).unwrap();
+        let mut value: Value = serde_json::from_str(PERP_TRADE).unwrap();
+        embed(&mut value["party_a_order"], signature_a);
+        embed(&mut value["party_b_order"], signature_b);
+
+        let ret = unified_verify_perpetual_trade(&value.to_string()).unwrap();
+        assert_eq!(
+            ret,
+            TradeVerification {
+                party_a: true,
+                party_b: false,
+            }
+        );
+        assert!(!ret.is_valid());
+
+        value["party_b_order"]
+            .as_object_mut()
+            .unwrap()
+            .remove("signature");
+        let err = unified_verify_perpetual_trade(&value.to_string()).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SIGNATURE");
+        assert!(err.to_string().contains("party_b_order.signature"));
+    }
+
+    #[test]
+    fn test_verify_trade_with_one_malformed_order() {
+        // the low 255 bits are above the field modulus, neither a point nor a public key
+        let off_curve = format!("0x{}", "f".repeat(64));
+        let (signature_a, _) = unified_sign_perpetual_trade(PERP_TRADE, PRI_KEY, PRI_KEY).unwrap();
+        let taker_only = TradeVerification {
+            party_a: true,
+            party_b: false,
+        };
+
+        let mut value: Value = serde_json::from_str(PERP_TRADE).unwrap();
+        embed(&mut value["party_a_order"], signature_a.clone());
+        value["party_b_order"]["signature"] = json!({"r": off_curve, "s": "0x1"});
+        let ret = unified_verify_perpetual_trade(&value.to_string()).unwrap();
+        assert_eq!(ret, taker_only);
+
+        embed(&mut value["party_b_order"], signature_a);
+        value["party_b_order"]["public_key"] = json!(off_curve);
+        let ret = unified_verify_perpetual_trade(&value.to_string()).unwrap();
+        assert_eq!(ret, taker_only);
+    }
+}
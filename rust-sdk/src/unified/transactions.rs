@@ -1,33 +1,83 @@
+pub use deleverage::*;
+pub use hash_trait::HashTrait;
 pub use liquidate::*;
 pub use oracle::*;
 pub use perpetual_trade::*;
+pub use sign_trait::SignTrait;
 pub use spot_trade::*;
 pub use transfer::*;
+pub use unified_transaction::*;
 pub use withdrawal::*;
 
-pub(crate) mod hash_trait;
+// Setters for the `OrderBase` fields shared by most builders.
+macro_rules! impl_base_setters {
+    () => {
+        pub fn nonce(mut self, nonce: crate::types::NonceType) -> Self {
+            self.inner.base.nonce = nonce;
+            self
+        }
+
+        pub fn public_key(mut self, public_key: crate::tx::public_key_type::PublicKeyType) -> Self {
+            self.inner.base.public_key = public_key;
+            self
+        }
+
+        pub fn expiration_timestamp(
+            mut self,
+            expiration_timestamp: crate::types::TimestampType,
+        ) -> Self {
+            self.inner.base.expiration_timestamp = expiration_timestamp;
+            self
+        }
+    };
+}
+
+macro_rules! impl_setters {
+    ($($field:ident: $arg:ty),* $(,)?) => {
+        $(
+            pub fn $field(mut self, $field: $arg) -> Self {
+                self.inner.$field = $field.into();
+                self
+            }
+        )*
+    };
+}
+
+mod deleverage;
+pub mod hash_trait;
 mod liquidate;
 mod oracle;
 pub mod order;
 mod perpetual_trade;
-pub(crate) mod sign_trait;
+pub mod sign_trait;
 mod spot_trade;
 mod transfer;
+mod unified_transaction;
 mod withdrawal;
 
 #[cfg(test)]
 mod test {
+    use std::convert::TryFrom;
+
     use crate::hash_type::hash_type_to_string_with_0xprefix;
-    use crate::tx::packed_public_key::private_key_from_string;
+    use crate::tx::packed_public_key::{private_key_from_string, PackedPublicKey};
+    use crate::tx::public_key_type::PublicKeyType;
     use crate::unified::transactions::hash_trait::HashTrait;
     use crate::unified::transactions::sign_trait::SignTrait;
     use crate::verify_jubjub_signature;
 
     pub const PRI_KEY: &str = "0x01e1b55a539517898350ca915cbf8b25b70d9313a5ab0ff0a3466ed7799f11fe";
     pub const PUB_KEY: &str = "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a";
+
+    pub fn public_key() -> PublicKeyType {
+        PackedPublicKey::try_from(PUB_KEY.trim_start_matches("0x"))
+            .unwrap()
+            .into()
+    }
+
     pub fn sign_and_verify(tx: impl SignTrait + HashTrait) {
         let pri_key = private_key_from_string(PRI_KEY).unwrap();
-        let sig = tx.sign(&pri_key);
+        let sig = tx.sign_unchecked(&pri_key);
 
         let msg = tx.hash();
         let verify =
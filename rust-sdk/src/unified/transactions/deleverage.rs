@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ZkdexError};
+use crate::serde_wrapper::U64SerdeStr;
+use crate::unified::types::{AmountType, AssetIdType, PositionIdType, SignedAmountType};
+use crate::validate::{Validate, Validator};
+
+/// The deleverager takes over part of the synthetic position of the deleveraged position at a
+/// price set by the sequencer. It is not signed, so it has no hash.
+///
+/// The json is the one the sequencer publishes, with the fields in the order below.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Deleverage {
+    pub deleveraged_position_id: PositionIdType,
+    pub deleverager_position_id: PositionIdType,
+    pub synthetic_asset_id: AssetIdType,
+    #[serde(with = "U64SerdeStr")]
+    pub amount_collateral: AmountType,
+    #[serde(with = "U64SerdeStr")]
+    pub amount_synthetic: AmountType,
+    pub deleverager_is_buying_synthetic: bool,
+}
+
+impl Validate for Deleverage {
+    fn check(&self, validator: &mut Validator) {
+        if self.deleverager_position_id == self.deleveraged_position_id {
+            validator.push(
+                "deleverager_position_id",
+                "must differ from deleveraged_position_id",
+            );
+        }
+        validator.positive(self.amount_collateral, "amount_collateral");
+        validator.positive(self.amount_synthetic, "amount_synthetic");
+    }
+}
+
+impl Deleverage {
+    pub fn builder() -> DeleverageBuilder {
+        DeleverageBuilder::default()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| ZkdexError::InvalidTransaction(e.to_string()))
+    }
+
+    /// Check the fields and the synthetic balances of both positions before the deleverage, each
+    /// position must be reduced toward zero.
+    pub fn check_balances(
+        &self,
+        deleveraged_balance: &SignedAmountType,
+        deleverager_balance: &SignedAmountType,
+    ) -> Result<()> {
+        let mut validator = Validator::new(None);
+        self.check(&mut validator);
+        validator.reduces_position(
+            deleveraged_balance,
+            !self.deleverager_is_buying_synthetic,
+            self.amount_synthetic,
+            "deleveraged_position_id",
+        );
+        validator.reduces_position(
+            deleverager_balance,
+            self.deleverager_is_buying_synthetic,
+            self.amount_synthetic,
+            "deleverager_position_id",
+        );
+        validator.finish()
+    }
+}
+
+/// Builds a [`Deleverage`], `build` refuses what [`Validate`] does: zero amounts and a position that
+/// deleverages itself.
+#[derive(Debug, Clone, Default)]
+pub struct DeleverageBuilder {
+    inner: Deleverage,
+}
+
+impl DeleverageBuilder {
+    impl_setters!(
+        deleveraged_position_id: u32,
+        deleverager_position_id: u32,
+        synthetic_asset_id: u32,
+        amount_collateral: AmountType,
+        amount_synthetic: AmountType,
+        deleverager_is_buying_synthetic: bool,
+    );
+
+    pub fn build(self) -> Result<Deleverage> {
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELEVERAGE: &str = r#"{"deleveraged_position_id":"1541968236","deleverager_position_id":"11534","synthetic_asset_id":"0x2","amount_collateral":"8187132600","amount_synthetic":"1655802","deleverager_is_buying_synthetic":false}"#;
+
+    fn balance(balance: &str) -> SignedAmountType {
+        serde_json::from_value(serde_json::Value::from(balance)).unwrap()
+    }
+
+    #[test]
+    fn test_deleverage_serde() {
+        let tx: Deleverage = serde_json::from_str(DELEVERAGE).unwrap();
+        assert_eq!(tx.to_json().unwrap(), DELEVERAGE);
+
+        let built = Deleverage::builder()
+            .deleveraged_position_id(1541968236)
+            .deleverager_position_id(11534)
+            .synthetic_asset_id(2)
+            .amount_collateral(8187132600)
+            .amount_synthetic(1655802)
+            .build()
+            .unwrap();
+        assert_eq!(built, tx);
+
+        let err = Deleverage::builder()
+            .deleveraged_position_id(1)
+            .deleverager_position_id(1)
+            .amount_collateral(1)
+            .amount_synthetic(1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: deleverager_position_id: must differ from deleveraged_position_id"
+        );
+    }
+
+    #[test]
+    fn test_deleverage_check_balances() {
+        let tx: Deleverage = serde_json::from_str(DELEVERAGE).unwrap();
+        // the deleverager sells to the short deleveraged position
+        assert!(tx
+            .check_balances(&balance("-1655802"), &balance("2000000"))
+            .is_ok());
+
+        let err = tx
+            .check_balances(&balance("-1655801"), &balance("-5"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: deleveraged_position_id: trades more than the balance -1655801; \
+             deleverager_position_id: sells but the position is not long"
+        );
+        let err = tx
+            .check_balances(&balance("0"), &balance("2000000"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("deleveraged_position_id: buys but the position is not short"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::ZkdexError;
 use crate::serde_wrapper::U64SerdeStr;
 use crate::tx::packed_public_key::PrivateKeyType;
 use crate::tx::sign::TxSignature;
@@ -5,6 +6,7 @@ use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::order::perpetual::LimitOrder;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::{AmountType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
@@ -29,8 +31,49 @@ impl HashTrait for Liquidate {
 }
 
 impl SignTrait for Liquidate {
-    fn sign(&self, private_key: &PrivateKeyType) -> TxSignature {
-        self.liquidator_order.sign(private_key)
+    fn sign_unchecked(&self, private_key: &PrivateKeyType) -> TxSignature {
+        self.liquidator_order.sign_unchecked(private_key)
+    }
+}
+
+impl Validate for Liquidate {
+    fn check(&self, validator: &mut Validator) {
+        validator.nested("liquidator_order", &self.liquidator_order);
+        validator.positive(self.actual_collateral, "actual_collateral");
+        validator.positive(self.actual_synthetic, "actual_synthetic");
+    }
+}
+
+impl Liquidate {
+    pub fn builder() -> LiquidateBuilder {
+        LiquidateBuilder::default()
+    }
+}
+
+/// Builds a [`Liquidate`] around the liquidator's order, `build` refuses what [`Validate`] does and
+/// a liquidator that liquidates its own position.
+#[derive(Debug, Clone, Default)]
+pub struct LiquidateBuilder {
+    inner: Liquidate,
+}
+
+impl LiquidateBuilder {
+    impl_setters!(
+        liquidator_order: LimitOrder,
+        liquidated_position_id: u32,
+        actual_collateral: AmountType,
+        actual_synthetic: AmountType,
+        actual_liquidator_fee: AmountType,
+    );
+
+    pub fn build(self) -> crate::error::Result<Liquidate> {
+        let tx = &self.inner;
+        if tx.liquidated_position_id == tx.liquidator_order.position_id {
+            return Err(ZkdexError::InvalidTransaction(
+                "liquidated_position_id equals liquidator_order.position_id".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
     }
 }
 
@@ -38,7 +81,7 @@ impl SignTrait for Liquidate {
 mod tests {
     use super::*;
 
-    use crate::unified::transactions::test::sign_and_verify;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
 
     #[test]
     fn test_tx_serde() {
@@ -97,4 +140,43 @@ mod tests {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    fn test_builder() {
+        let order = LimitOrder::builder()
+            .public_key(public_key())
+            .position_id(7)
+            .asset_id_synthetic(0x2)
+            .asset_id_collateral(0x57d05d)
+            .amount_synthetic(10)
+            .amount_collateral(100)
+            .build()
+            .unwrap();
+        let builder = Liquidate::builder()
+            .liquidator_order(order)
+            .liquidated_position_id(8)
+            .actual_collateral(100)
+            .actual_synthetic(10)
+            .actual_liquidator_fee(1);
+        let tx = builder.clone().build().unwrap();
+        assert!(tx.validate(None).is_ok());
+        assert_eq!(tx.hash(), tx.liquidator_order.hash());
+        sign_and_verify(tx);
+
+        let own_position = builder.clone().liquidated_position_id(7).build();
+        assert_eq!(own_position.unwrap_err().code(), "INVALID_TRANSACTION");
+        let empty = builder.clone().actual_synthetic(0).build().unwrap_err();
+        assert_eq!(
+            empty.to_string(),
+            "invalid fields: actual_synthetic: must be at least 1"
+        );
+        let unsigned = builder
+            .liquidator_order(LimitOrder::default())
+            .build()
+            .unwrap_err();
+        assert_eq!(unsigned.code(), "INVALID_FIELDS");
+        assert!(unsigned
+            .to_string()
+            .contains("liquidator_order.public_key: must be set"));
+    }
 }
@@ -1,11 +1,14 @@
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::constant::EXTERNAL_PRICE_UPPER_BOUND;
+use crate::error::ZkdexError;
 use crate::hash::hash2;
 use crate::tx::public_key_type::PublicKeyType;
 use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::{ExternalPriceType, TimestampType};
+use crate::validate::{Validate, Validator};
 use crate::U128SerdeAsString;
 use crate::U256SerdeAsRadix16Prefix0xString;
 use crate::U32SerdeAsString;
@@ -18,7 +21,7 @@ use crate::U32SerdeAsString;
 //   (synthetic_asset_unit / synthetic_resolution).
 
 // Represents a single signature on an external price with a timestamp.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct SignedOraclePrice {
     pub signer_key: PublicKeyType,
     #[serde(with = "U128SerdeAsString")]
@@ -48,10 +51,57 @@ impl HashTrait for SignedOraclePrice {
 
 impl SignTrait for SignedOraclePrice {}
 
+impl Validate for SignedOraclePrice {
+    fn check(&self, validator: &mut Validator) {
+        validator.public_key(&self.signer_key, "signer_key");
+        validator.positive(self.external_price, "external_price");
+        validator.below(
+            self.external_price,
+            U256::from(EXTERNAL_PRICE_UPPER_BOUND),
+            "external_price",
+        );
+    }
+}
+
+impl SignedOraclePrice {
+    pub fn builder() -> SignedOraclePriceBuilder {
+        SignedOraclePriceBuilder::default()
+    }
+}
+
+/// Builds a [`SignedOraclePrice`] in memory, `build` refuses what [`Validate`] does, an unset signer
+/// or a price out of bounds, and an unset asset id.
+#[derive(Debug, Clone, Default)]
+pub struct SignedOraclePriceBuilder {
+    inner: SignedOraclePrice,
+}
+
+impl SignedOraclePriceBuilder {
+    impl_setters!(
+        signer_key: PublicKeyType,
+        external_price: ExternalPriceType,
+        timestamp: TimestampType,
+        signed_asset_id: U256,
+    );
+
+    pub fn build(self) -> crate::error::Result<SignedOraclePrice> {
+        if self.inner.signed_asset_id.is_zero() {
+            return Err(ZkdexError::InvalidTransaction(
+                "signed_asset_id is not set".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::unified::transactions::test::sign_and_verify;
+    use primitive_types::U256;
+
+    use crate::tx::public_key_type::PublicKeyType;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
     use crate::unified::transactions::SignedOraclePrice;
+    use crate::validate::Validate;
 
     #[test]
     pub fn test_deserialize() {
@@ -84,4 +134,32 @@ mod test {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    pub fn test_builder() {
+        let signed_asset_id =
+            U256::from_str_radix("425443555344000000000000000000004d616b6572", 16).unwrap();
+        let builder = SignedOraclePrice::builder()
+            .signer_key(public_key())
+            .external_price(30000000)
+            .timestamp(1651148012)
+            .signed_asset_id(signed_asset_id);
+        let price = builder.clone().build().unwrap();
+        assert!(price.validate(None).is_ok());
+        assert_eq!(price.signed_asset_id, signed_asset_id);
+        sign_and_verify(price);
+
+        let free = builder.clone().external_price(0).build().unwrap_err();
+        assert_eq!(
+            free.to_string(),
+            "invalid fields: external_price: must be at least 1"
+        );
+        let unnamed = builder.clone().signed_asset_id(U256::zero()).build();
+        assert_eq!(unnamed.unwrap_err().code(), "INVALID_TRANSACTION");
+        let unsigned = builder.signer_key(PublicKeyType::default()).build();
+        assert_eq!(
+            unsigned.unwrap_err().to_string(),
+            "invalid fields: signer_key: must be set"
+        );
+    }
 }
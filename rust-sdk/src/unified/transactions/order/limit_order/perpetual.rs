@@ -4,11 +4,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::OrderBase;
 use crate::constant::UNIFIED_PERPETUAL_LIMIT_ORDER_TYPE;
+use crate::error::ZkdexError;
 use crate::hash::new_hasher;
 use crate::hash::Hasher;
 use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::{AmountType, AssetIdType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use crate::U64SerdeStr;
 
 #[derive(Serialize, Deserialize)]
@@ -71,10 +73,56 @@ impl HashTrait for LimitOrder {
 
 impl SignTrait for LimitOrder {}
 
+impl Validate for LimitOrder {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount_synthetic, "amount_synthetic");
+        validator.positive(self.amount_collateral, "amount_collateral");
+    }
+}
+
+impl LimitOrder {
+    pub fn builder() -> LimitOrderBuilder {
+        LimitOrderBuilder::default()
+    }
+}
+
+/// Builds a perpetual [`LimitOrder`] in memory, `build` refuses what [`Validate`] does and an order
+/// whose synthetic and collateral assets are the same.
+#[derive(Debug, Clone, Default)]
+pub struct LimitOrderBuilder {
+    inner: LimitOrder,
+}
+
+impl LimitOrderBuilder {
+    impl_base_setters!();
+
+    impl_setters!(
+        position_id: u32,
+        asset_id_synthetic: u32,
+        asset_id_collateral: u32,
+        amount_synthetic: AmountType,
+        amount_collateral: AmountType,
+        amount_fee: AmountType,
+        is_buying_synthetic: bool,
+        margin_type: MarginType,
+    );
+
+    pub fn build(self) -> crate::error::Result<LimitOrder> {
+        if self.inner.asset_id_synthetic == self.inner.asset_id_collateral {
+            return Err(ZkdexError::InvalidTransaction(
+                "asset_id_synthetic and asset_id_collateral must differ".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::unified::transactions::order::perpetual::LimitOrder;
-    use crate::unified::transactions::test::sign_and_verify;
+    use crate::unified::transactions::order::perpetual::{LimitOrder, MarginType};
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
+    use crate::validate::Validate;
 
     #[test]
     fn test_sign_verify() {
@@ -99,4 +147,42 @@ mod test {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    fn test_builder() {
+        let builder = LimitOrder::builder()
+            .public_key(public_key())
+            .nonce(1)
+            .expiration_timestamp(3608164305)
+            .position_id(7)
+            .asset_id_synthetic(0x2)
+            .asset_id_collateral(0x57d05d)
+            .amount_synthetic(10)
+            .amount_collateral(100)
+            .amount_fee(1)
+            .is_buying_synthetic(true)
+            .margin_type(MarginType::Isolated);
+        let order = builder.clone().build().unwrap();
+        assert!(order.validate(None).is_ok());
+        assert_eq!(order.margin_type, MarginType::Isolated);
+        sign_and_verify(order);
+
+        let same_assets = builder.clone().asset_id_collateral(0x2).build();
+        assert_eq!(same_assets.unwrap_err().code(), "INVALID_TRANSACTION");
+        let empty = builder.amount_collateral(0).build().unwrap_err();
+        assert_eq!(
+            empty.to_string(),
+            "invalid fields: amount_collateral: must be at least 1"
+        );
+        let unsigned = LimitOrder::builder()
+            .asset_id_synthetic(0x2)
+            .amount_synthetic(1)
+            .amount_collateral(1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            unsigned.to_string(),
+            "invalid fields: public_key: must be set"
+        );
+    }
 }
@@ -1,11 +1,13 @@
 use crate::common::OrderBase;
 use crate::constant::UNIFIED_SPOT_LIMIT_ORDER_TYPE;
+use crate::error::ZkdexError;
 use crate::hash::new_hasher;
 use crate::hash::Hasher;
 use crate::serde_utils::serde_str;
 use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::{AmountType, AssetIdType, PositionIdType};
+use crate::validate::{Validate, Validator};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
@@ -53,10 +55,54 @@ impl HashTrait for LimitOrder {
 
 impl SignTrait for LimitOrder {}
 
+impl Validate for LimitOrder {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount_buy, "amount_buy");
+        validator.positive(self.amount_sell, "amount_sell");
+    }
+}
+
+impl LimitOrder {
+    pub fn builder() -> LimitOrderBuilder {
+        LimitOrderBuilder::default()
+    }
+}
+
+/// Builds a spot [`LimitOrder`] in memory, `build` refuses what [`Validate`] does and an order that
+/// buys and sells the same asset.
+#[derive(Debug, Clone, Default)]
+pub struct LimitOrderBuilder {
+    inner: LimitOrder,
+}
+
+impl LimitOrderBuilder {
+    impl_base_setters!();
+
+    impl_setters!(
+        amount_buy: AmountType,
+        amount_sell: AmountType,
+        amount_fee: AmountType,
+        asset_buy: u32,
+        asset_sell: u32,
+        position_id: u32,
+    );
+
+    pub fn build(self) -> crate::error::Result<LimitOrder> {
+        if self.inner.asset_buy == self.inner.asset_sell {
+            return Err(ZkdexError::InvalidTransaction(
+                "asset_buy and asset_sell must differ".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::unified::transactions::order::spot::LimitOrder;
-    use crate::unified::transactions::test::sign_and_verify;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
+    use crate::validate::Validate;
 
     #[test]
     fn test_sign_verify() {
@@ -82,4 +128,40 @@ mod test {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    fn test_builder() {
+        let builder = LimitOrder::builder()
+            .public_key(public_key())
+            .nonce(1)
+            .expiration_timestamp(3396833)
+            .amount_buy(80)
+            .amount_sell(70)
+            .amount_fee(1)
+            .asset_buy(0x2222)
+            .asset_sell(0x1111)
+            .position_id(9223);
+        let order = builder.clone().build().unwrap();
+        assert!(order.validate(None).is_ok());
+        assert_eq!(order.asset_sell, 0x1111);
+        sign_and_verify(order);
+
+        let same_assets = builder.clone().asset_sell(0x2222).build().unwrap_err();
+        assert_eq!(same_assets.code(), "INVALID_TRANSACTION");
+        let unsold = builder.amount_sell(0).build().unwrap_err();
+        assert_eq!(
+            unsold.to_string(),
+            "invalid fields: amount_sell: must be at least 1"
+        );
+        let unsigned = LimitOrder::builder()
+            .amount_buy(1)
+            .amount_sell(1)
+            .asset_buy(1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            unsigned.to_string(),
+            "invalid fields: public_key: must be set"
+        );
+    }
 }
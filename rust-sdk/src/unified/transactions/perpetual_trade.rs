@@ -3,8 +3,10 @@ use {
     serde::{Deserialize, Serialize},
 };
 
+use crate::error::ZkdexError;
 use crate::unified::transactions::order::perpetual::LimitOrder;
 use crate::unified::types::{AmountType, SignedAmountType};
+use crate::validate::{Validate, Validator};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[repr(C)]
@@ -19,12 +21,88 @@ pub struct PerpetualTrade {
     pub actual_b_fee: SignedAmountType,
 }
 
+impl Validate for PerpetualTrade {
+    fn check(&self, validator: &mut Validator) {
+        validator.nested("party_a_order", &self.party_a_order);
+        validator.nested("party_b_order", &self.party_b_order);
+        validator.positive(self.actual_collateral, "actual_collateral");
+        validator.positive(self.actual_synthetic, "actual_synthetic");
+    }
+}
+
+impl PerpetualTrade {
+    pub fn builder() -> PerpetualTradeBuilder {
+        PerpetualTradeBuilder::default()
+    }
+}
+
+/// Builds a [`PerpetualTrade`] from two orders, `build` refuses what [`Validate`] does and checks
+/// that the orders are on opposite sides of the same synthetic and collateral assets and that the
+/// fill fits both of them.
+#[derive(Debug, Clone, Default)]
+pub struct PerpetualTradeBuilder {
+    inner: PerpetualTrade,
+}
+
+impl PerpetualTradeBuilder {
+    impl_setters!(
+        party_a_order: LimitOrder,
+        party_b_order: LimitOrder,
+        actual_collateral: AmountType,
+        actual_synthetic: AmountType,
+        actual_a_fee: SignedAmountType,
+        actual_b_fee: SignedAmountType,
+    );
+
+    pub fn build(self) -> crate::error::Result<PerpetualTrade> {
+        let trade = &self.inner;
+        if trade.party_a_order.is_buying_synthetic == trade.party_b_order.is_buying_synthetic {
+            return Err(ZkdexError::InvalidTransaction(
+                "party_a_order and party_b_order are on the same side".to_string(),
+            ));
+        }
+        if trade.party_a_order.asset_id_synthetic != trade.party_b_order.asset_id_synthetic {
+            return Err(ZkdexError::InvalidTransaction(
+                "party_a_order and party_b_order trade different synthetic assets".to_string(),
+            ));
+        }
+        if trade.party_a_order.asset_id_collateral != trade.party_b_order.asset_id_collateral {
+            return Err(ZkdexError::InvalidTransaction(
+                "party_a_order and party_b_order trade different collateral assets".to_string(),
+            ));
+        }
+        if trade.actual_synthetic > trade.party_a_order.amount_synthetic
+            || trade.actual_synthetic > trade.party_b_order.amount_synthetic
+        {
+            return Err(ZkdexError::InvalidTransaction(
+                "actual_synthetic exceeds the order amount_synthetic".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::unified::transactions::test::sign_and_verify;
+    use primitive_types::U256;
+
+    use crate::tx::public_key_type::PublicKeyType;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
 
     use super::*;
 
+    fn order(is_buying_synthetic: bool, synthetic: u32, collateral: u32) -> LimitOrder {
+        LimitOrder::builder()
+            .public_key(public_key())
+            .asset_id_synthetic(synthetic)
+            .asset_id_collateral(collateral)
+            .amount_synthetic(10)
+            .amount_collateral(100)
+            .is_buying_synthetic(is_buying_synthetic)
+            .build()
+            .unwrap()
+    }
+
     #[test]
     fn test_trade_serde() {
         let js = r##"
@@ -121,4 +199,80 @@ mod tests {
         sign_and_verify(tx.party_a_order);
         sign_and_verify(tx.party_b_order);
     }
+
+    #[test]
+    fn test_validate() {
+        let mut tx = PerpetualTrade::default();
+        tx.party_a_order.base.public_key = PublicKeyType::new(U256::one());
+        tx.party_b_order.base = tx.party_a_order.base.clone();
+        tx.party_a_order.base.expiration_timestamp = 3608164305;
+        tx.party_b_order.base.expiration_timestamp = 36081;
+        tx.party_a_order.amount_synthetic = 1;
+        tx.party_a_order.amount_collateral = 1;
+        tx.party_b_order.amount_synthetic = 1;
+        tx.party_b_order.amount_collateral = 1;
+        tx.actual_collateral = 1;
+        tx.actual_synthetic = 1;
+        assert!(tx.validate(None).is_ok());
+
+        tx.actual_synthetic = 0;
+        tx.party_b_order.amount_collateral = 0;
+        let mut validator = Validator::new(Some(1_000_000));
+        tx.check(&mut validator);
+        let fields: Vec<_> = validator
+            .violations()
+            .iter()
+            .map(|v| v.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "party_b_order.expiration_timestamp",
+                "party_b_order.amount_collateral",
+                "actual_synthetic"
+            ]
+        );
+
+        let err = tx.validate(None).unwrap_err();
+        assert_eq!(err.code(), "INVALID_FIELDS");
+        assert!(err
+            .to_string()
+            .contains("actual_synthetic: must be at least 1"));
+    }
+
+    #[test]
+    fn test_builder() {
+        let builder = PerpetualTrade::builder()
+            .party_a_order(order(true, 0x2, 0x57d05d))
+            .party_b_order(order(false, 0x2, 0x57d05d))
+            .actual_collateral(100)
+            .actual_synthetic(10);
+        let trade = builder.clone().build().unwrap();
+        assert!(trade.validate(None).is_ok());
+        assert_eq!(trade.actual_synthetic, 10);
+        sign_and_verify(trade.party_a_order);
+
+        let invalid = |builder: PerpetualTradeBuilder| builder.build().unwrap_err().to_string();
+        assert_eq!(
+            invalid(builder.clone().party_b_order(order(true, 0x2, 0x57d05d))),
+            "invalid transaction: party_a_order and party_b_order are on the same side"
+        );
+        assert_eq!(
+            invalid(builder.clone().party_b_order(order(false, 0x3, 0x57d05d))),
+            "invalid transaction: party_a_order and party_b_order trade different synthetic assets"
+        );
+        assert_eq!(
+            invalid(builder.clone().party_b_order(order(false, 0x2, 0x1))),
+            "invalid transaction: party_a_order and party_b_order trade different collateral assets"
+        );
+        assert_eq!(
+            invalid(builder.clone().actual_synthetic(11)),
+            "invalid transaction: actual_synthetic exceeds the order amount_synthetic"
+        );
+        let err = builder.actual_collateral(0).build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid fields: actual_collateral: must be at least 1"
+        );
+    }
 }
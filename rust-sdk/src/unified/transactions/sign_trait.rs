@@ -1,10 +1,19 @@
+use crate::error::Result;
 use crate::felt::LeBytesConvert;
 use crate::tx::packed_public_key::PrivateKeyType;
 use crate::tx::sign::TxSignature;
 use crate::unified::transactions::hash_trait::HashTrait;
+use crate::validate::Validate;
 
-pub trait SignTrait: HashTrait {
-    fn sign(&self, private_key: &PrivateKeyType) -> TxSignature {
+pub trait SignTrait: HashTrait + Validate {
+    /// Sign the hash of the transaction, one that breaks any bound is refused.
+    fn sign(&self, private_key: &PrivateKeyType) -> Result<TxSignature> {
+        self.validate(None)?;
+        Ok(self.sign_unchecked(private_key))
+    }
+
+    /// Same as [`SignTrait::sign`] without the field range checks.
+    fn sign_unchecked(&self, private_key: &PrivateKeyType) -> TxSignature {
         let hash = self.hash();
         let (sig, _pk) = TxSignature::sign_msg(&private_key, hash.as_le_bytes());
         sig
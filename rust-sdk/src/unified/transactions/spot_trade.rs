@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::ZkdexError;
 use crate::serde_utils::serde_str;
 use crate::unified::transactions::order::spot::LimitOrder;
 use crate::unified::types::{AmountType, SignedAmountType};
+use crate::validate::{Validate, Validator};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct SpotTrade {
@@ -16,10 +18,78 @@ pub struct SpotTrade {
     pub actual_b_fee: SignedAmountType,
 }
 
+impl Validate for SpotTrade {
+    fn check(&self, validator: &mut Validator) {
+        validator.nested("party_a_order", &self.party_a_order);
+        validator.nested("party_b_order", &self.party_b_order);
+        validator.positive(self.actual_a_sold, "actual_a_sold");
+        validator.positive(self.actual_b_sold, "actual_b_sold");
+    }
+}
+
+impl SpotTrade {
+    pub fn builder() -> SpotTradeBuilder {
+        SpotTradeBuilder::default()
+    }
+}
+
+/// Builds a [`SpotTrade`] from two orders, `build` refuses what [`Validate`] does and checks that
+/// each party buys what the other sells and that neither party sells more than its order allows.
+#[derive(Debug, Clone, Default)]
+pub struct SpotTradeBuilder {
+    inner: SpotTrade,
+}
+
+impl SpotTradeBuilder {
+    impl_setters!(
+        party_a_order: LimitOrder,
+        party_b_order: LimitOrder,
+        actual_a_sold: AmountType,
+        actual_b_sold: AmountType,
+        actual_a_fee: SignedAmountType,
+        actual_b_fee: SignedAmountType,
+    );
+
+    pub fn build(self) -> crate::error::Result<SpotTrade> {
+        let trade = &self.inner;
+        if trade.party_a_order.asset_sell != trade.party_b_order.asset_buy
+            || trade.party_a_order.asset_buy != trade.party_b_order.asset_sell
+        {
+            return Err(ZkdexError::InvalidTransaction(
+                "party_a_order and party_b_order trade different assets".to_string(),
+            ));
+        }
+        if trade.actual_a_sold > trade.party_a_order.amount_sell {
+            return Err(ZkdexError::InvalidTransaction(
+                "actual_a_sold exceeds party_a_order.amount_sell".to_string(),
+            ));
+        }
+        if trade.actual_b_sold > trade.party_b_order.amount_sell {
+            return Err(ZkdexError::InvalidTransaction(
+                "actual_b_sold exceeds party_b_order.amount_sell".to_string(),
+            ));
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::unified::transactions::order::spot::LimitOrder;
     use crate::unified::transactions::spot_trade::SpotTrade;
-    use crate::unified::transactions::test::sign_and_verify;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
+    use crate::validate::Validate;
+
+    fn order(asset_buy: u32, asset_sell: u32) -> LimitOrder {
+        LimitOrder::builder()
+            .public_key(public_key())
+            .amount_buy(80)
+            .amount_sell(70)
+            .asset_buy(asset_buy)
+            .asset_sell(asset_sell)
+            .build()
+            .unwrap()
+    }
 
     #[test]
     fn test_trade_serde() {
@@ -104,4 +174,35 @@ mod tests {
         sign_and_verify(tx.party_a_order);
         sign_and_verify(tx.party_b_order);
     }
+
+    #[test]
+    fn test_builder() {
+        let builder = SpotTrade::builder()
+            .party_a_order(order(0x2222, 0x1111))
+            .party_b_order(order(0x1111, 0x2222))
+            .actual_a_sold(30)
+            .actual_b_sold(40);
+        let trade = builder.clone().build().unwrap();
+        assert!(trade.validate(None).is_ok());
+        assert_eq!(trade.actual_a_sold, 30);
+        sign_and_verify(trade.party_b_order);
+
+        let other_buy = builder.clone().party_b_order(order(0x1111, 0x3333));
+        assert_eq!(
+            other_buy.build().unwrap_err().to_string(),
+            "invalid transaction: party_a_order and party_b_order trade different assets"
+        );
+        let other_sell = builder.clone().party_b_order(order(0x3333, 0x2222));
+        assert_eq!(
+            other_sell.build().unwrap_err().code(),
+            "INVALID_TRANSACTION"
+        );
+        let oversold = builder.clone().actual_a_sold(71).build().unwrap_err();
+        assert_eq!(oversold.code(), "INVALID_TRANSACTION");
+        let unsold = builder.actual_b_sold(0).build().unwrap_err();
+        assert_eq!(
+            unsold.to_string(),
+            "invalid fields: actual_b_sold: must be at least 1"
+        );
+    }
 }
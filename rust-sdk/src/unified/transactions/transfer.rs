@@ -11,6 +11,7 @@ use crate::types::AmountType;
 use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::{AssetIdType, PositionIdType};
+use crate::validate::{Validate, Validator};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[repr(C)]
@@ -24,6 +25,14 @@ pub struct Transfer {
     pub sender_position_id: PositionIdType,
     pub receiver_position_id: PositionIdType,
     pub receiver_public_key: PublicKeyType,
+    /// The maximal fee, the fee fields are optional and not part of the hash while it is zero.
+    #[serde(default, with = "serde_str")]
+    pub fee: AmountType,
+    /// Set exactly when there is a fee, along with `fee_position_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_asset_id: Option<AssetIdType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_position_id: Option<PositionIdType>,
 }
 
 impl HashTrait for Transfer {
@@ -53,16 +62,97 @@ impl HashTrait for Transfer {
 
         hasher.update_single(&packed_message1);
 
+        // Add the fee to the signature hash if exists.
+        if transfer.fee != 0 {
+            let packed_message2 = U256([
+                transfer.fee,
+                transfer.fee_asset_id.unwrap_or_default().into(),
+                transfer.fee_position_id.unwrap_or_default().into(),
+                0,
+            ]);
+            hasher.update_single(&packed_message2);
+        }
+
         hasher.finalize()
     }
 }
 
 impl SignTrait for Transfer {}
 
+impl Validate for Transfer {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "sender_public_key");
+        validator.public_key(&self.receiver_public_key, "receiver_public_key");
+        validator.positive(self.amount, "amount");
+        self.check_fee(validator);
+    }
+}
+
+impl Transfer {
+    pub fn builder() -> TransferBuilder {
+        TransferBuilder::default()
+    }
+
+    /// The fee fields go with a fee, they would be serialized without being signed otherwise.
+    fn check_fee(&self, validator: &mut Validator) {
+        let fields = [
+            ("fee_asset_id", self.fee_asset_id.is_some()),
+            ("fee_position_id", self.fee_position_id.is_some()),
+        ];
+        for (field, set) in fields {
+            if self.fee != 0 && !set {
+                validator.push(field, "must be set with a fee");
+            } else if self.fee == 0 && set {
+                validator.push(field, "must not be set without a fee");
+            }
+        }
+    }
+}
+
+/// Builds a [`Transfer`] in memory, `build` refuses what [`Validate`] does: unset keys, a zero
+/// amount and fee fields without a fee or a fee without them.
+#[derive(Debug, Clone, Default)]
+pub struct TransferBuilder {
+    inner: Transfer,
+}
+
+impl TransferBuilder {
+    impl_base_setters!();
+
+    impl_setters!(
+        amount: AmountType,
+        asset_id: u32,
+        synthetic_id: u32,
+        sender_position_id: u32,
+        receiver_position_id: u32,
+        receiver_public_key: PublicKeyType,
+        fee: AmountType,
+    );
+
+    pub fn fee_asset_id(mut self, fee_asset_id: u32) -> Self {
+        self.inner.fee_asset_id = Some(fee_asset_id.into());
+        self
+    }
+
+    pub fn fee_position_id(mut self, fee_position_id: u32) -> Self {
+        self.inner.fee_position_id = Some(fee_position_id.into());
+        self
+    }
+
+    pub fn build(self) -> crate::error::Result<Transfer> {
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::unified::transactions::test::sign_and_verify;
-    use crate::unified::transactions::Transfer;
+    use std::convert::TryFrom;
+
+    use crate::hash_type::hash_type_to_string_with_0xprefix;
+    use crate::tx::packed_public_key::PackedPublicKey;
+    use crate::unified::transactions::test::{sign_and_verify, PUB_KEY};
+    use crate::unified::transactions::{HashTrait, Transfer};
+    use crate::validate::Validate;
 
     #[test]
     fn test_transfer_serde() {
@@ -103,4 +193,158 @@ mod tests {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    fn test_builder() {
+        let js = r##"
+        {
+            "amount": "7758176404715800194",
+            "asset_id": "0x1234",
+            "synthetic_id" : "0x0",
+            "expiration_timestamp": "2404381470",
+            "nonce": "2195908194",
+            "receiver_position_id": "609106",
+            "receiver_public_key": "0x259f432e6f4590b9a164106cf6a659eb4862b21fb97d43588561712e8e5216b",
+            "sender_position_id": "93098",
+            "sender_public_key": "0x28e4d45cd0538ffa6fdc09e70f0fea4e56c47fda87a2a969c22b4fdfe997f60"
+        }
+        "##;
+        let expected = serde_json::from_str::<Transfer>(js).unwrap();
+
+        let tx = Transfer::builder()
+            .nonce(2195908194)
+            .public_key(expected.base.public_key.clone())
+            .expiration_timestamp(2404381470)
+            .amount(7758176404715800194)
+            .asset_id(0x1234)
+            .synthetic_id(0)
+            .sender_position_id(93098)
+            .receiver_position_id(609106)
+            .receiver_public_key(expected.receiver_public_key.clone())
+            .build()
+            .unwrap();
+        assert_eq!(tx, expected);
+        assert_eq!(tx.hash(), expected.hash());
+
+        let pub_key = PackedPublicKey::try_from(PUB_KEY.trim_start_matches("0x")).unwrap();
+        let tx = Transfer::builder()
+            .public_key(pub_key.clone().into())
+            .receiver_public_key(pub_key.into())
+            .amount(1)
+            .build()
+            .unwrap();
+        sign_and_verify(tx);
+    }
+
+    #[test]
+    fn test_transfer_fee() {
+        let js = r##"
+        {
+            "amount": "7758176404715800194",
+            "asset_id": "0x1234",
+            "synthetic_id" : "0x0",
+            "expiration_timestamp": "2404381470",
+            "nonce": "2195908194",
+            "receiver_position_id": "609106",
+            "receiver_public_key": "0x259f432e6f4590b9a164106cf6a659eb4862b21fb97d43588561712e8e5216b",
+            "sender_position_id": "93098",
+            "sender_public_key": "0x28e4d45cd0538ffa6fdc09e70f0fea4e56c47fda87a2a969c22b4fdfe997f60",
+            "fee": "1000",
+            "fee_asset_id": "0x1234",
+            "fee_position_id": "93098"
+        }
+        "##;
+        let tx = serde_json::from_str::<Transfer>(js).unwrap();
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(tx.hash()),
+            "0x24d6587cb82c110fa183d0ceee73ff0942f2031254841b01a6f4a74a222ceaad"
+        );
+        let mut value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["fee"], "1000");
+        assert_eq!(value["fee_position_id"], "93098");
+
+        // without a fee the other fee fields are not signed
+        let mut free = tx.clone();
+        free.fee = 0;
+        assert_eq!(
+            hash_type_to_string_with_0xprefix(free.hash()),
+            "0x2f2a9282913e2163832315349f18e4d897d75936c4fa6427c1794ca8ea8b2948"
+        );
+        let fields = value.as_object_mut().unwrap();
+        for field in ["fee", "fee_asset_id", "fee_position_id"] {
+            fields.remove(field);
+        }
+        let without_fee = serde_json::from_value::<Transfer>(value).unwrap();
+        assert_eq!(without_fee.hash(), free.hash());
+        let value = serde_json::to_value(&without_fee).unwrap();
+        assert!(value.get("fee_asset_id").is_none());
+        assert!(value.get("fee_position_id").is_none());
+        assert!(free.validate(None).is_err());
+        assert!(without_fee.validate(None).is_ok());
+
+        let built = Transfer::builder()
+            .nonce(2195908194)
+            .public_key(tx.base.public_key.clone())
+            .expiration_timestamp(2404381470)
+            .amount(7758176404715800194)
+            .asset_id(0x1234)
+            .sender_position_id(93098)
+            .receiver_position_id(609106)
+            .receiver_public_key(tx.receiver_public_key.clone())
+            .fee(1000)
+            .fee_asset_id(0x1234)
+            .fee_position_id(93098)
+            .build()
+            .unwrap();
+        assert_eq!(built, tx);
+
+        let pub_key = PackedPublicKey::try_from(PUB_KEY.trim_start_matches("0x")).unwrap();
+        let tx = Transfer::builder()
+            .public_key(pub_key.clone().into())
+            .receiver_public_key(pub_key.into())
+            .amount(1)
+            .fee(1)
+            .fee_asset_id(1)
+            .fee_position_id(0)
+            .build()
+            .unwrap();
+        sign_and_verify(tx);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_fields() {
+        let pub_key = PackedPublicKey::try_from(PUB_KEY.trim_start_matches("0x")).unwrap();
+        let missing_amount = Transfer::builder()
+            .public_key(pub_key.clone().into())
+            .receiver_public_key(pub_key.clone().into())
+            .build();
+        assert_eq!(
+            missing_amount.unwrap_err().to_string(),
+            "invalid fields: amount: must be at least 1"
+        );
+
+        let missing_receiver = Transfer::builder()
+            .public_key(pub_key.clone().into())
+            .amount(1)
+            .build();
+        let missing_receiver = missing_receiver.unwrap_err();
+        assert_eq!(missing_receiver.code(), "INVALID_FIELDS");
+        assert!(missing_receiver.to_string().contains("receiver_public_key"));
+
+        let builder = Transfer::builder()
+            .public_key(pub_key.clone().into())
+            .receiver_public_key(pub_key.into())
+            .amount(1);
+        let missing_fee_fields = builder.clone().fee(1).fee_asset_id(1).build().unwrap_err();
+        assert_eq!(
+            missing_fee_fields.to_string(),
+            "invalid fields: fee_position_id: must be set with a fee"
+        );
+        let missing_fee = builder
+            .fee_asset_id(1)
+            .fee_position_id(0)
+            .build()
+            .unwrap_err();
+        assert_eq!(missing_fee.code(), "INVALID_FIELDS");
+    }
 }
@@ -0,0 +1,325 @@
+use primitive_types::U256;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::error::{parse_json, parse_json_value, ZkdexError};
+use crate::tx::public_key_type::PublicKeyType;
+use crate::unified::transactions::hash_trait::HashTrait;
+use crate::unified::transactions::order::perpetual::MarginType;
+use crate::unified::transactions::order::{perpetual, spot};
+use crate::unified::transactions::{
+    Liquidate, PerpetualTrade, SignedOraclePrice, SpotTrade, Transfer, Withdrawal,
+};
+use crate::validate::{Validate, Validator};
+
+pub const UNIFIED_TRANSFER_TYPE_NAME: &str = "UNIFIED_TRANSFER";
+pub const UNIFIED_WITHDRAWAL_TYPE_NAME: &str = "UNIFIED_WITHDRAWAL";
+pub const UNIFIED_SPOT_LIMIT_ORDER_TYPE_NAME: &str = "UNIFIED_SPOT_LIMIT_ORDER";
+pub const UNIFIED_SPOT_TRADE_TYPE_NAME: &str = "UNIFIED_SPOT_TRADE";
+pub const UNIFIED_PERP_TRADE_TYPE_NAME: &str = "UNIFIED_PERP_TRADE";
+pub const UNIFIED_LIQUIDATE_TYPE_NAME: &str = "UNIFIED_LIQUIDATE";
+pub const UNIFIED_ORACLE_PRICE_TYPE_NAME: &str = "UNIFIED_ORACLE_PRICE";
+// A perpetual limit order already carries its margin type in "type".
+pub const PERP_CROSS_TYPE_NAME: &str = "PERP_CROSS";
+pub const PERP_ISO_TYPE_NAME: &str = "PERP_ISO";
+
+const TYPES: &[&str] = &[
+    UNIFIED_TRANSFER_TYPE_NAME,
+    UNIFIED_WITHDRAWAL_TYPE_NAME,
+    UNIFIED_SPOT_LIMIT_ORDER_TYPE_NAME,
+    PERP_CROSS_TYPE_NAME,
+    PERP_ISO_TYPE_NAME,
+    UNIFIED_SPOT_TRADE_TYPE_NAME,
+    UNIFIED_PERP_TRADE_TYPE_NAME,
+    UNIFIED_LIQUIDATE_TYPE_NAME,
+    UNIFIED_ORACLE_PRICE_TYPE_NAME,
+];
+
+/// Any unified transaction, selected by the `"type"` field of its json.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnifiedTransaction {
+    Transfer(Transfer),
+    Withdrawal(Withdrawal),
+    SpotLimitOrder(spot::LimitOrder),
+    PerpetualLimitOrder(perpetual::LimitOrder),
+    SpotTrade(SpotTrade),
+    PerpetualTrade(PerpetualTrade),
+    Liquidate(Liquidate),
+    OraclePrice(SignedOraclePrice),
+}
+
+impl UnifiedTransaction {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            UnifiedTransaction::Transfer(_) => UNIFIED_TRANSFER_TYPE_NAME,
+            UnifiedTransaction::Withdrawal(_) => UNIFIED_WITHDRAWAL_TYPE_NAME,
+            UnifiedTransaction::SpotLimitOrder(_) => UNIFIED_SPOT_LIMIT_ORDER_TYPE_NAME,
+            UnifiedTransaction::PerpetualLimitOrder(order) => match order.margin_type {
+                MarginType::Cross => PERP_CROSS_TYPE_NAME,
+                MarginType::Isolated => PERP_ISO_TYPE_NAME,
+            },
+            UnifiedTransaction::SpotTrade(_) => UNIFIED_SPOT_TRADE_TYPE_NAME,
+            UnifiedTransaction::PerpetualTrade(_) => UNIFIED_PERP_TRADE_TYPE_NAME,
+            UnifiedTransaction::Liquidate(_) => UNIFIED_LIQUIDATE_TYPE_NAME,
+            UnifiedTransaction::OraclePrice(_) => UNIFIED_ORACLE_PRICE_TYPE_NAME,
+        }
+    }
+
+    pub fn is_trade(&self) -> bool {
+        matches!(
+            self,
+            UnifiedTransaction::SpotTrade(_) | UnifiedTransaction::PerpetualTrade(_)
+        )
+    }
+
+    /// Every message that has to be signed, with the public key expected to sign it.
+    /// A trade yields party A first and party B second, everything else yields a single entry.
+    pub fn signers(&self) -> Vec<(&PublicKeyType, U256)> {
+        match self {
+            UnifiedTransaction::Transfer(tx) => vec![(&tx.base.public_key, tx.hash())],
+            UnifiedTransaction::Withdrawal(tx) => vec![(&tx.base.public_key, tx.hash())],
+            UnifiedTransaction::SpotLimitOrder(tx) => vec![(&tx.base.public_key, tx.hash())],
+            UnifiedTransaction::PerpetualLimitOrder(tx) => vec![(&tx.base.public_key, tx.hash())],
+            UnifiedTransaction::SpotTrade(tx) => vec![
+                (&tx.party_a_order.base.public_key, tx.party_a_order.hash()),
+                (&tx.party_b_order.base.public_key, tx.party_b_order.hash()),
+            ],
+            UnifiedTransaction::PerpetualTrade(tx) => vec![
+                (&tx.party_a_order.base.public_key, tx.party_a_order.hash()),
+                (&tx.party_b_order.base.public_key, tx.party_b_order.hash()),
+            ],
+            UnifiedTransaction::Liquidate(tx) => {
+                vec![(&tx.liquidator_order.base.public_key, tx.hash())]
+            }
+            UnifiedTransaction::OraclePrice(tx) => vec![(&tx.signer_key, tx.hash())],
+        }
+    }
+}
+
+impl Validate for UnifiedTransaction {
+    fn check(&self, validator: &mut Validator) {
+        match self {
+            UnifiedTransaction::Transfer(tx) => tx.check(validator),
+            UnifiedTransaction::Withdrawal(tx) => tx.check(validator),
+            UnifiedTransaction::SpotLimitOrder(tx) => tx.check(validator),
+            UnifiedTransaction::PerpetualLimitOrder(tx) => tx.check(validator),
+            UnifiedTransaction::SpotTrade(tx) => tx.check(validator),
+            UnifiedTransaction::PerpetualTrade(tx) => tx.check(validator),
+            UnifiedTransaction::Liquidate(tx) => tx.check(validator),
+            UnifiedTransaction::OraclePrice(tx) => tx.check(validator),
+        }
+    }
+}
+
+impl Serialize for UnifiedTransaction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = match self {
+            UnifiedTransaction::Transfer(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::Withdrawal(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::SpotLimitOrder(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::PerpetualLimitOrder(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::SpotTrade(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::PerpetualTrade(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::Liquidate(tx) => serde_json::to_value(tx),
+            UnifiedTransaction::OraclePrice(tx) => serde_json::to_value(tx),
+        };
+        let mut value = value.map_err(ser::Error::custom)?;
+        if let Value::Object(map) = &mut value {
+            map.insert("type".to_string(), Value::from(self.type_name()));
+        }
+        value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnifiedTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Self::from_value(value).map_err(de::Error::custom)
+    }
+}
+
+impl UnifiedTransaction {
+    /// Parse a transaction from json, a failure reports the path of the offending field.
+    pub fn from_json(json: &str) -> crate::error::Result<Self> {
+        let value: Value = parse_json(json)?;
+        Self::from_value(value)
+    }
+
+    pub(crate) fn from_value(value: Value) -> crate::error::Result<Self> {
+        let tx_type = match value.get("type").and_then(Value::as_str) {
+            Some(tx_type) => tx_type.to_string(),
+            None => {
+                return Err(ZkdexError::MalformedJson {
+                    path: "type".to_string(),
+                    reason: "missing field `type`".to_string(),
+                })
+            }
+        };
+
+        match tx_type.as_str() {
+            UNIFIED_TRANSFER_TYPE_NAME => parse_json_value(value).map(Self::Transfer),
+            UNIFIED_WITHDRAWAL_TYPE_NAME => parse_json_value(value).map(Self::Withdrawal),
+            UNIFIED_SPOT_LIMIT_ORDER_TYPE_NAME => parse_json_value(value).map(Self::SpotLimitOrder),
+            PERP_CROSS_TYPE_NAME | PERP_ISO_TYPE_NAME => {
+                parse_json_value(value).map(Self::PerpetualLimitOrder)
+            }
+            UNIFIED_SPOT_TRADE_TYPE_NAME => parse_json_value(value).map(Self::SpotTrade),
+            UNIFIED_PERP_TRADE_TYPE_NAME => parse_json_value(value).map(Self::PerpetualTrade),
+            UNIFIED_LIQUIDATE_TYPE_NAME => parse_json_value(value).map(Self::Liquidate),
+            UNIFIED_ORACLE_PRICE_TYPE_NAME => parse_json_value(value).map(Self::OraclePrice),
+            other => Err(ZkdexError::MalformedJson {
+                path: "type".to_string(),
+                reason: format!("unknown variant `{}`, expected one of {:?}", other, TYPES),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unified::transactions::test::{PRI_KEY, PUB_KEY};
+    use crate::unified::{hash_any, sign_any, verify_any, AnyHash, AnySignature};
+
+    const PERP_TRADE: &str = r##"
+{
+    "party_a_order":{
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484339",
+        "order_type":"LIMIT_ORDER_WITH_FEES",
+        "position_id":"4805234",
+        "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+    },
+    "party_b_order":{
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874138764573096",
+        "amount_fee":"17677494534592486883",
+        "amount_synthetic":"15460142528840632302",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"36081",
+        "is_buying_synthetic":false,
+        "nonce":"12104",
+        "order_type":"LIMIT_ORDER_WITH_FEES",
+        "position_id":"48052349",
+        "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+    },
+    "actual_a_fee":"87916620",
+    "actual_b_fee":"-9309",
+    "actual_collateral":"775817",
+    "actual_synthetic":"1530808",
+    "type":"UNIFIED_PERP_TRADE"
+}
+    "##;
+
+    #[test]
+    fn test_dispatch_on_type() {
+        let tx: UnifiedTransaction = serde_json::from_str(PERP_TRADE).unwrap();
+        assert_eq!(tx.type_name(), UNIFIED_PERP_TRADE_TYPE_NAME);
+        assert!(tx.is_trade());
+        assert_eq!(tx.signers().len(), 2);
+
+        let order = r##"
+        {
+        "type":"PERP_ISO",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484339",
+        "position_id":"4805234",
+        "public_key":"0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7"
+        }
+        "##;
+        let tx: UnifiedTransaction = serde_json::from_str(order).unwrap();
+        match &tx {
+            UnifiedTransaction::PerpetualLimitOrder(order) => {
+                assert_eq!(order.margin_type, MarginType::Isolated)
+            }
+            _ => panic!("expected a perpetual limit order"),
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let tx: UnifiedTransaction = serde_json::from_str(PERP_TRADE).unwrap();
+        let json = serde_json::to_string(&tx).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["type"], UNIFIED_PERP_TRADE_TYPE_NAME);
+        let tx2: UnifiedTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(tx, tx2);
+    }
+
+    #[test]
+    fn test_unknown_or_missing_type() {
+        assert!(serde_json::from_str::<UnifiedTransaction>(r#"{"type":"DEPOSIT"}"#).is_err());
+        assert!(serde_json::from_str::<UnifiedTransaction>(r#"{"nonce":"1"}"#).is_err());
+        let err = UnifiedTransaction::from_json(r#"{"type":"DEPOSIT"}"#).unwrap_err();
+        assert_eq!(err.code(), "MALFORMED_JSON");
+    }
+
+    #[test]
+    fn test_sign_hash_verify_any() {
+        // Only party A is owned by PRI_KEY.
+        let signature = match sign_any(PERP_TRADE, PRI_KEY).unwrap() {
+            AnySignature::Compose {
+                signature_a: Some(signature),
+                signature_b: None,
+            } => signature,
+            other => panic!("unexpected signature {:?}", other),
+        };
+        assert!(matches!(
+            hash_any(PERP_TRADE).unwrap(),
+            AnyHash::Compose { .. }
+        ));
+        assert!(verify_any(PERP_TRADE, signature.clone(), PUB_KEY).unwrap());
+
+        let order = r##"
+        {
+        "type":"PERP_CROSS",
+        "amount_collateral":"15334874",
+        "amount_fee":"1767749",
+        "amount_synthetic":"15460142",
+        "asset_id_collateral":"0x57d05d",
+        "asset_id_synthetic":"0x2",
+        "expiration_timestamp":"3608164305",
+        "is_buying_synthetic":true,
+        "nonce":"1210484340",
+        "position_id":"4805234",
+        "public_key":"0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a"
+        }
+        "##;
+        let order_signature = match sign_any(order, PRI_KEY).unwrap() {
+            AnySignature::Single(signature) => signature,
+            other => panic!("unexpected signature {:?}", other),
+        };
+        assert!(matches!(hash_any(order).unwrap(), AnyHash::Single(_)));
+        assert!(verify_any(order, order_signature, PUB_KEY).unwrap());
+        // The trade signature does not cover an order with another nonce.
+        assert!(!verify_any(order, signature, PUB_KEY).unwrap());
+
+        // Party B of the trade is not owned by PRI_KEY.
+        let not_owned = order.replace(
+            "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+            "0x6b974202431eb8c0692c9c8111528d947bc7e70f7ffefaffbab7455dfa5d4f7",
+        );
+        let err = sign_any(&not_owned, PRI_KEY).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PRIVATE_KEY");
+    }
+}
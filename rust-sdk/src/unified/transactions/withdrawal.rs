@@ -12,6 +12,7 @@ use crate::unified::transactions::hash_trait::HashTrait;
 use crate::unified::transactions::sign_trait::SignTrait;
 use crate::unified::types::chain_id::ChainIdType;
 use crate::unified::types::{AssetIdType, PositionIdType};
+use crate::validate::{Validate, Validator};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Withdrawal {
@@ -66,10 +67,50 @@ impl HashTrait for Withdrawal {
 
 impl SignTrait for Withdrawal {}
 
+impl Validate for Withdrawal {
+    fn check(&self, validator: &mut Validator) {
+        validator.order_base(&self.base, "public_key");
+        validator.positive(self.amount, "amount");
+    }
+}
+
+impl Withdrawal {
+    pub fn builder() -> WithdrawalBuilder {
+        WithdrawalBuilder::default()
+    }
+}
+
+/// Builds a [`Withdrawal`] in memory, `build` refuses what [`Validate`] does. When `owner_key` is
+/// left unset the withdrawal goes to the public key itself.
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawalBuilder {
+    inner: Withdrawal,
+}
+
+impl WithdrawalBuilder {
+    impl_base_setters!();
+
+    impl_setters!(
+        owner_key: PublicKeyType,
+        amount: AmountType,
+        fee: AmountType,
+        asset_id: u32,
+        position_id: u32,
+        chain_id: u32,
+    );
+
+    pub fn build(mut self) -> crate::error::Result<Withdrawal> {
+        if self.inner.owner_key.0.is_zero() {
+            self.inner.owner_key = self.inner.base.public_key.clone();
+        }
+        self.inner.validate(None).map(|_| self.inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::unified::transactions::test::sign_and_verify;
-    use crate::unified::transactions::Withdrawal;
+    use crate::unified::transactions::test::{public_key, sign_and_verify};
+    use crate::unified::transactions::{HashTrait, Withdrawal};
 
     #[test]
     fn test_withdrawal_serde() {
@@ -110,4 +151,55 @@ mod tests {
         let tx = tx.unwrap();
         sign_and_verify(tx);
     }
+
+    #[test]
+    fn test_builder() {
+        let js = r##"
+        {
+            "amount": "1682637359498011204",
+            "eth_address": "0xB6aD5EfBd6aDfa29dEfad5BC0f8cE0ad57d4c5Fb",
+            "expiration_timestamp": "2101470722",
+            "asset_id": "0x11111",
+            "nonce": "4265854110",
+            "position_id": "775817640",
+            "fee":"0",
+            "public_key": "0x0d4a693a09887aabea49f49a7a0968929f17b65134ab3b26201e49a43cbe7c2a",
+            "chain_id": "123"
+        }
+        "##;
+        let expected = serde_json::from_str::<Withdrawal>(js).unwrap();
+        let tx = Withdrawal::builder()
+            .nonce(4265854110)
+            .public_key(expected.base.public_key.clone())
+            .expiration_timestamp(2101470722)
+            .owner_key(expected.owner_key.clone())
+            .amount(1682637359498011204)
+            .asset_id(0x11111)
+            .position_id(775817640)
+            .chain_id(123)
+            .build()
+            .unwrap();
+        assert_eq!(tx, expected);
+        assert_eq!(tx.hash(), expected.hash());
+
+        // without an owner key the withdrawal goes to the public key itself
+        let tx = Withdrawal::builder()
+            .public_key(public_key())
+            .amount(1)
+            .build()
+            .unwrap();
+        assert_eq!(tx.owner_key, tx.base.public_key);
+        sign_and_verify(tx);
+
+        let empty = Withdrawal::builder().public_key(public_key()).build();
+        assert_eq!(
+            empty.unwrap_err().to_string(),
+            "invalid fields: amount: must be at least 1"
+        );
+        let unsigned = Withdrawal::builder().amount(1).build();
+        assert_eq!(
+            unsigned.unwrap_err().to_string(),
+            "invalid fields: public_key: must be set"
+        );
+    }
 }
@@ -5,6 +5,7 @@ mod position_id;
 mod signed_amount;
 
 pub use asset_id::*;
+pub use chain_id::*;
 pub use position_id::*;
 pub use signed_amount::*;
 
@@ -7,6 +7,16 @@ pub struct SignedAmountType {
 
 use crate::types::AmountType;
 
+impl From<&SignedAmountType> for i128 {
+    fn from(value: &SignedAmountType) -> Self {
+        if value.is_neg {
+            -(value.amount as i128)
+        } else {
+            value.amount as i128
+        }
+    }
+}
+
 mod native_env {
     use super::*;
     use num_traits::Zero;
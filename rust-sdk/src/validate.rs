@@ -0,0 +1,141 @@
+//! Range checks run on a transaction before it is hashed and signed.
+//!
+//! The `sign_*` functions, json and typed alike, and [`SignTrait::sign`] refuse a transaction
+//! that breaks any bound, their `*_unchecked` counterparts skip the checks.
+//!
+//! [`SignTrait::sign`]: crate::unified::transactions::sign_trait::SignTrait::sign
+
+use std::fmt::{Display, Formatter};
+
+use primitive_types::U256;
+
+use crate::common::OrderBase;
+use crate::constant::POSITIVE_AMOUNT_LOWER_BOUND;
+use crate::error::{Result, ZkdexError};
+use crate::tx::public_key_type::PublicKeyType;
+use crate::types::{AmountType, TimestampType};
+
+/// A violated bound, `field` is the json path of the offending value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub field: String,
+    pub reason: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+
+/// Walks a transaction and collects every violated bound.
+#[derive(Debug, Default)]
+pub struct Validator {
+    now: Option<TimestampType>,
+    prefix: String,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    /// `now` is the current time in seconds, a transaction that expired before it is rejected.
+    pub fn new(now: Option<TimestampType>) -> Self {
+        Self {
+            now,
+            ..Default::default()
+        }
+    }
+
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    pub fn finish(self) -> Result<()> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ZkdexError::InvalidFields(self.violations))
+        }
+    }
+
+    pub fn push(&mut self, field: &str, reason: impl Into<String>) {
+        self.violations.push(Violation {
+            field: format!("{}{}", self.prefix, field),
+            reason: reason.into(),
+        });
+    }
+
+    /// Check a nested object, the violations it reports are prefixed with `field`.
+    pub fn nested(&mut self, field: &str, value: &impl Validate) {
+        let len = self.prefix.len();
+        self.prefix.push_str(field);
+        self.prefix.push('.');
+        value.check(self);
+        self.prefix.truncate(len);
+    }
+
+    pub fn order_base(&mut self, base: &OrderBase, public_key_field: &str) {
+        self.public_key(&base.public_key, public_key_field);
+        if let Some(now) = self.now {
+            if base.expiration_timestamp < now {
+                self.push(
+                    "expiration_timestamp",
+                    format!("expired at {}, now is {}", base.expiration_timestamp, now),
+                );
+            }
+        }
+    }
+
+    pub fn public_key(&mut self, key: &PublicKeyType, field: &str) {
+        if key.0.is_zero() {
+            self.push(field, "must be set");
+        }
+    }
+
+    pub fn positive(&mut self, amount: impl Into<u128>, field: &str) {
+        if amount.into() < POSITIVE_AMOUNT_LOWER_BOUND as u128 {
+            self.push(
+                field,
+                format!("must be at least {}", POSITIVE_AMOUNT_LOWER_BOUND),
+            );
+        }
+    }
+
+    pub fn below(&mut self, value: impl Into<U256>, bound: U256, field: &str) {
+        if value.into() >= bound {
+            self.push(field, format!("must be below {:#x}", bound));
+        }
+    }
+
+    /// A position holding `balance` of a synthetic buys, or sells, `amount` of it: the trade must
+    /// bring the balance toward zero, neither opening the position nor flipping its side.
+    pub fn reduces_position(
+        &mut self,
+        balance: impl Into<i128>,
+        is_buying: bool,
+        amount: AmountType,
+        field: &str,
+    ) {
+        let balance = balance.into();
+        if is_buying && balance >= 0 {
+            self.push(field, "buys but the position is not short");
+        } else if !is_buying && balance <= 0 {
+            self.push(field, "sells but the position is not long");
+        } else if amount as u128 > balance.unsigned_abs() {
+            self.push(field, format!("trades more than the balance {}", balance));
+        }
+    }
+}
+
+/// Field range checks of a transaction.
+pub trait Validate {
+    /// Report every violated bound to `validator`.
+    fn check(&self, validator: &mut Validator);
+
+    /// Fail with [`ZkdexError::InvalidFields`] listing every violated bound, a transaction that
+    /// expires before `now` (seconds) is rejected as well.
+    fn validate(&self, now: Option<TimestampType>) -> Result<()> {
+        let mut validator = Validator::new(now);
+        self.check(&mut validator);
+        validator.finish()
+    }
+}
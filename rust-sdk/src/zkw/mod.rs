@@ -1,45 +1,103 @@
 use halo2_proofs::arithmetic::FieldExt;
 use primitive_types::U256;
 
+use crate::zkw::native::{element_from_limbs, element_to_limbs, NativePoseidon};
 use crate::zkw::poseidon::PoseidonContext;
 
+pub mod native;
 mod poseidon;
 
-pub struct PoseidonHasher(u64, PoseidonContext);
+/// Poseidon of u64 limbs, each 4 limbs make one field element. Same hashes as
+/// [`ZkwPoseidonHasher`], computed by [`NativePoseidon`].
+pub struct PoseidonHasher {
+    poseidon: NativePoseidon,
+    limbs: [u64; 4],
+    len: usize,
+}
 
 impl PoseidonHasher {
+    pub fn new() -> Self {
+        Self {
+            poseidon: NativePoseidon::new(),
+            limbs: [0; 4],
+            len: 0,
+        }
+    }
+
+    pub fn update(&mut self, v: u64) {
+        self.limbs[self.len] = v;
+        self.len += 1;
+        if self.len == 4 {
+            self.poseidon.update(element_from_limbs(&self.limbs));
+            self.len = 0;
+        }
+    }
+
+    /// The hash of `data` in one go, see [`ZkwPoseidonHasher::hash`] for `padding`.
+    pub fn hash(data: &[u64], padding: bool) -> [u64; 4] {
+        let mut hasher = Self::new();
+        feed(data, padding, |v| hasher.update(v));
+        hasher.finalize()
+    }
+
+    /// The hash of the limbs so far, zero padded to a full element. The hasher starts over.
+    pub fn finalize(&mut self) -> [u64; 4] {
+        while self.len != 0 {
+            self.update(0);
+        }
+        let poseidon = std::mem::take(&mut self.poseidon);
+        element_to_limbs(&poseidon.finalize())
+    }
+}
+
+impl Default for PoseidonHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The hasher of the zkWasm sdk, it runs every 4 limbs through a field reducer and clones the
+/// sponge of the `poseidon` crate per hash. Kept as the reference of [`PoseidonHasher`].
+pub struct ZkwPoseidonHasher(u64, PoseidonContext);
+
+impl ZkwPoseidonHasher {
     pub fn new() -> Self {
         let mut ctx = PoseidonContext::default();
         ctx.poseidon_new(1u64 as usize);
-        PoseidonHasher(0u64, ctx)
+        ZkwPoseidonHasher(0u64, ctx)
     }
 
     pub fn hash(data: &[u64], padding: bool) -> [u64; 4] {
         let mut hasher = Self::new();
-        if padding {
-            let group = data.len() / 3;
-            let mut j = 0;
-            for i in 0..group {
-                j = i * 3;
-                hasher.update(data[j]);
-                hasher.update(data[j + 1]);
-                hasher.update(data[j + 2]);
-                hasher.update(0u64);
-            }
-            j += 3;
-            for i in j..data.len() {
-                hasher.update(data[i]);
-            }
-        } else {
-            for d in data {
-                hasher.update(*d);
-            }
-        }
+        feed(data, padding, |v| hasher.update(v));
         hasher.finalize()
     }
 }
 
-impl PoseidonHasher {
+/// Pass `data` to `update`, with `padding` a zero limb follows every 3 limbs.
+fn feed(data: &[u64], padding: bool, mut update: impl FnMut(u64)) {
+    if padding {
+        let group = data.len() / 3;
+        let mut j = 0;
+        for i in 0..group {
+            j = i * 3;
+            update(data[j]);
+            update(data[j + 1]);
+            update(data[j + 2]);
+            update(0u64);
+        }
+        j += 3;
+        for d in data.iter().skip(j) {
+            update(*d);
+        }
+    } else {
+        for d in data {
+            update(*d);
+        }
+    }
+}
+
+impl ZkwPoseidonHasher {
     pub fn update(&mut self, v: u64) {
         self.1.poseidon_push(v);
         self.0 += 1;
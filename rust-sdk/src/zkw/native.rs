@@ -0,0 +1,263 @@
+//! Poseidon on the host, working directly on `Fr`.
+//!
+//! The permutation is the one of the `poseidon` crate with its optimized constants: the first
+//! full rounds, the partial rounds with sparse MDS matrices, then the last full rounds. The state
+//! lives on the stack and the constants are read from a single [`Spec`], nothing is cloned or
+//! allocated per hash.
+
+use std::convert::TryInto;
+
+use ff::{Field, PrimeField};
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::pairing::bn256::Fr;
+use poseidon::Spec;
+
+const T: usize = 9;
+const RATE: usize = 8;
+const R_F: usize = 8;
+const R_P: usize = 63;
+
+struct Constants {
+    start: Vec<[Fr; T]>,
+    partial: Vec<Fr>,
+    end: Vec<[Fr; T]>,
+    mds: [[Fr; T]; T],
+    pre_sparse_mds: [[Fr; T]; T],
+    /// The first row and the rest of the first column of each sparse MDS matrix.
+    sparse_mds: Vec<([Fr; T], [Fr; RATE])>,
+}
+
+lazy_static::lazy_static! {
+    static ref CONSTANTS: Constants = {
+        let spec = Spec::<Fr, T, RATE>::new(R_F, R_P);
+        let constants = spec.constants();
+        let matrices = spec.mds_matrices();
+        Constants {
+            start: constants.start().clone(),
+            partial: constants.partial().clone(),
+            end: constants.end().clone(),
+            mds: matrices.mds().rows(),
+            pre_sparse_mds: matrices.pre_sparse_mds().rows(),
+            sparse_mds: matrices
+                .sparse_matrices()
+                .iter()
+                .map(|matrix| (*matrix.row(), *matrix.col_hat()))
+                .collect(),
+        }
+    };
+    /// 2^128, to read 4 limbs as a field element.
+    static ref TWO_128: Fr = Fr::from_u128(1 << 64).square();
+}
+
+/// A sponge of rate 8 absorbing `Fr` elements, padded as described in the `hash` module.
+#[derive(Clone)]
+pub struct NativePoseidon {
+    state: [Fr; T],
+    /// The elements of the block being filled.
+    block: [Fr; RATE],
+    len: usize,
+}
+
+impl NativePoseidon {
+    pub fn new() -> Self {
+        let mut state = [Fr::zero(); T];
+        state[0] = Fr::from_u128(1 << 64);
+        Self {
+            state,
+            block: [Fr::zero(); RATE],
+            len: 0,
+        }
+    }
+
+    pub fn update(&mut self, element: Fr) {
+        self.block[self.len] = element;
+        self.len += 1;
+        if self.len == RATE {
+            for (word, element) in self.state[1..].iter_mut().zip(self.block.iter()) {
+                *word += *element;
+            }
+            permute(&mut self.state);
+            self.len = 0;
+        }
+    }
+
+    /// Append a 1 and zeros up to the end of the block, the hash is the first rate element.
+    pub fn finalize(mut self) -> Fr {
+        self.update(Fr::one());
+        while self.len != 0 {
+            self.update(Fr::zero());
+        }
+        self.state[1]
+    }
+}
+
+impl Default for NativePoseidon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The little endian 256 bit integer of `limbs`, modulo the field.
+pub fn element_from_limbs(limbs: &[u64; 4]) -> Fr {
+    let low = Fr::from_u128(limbs[0] as u128 | (limbs[1] as u128) << 64);
+    let high = Fr::from_u128(limbs[2] as u128 | (limbs[3] as u128) << 64);
+    low + high * *TWO_128
+}
+
+pub fn element_to_limbs(element: &Fr) -> [u64; 4] {
+    let repr = element.to_repr();
+    let mut limbs = [0u64; 4];
+    for (limb, bytes) in limbs.iter_mut().zip(repr.as_ref().chunks(8)) {
+        *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    limbs
+}
+
+fn permute(state: &mut [Fr; T]) {
+    let constants = &*CONSTANTS;
+    let half = R_F / 2;
+
+    add_constants(state, &constants.start[0]);
+    for round_constants in constants.start.iter().skip(1).take(half - 1) {
+        sbox_full(state);
+        add_constants(state, round_constants);
+        apply_mds(state, &constants.mds);
+    }
+    sbox_full(state);
+    add_constants(state, constants.start.last().unwrap());
+    apply_mds(state, &constants.pre_sparse_mds);
+
+    for (constant, (row, col_hat)) in constants.partial.iter().zip(&constants.sparse_mds) {
+        state[0] = pow5(state[0]);
+        state[0] += *constant;
+        apply_sparse_mds(state, row, col_hat);
+    }
+
+    for round_constants in constants.end.iter() {
+        sbox_full(state);
+        add_constants(state, round_constants);
+        apply_mds(state, &constants.mds);
+    }
+    sbox_full(state);
+    apply_mds(state, &constants.mds);
+}
+
+fn pow5(x: Fr) -> Fr {
+    x.square().square() * x
+}
+
+fn sbox_full(state: &mut [Fr; T]) {
+    for word in state.iter_mut() {
+        *word = pow5(*word);
+    }
+}
+
+fn add_constants(state: &mut [Fr; T], constants: &[Fr; T]) {
+    for (word, constant) in state.iter_mut().zip(constants) {
+        *word += *constant;
+    }
+}
+
+fn apply_mds(state: &mut [Fr; T], mds: &[[Fr; T]; T]) {
+    let mut mixed = [Fr::zero(); T];
+    for (word, row) in mixed.iter_mut().zip(mds) {
+        for (cell, word_in) in row.iter().zip(state.iter()) {
+            *word += *cell * *word_in;
+        }
+    }
+    *state = mixed;
+}
+
+fn apply_sparse_mds(state: &mut [Fr; T], row: &[Fr; T], col_hat: &[Fr; RATE]) {
+    let first = state[0];
+    let mut mixed = Fr::zero();
+    for (cell, word) in row.iter().zip(state.iter()) {
+        mixed += *cell * *word;
+    }
+    for (word, cell) in state[1..].iter_mut().zip(col_hat) {
+        *word += *cell * first;
+    }
+    state[0] = mixed;
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::other_test::Bencher;
+    use crate::zkw::{PoseidonHasher, ZkwPoseidonHasher};
+
+    fn random_limbs(n: usize) -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..n).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_matches_zkw_hasher() {
+        let mut inputs: Vec<Vec<u64>> = (0..80).map(random_limbs).collect();
+        // limbs above the modulus are reduced the same way
+        inputs.push(vec![u64::MAX; 12]);
+        for limbs in inputs {
+            let mut native = PoseidonHasher::new();
+            let mut zkw = ZkwPoseidonHasher::new();
+            for limb in &limbs {
+                native.update(*limb);
+                zkw.update(*limb);
+            }
+            assert_eq!(native.finalize(), zkw.finalize(), "{:?}", limbs);
+        }
+    }
+
+    #[test]
+    fn test_hash_matches_zkw_hasher() {
+        for limbs in (0..20).map(random_limbs) {
+            for padding in [false, true] {
+                assert_eq!(
+                    PoseidonHasher::hash(&limbs, padding),
+                    ZkwPoseidonHasher::hash(&limbs, padding)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_element_limbs() {
+        let limbs = [1, 2, 3, 4];
+        assert_eq!(element_to_limbs(&element_from_limbs(&limbs)), limbs);
+        let modulus = element_from_limbs(&crate::zkw::MODULUS);
+        assert_eq!(modulus, Fr::zero());
+
+        let mut poseidon = NativePoseidon::new();
+        poseidon.update(Fr::one());
+        poseidon.update(Fr::from_u128(2));
+        assert_eq!(
+            element_to_limbs(&poseidon.finalize()),
+            crate::hash::hash2(&1u64, &2u64).0
+        );
+    }
+
+    #[bench]
+    fn bench_native_poseidon(b: &mut Bencher) {
+        let limbs = random_limbs(48);
+        b.iter(|| {
+            let mut hasher = PoseidonHasher::new();
+            for limb in &limbs {
+                hasher.update(*limb);
+            }
+            hasher.finalize()
+        })
+    }
+
+    #[bench]
+    fn bench_zkw_poseidon(b: &mut Bencher) {
+        let limbs = random_limbs(48);
+        b.iter(|| {
+            let mut hasher = ZkwPoseidonHasher::new();
+            for limb in &limbs {
+                hasher.update(*limb);
+            }
+            hasher.finalize()
+        })
+    }
+}